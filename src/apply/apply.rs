@@ -0,0 +1,223 @@
+use std::fs;
+
+use serde_json::{Value, json};
+
+use crate::cli::args::ApplyArgs;
+use crate::normalize::rules::normalize_value;
+use crate::output::json::{CommandOutput, ProfileRef};
+use crate::refusal::RefusalPayload;
+use crate::resolve::resolver::resolve_profile;
+use crate::schema::{
+    ColumnEquivalence, ColumnType, NormalizeRule, build_header_index, canonicalize_header_sequence,
+    load_column_registry_aliases, resolve_profile_columns, resolve_registry_path,
+};
+use crate::slice::{CsvSink, DatasetRows, ReadOptions};
+use crate::stats::infer::infer_value;
+use crate::witness::append::append_for_command;
+
+struct ScopedColumn<'a> {
+    name: &'a str,
+    source_header: String,
+    source_index: usize,
+    rule: Option<&'a NormalizeRule>,
+    declared: Option<ColumnType>,
+    equivalence: ColumnEquivalence,
+}
+
+pub fn run(
+    args: &ApplyArgs,
+    no_witness: bool,
+    explicit: bool,
+    json_output: bool,
+) -> Result<CommandOutput, RefusalPayload> {
+    let resolved = resolve_profile(&args.profile)?;
+    let profile = &resolved.profile;
+    if !profile.is_frozen() {
+        return Err(RefusalPayload::invalid_schema_single(
+            "status",
+            "apply requires a frozen profile",
+        ));
+    }
+
+    let rows = DatasetRows::open(&args.dataset, ReadOptions::from_profile(profile))?;
+    let headers = rows.headers().clone();
+
+    let column_aliases = profile
+        .column_registry
+        .as_deref()
+        .map(|registry| {
+            load_column_registry_aliases(&resolve_registry_path(&resolved.path, registry))
+        })
+        .transpose()?;
+    let available = build_header_index(&headers, column_aliases.as_ref());
+
+    let header_names = canonicalize_header_sequence(&headers, column_aliases.as_ref());
+    let selection = resolve_profile_columns(profile, &header_names, &available);
+    let missing = selection
+        .missing
+        .iter()
+        .cloned()
+        .chain(
+            selection
+                .unmatched_selectors()
+                .filter(|selector| selector.list == "include_columns")
+                .map(|selector| selector.selector.clone()),
+        )
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        return Err(RefusalPayload::column_not_found(
+            missing,
+            available.available(),
+        ));
+    }
+    let columns = selection
+        .columns
+        .iter()
+        .filter_map(|name| {
+            let index = available.column_index(name)?;
+            Some(ScopedColumn {
+                name,
+                source_header: headers.get(index).unwrap_or_default().to_string(),
+                source_index: index,
+                rule: profile.normalize.get(name),
+                declared: profile.column_types.get(name).copied(),
+                equivalence: profile.equivalence_for(name),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let keep_csv = explicit || (!json_output && args.out.is_none());
+    let mut writer = csv::Writer::from_writer(CsvSink::open(args.out.as_deref(), keep_csv)?);
+    writer.write_record(&selection.columns).map_err(|error| {
+        RefusalPayload::csv_parse("apply", format!("failed to write header: {error}"))
+    })?;
+
+    let mut row_count = 0usize;
+    for record in rows {
+        let record = record?;
+        row_count += 1;
+        let cells = columns
+            .iter()
+            .map(|column| {
+                let raw = record.get(column.source_index).unwrap_or_default();
+                let trim = column.equivalence.trim_strings.unwrap_or(false);
+                let value = match column.rule {
+                    Some(rule) => normalize_value(rule, raw, trim).0,
+                    None if trim => raw.trim().to_string(),
+                    None => raw.to_string(),
+                };
+                let value = match column.equivalence.float_decimals {
+                    Some(decimals) => round_float(value, decimals, column.declared),
+                    None => value,
+                };
+                if column.equivalence.case_insensitive == Some(true) {
+                    value.to_lowercase()
+                } else {
+                    value
+                }
+            })
+            .collect::<Vec<_>>();
+        writer.write_record(&cells).map_err(|error| {
+            RefusalPayload::csv_parse("apply", format!("failed to write row: {error}"))
+        })?;
+    }
+
+    writer.flush().map_err(|error| {
+        RefusalPayload::io("apply", format!("failed to flush output CSV: {error}"))
+    })?;
+    let (output_hash, csv_bytes) = writer
+        .into_inner()
+        .map_err(|error| {
+            RefusalPayload::io("apply", format!("failed to finalize output CSV: {error}"))
+        })?
+        .finish()?;
+
+    let mut result = json!({
+        "input_path": args.dataset.display().to_string(),
+        "output_path": args.out.as_ref().map(|path| path.display().to_string()),
+        "manifest_path": args.emit_manifest.as_ref().map(|path| path.display().to_string()),
+        "profile_id": profile.profile_id.clone(),
+        "profile_sha256": profile.profile_sha256.clone(),
+        "rows": row_count,
+        "columns": &selection.columns,
+        "output_hash": output_hash
+    });
+
+    let witness_result = result.clone();
+    if let Some(csv_bytes) = csv_bytes {
+        result["scoped_csv"] = Value::String(String::from_utf8(csv_bytes).map_err(|error| {
+            RefusalPayload::invalid_schema_single(
+                "apply",
+                format!("scoped output was not valid UTF-8: {error}"),
+            )
+        })?);
+    }
+    let witness_id = append_for_command(
+        "apply",
+        &witness_result,
+        vec![args.dataset.clone(), resolved.path.clone()],
+        json!({
+            "subcommand": "apply",
+            "profile": &args.profile
+        }),
+        no_witness,
+    );
+
+    if let Some(manifest_path) = args.emit_manifest.as_deref() {
+        let manifest = json!({
+            "schema": "profile.apply_manifest.v1",
+            "input_path": args.dataset.display().to_string(),
+            "profile_id": profile.profile_id.clone(),
+            "profile_sha256": profile.profile_sha256.clone(),
+            "profile_path": resolved.path.display().to_string(),
+            "fingerprint_ref": profile.fingerprint_ref.clone(),
+            "column_registry": profile.column_registry.clone(),
+            "selectors": &selection.selectors,
+            "columns": columns
+                .iter()
+                .map(|column| json!({
+                    "column": column.name,
+                    "source_header": column.source_header,
+                    "source_index": column.source_index,
+                    "normalized": column.rule.is_some(),
+                    "equivalence": column.equivalence
+                }))
+                .collect::<Vec<_>>(),
+            "output_data_rows": row_count,
+            "output_hash": output_hash,
+            "witness_id": witness_id
+        });
+        let manifest_bytes = serde_json::to_vec_pretty(&manifest).map_err(|error| {
+            RefusalPayload::invalid_schema_single(
+                "manifest",
+                format!("failed to serialize apply manifest: {error}"),
+            )
+        })?;
+        fs::write(manifest_path, manifest_bytes).map_err(|error| {
+            RefusalPayload::io(manifest_path.display().to_string(), error.to_string())
+        })?;
+    }
+
+    Ok(CommandOutput::success(result)
+        .with_profile_ref(ProfileRef::from_profile(profile))
+        .with_witness_id(witness_id))
+}
+
+/// Round decimal values to `decimals` places. Integers are left alone unless the column is
+/// declared `decimal`, so `1` and `1.0` agree there but integer IDs keep their shape.
+fn round_float(value: String, decimals: u32, declared: Option<ColumnType>) -> String {
+    let numeric = match infer_value(&value) {
+        ColumnType::Decimal => true,
+        ColumnType::Integer => declared == Some(ColumnType::Decimal),
+        _ => false,
+    };
+    let Some(parsed) = numeric.then(|| value.trim().parse::<f64>().ok()).flatten() else {
+        return value;
+    };
+
+    let rounded = format!("{:.*}", decimals as usize, parsed);
+    match rounded.strip_prefix('-') {
+        Some(unsigned) if unsigned.chars().all(|ch| ch == '0' || ch == '.') => unsigned.to_string(),
+        _ => rounded,
+    }
+}
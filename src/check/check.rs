@@ -0,0 +1,373 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use regex::Regex;
+use serde_json::{Value, json};
+
+use crate::cli::args::CheckArgs;
+use crate::lint::violations::{RowViolation, ViolationExport};
+use crate::output::json::{CommandOutput, ProfileRef};
+use crate::refusal::RefusalPayload;
+use crate::resolve::resolver::resolve_profile;
+use crate::schema::{
+    ColumnConstraints, ColumnRule, build_header_index, compile_pattern,
+    load_column_registry_aliases, resolve_registry_path,
+};
+use crate::slice::{DatasetRows, ReadOptions};
+use crate::stats::infer::parse_temporal;
+use crate::witness::append::append_for_command;
+
+const SAMPLE_LIMIT: usize = 5;
+
+/// Column constraints in the order their issues are reported.
+const COLUMN_CONSTRAINTS: [&str; 5] = ["not_null", "unique", "allowed_values", "range", "pattern"];
+
+/// Rows breaking one constraint: how many, and the first few row numbers and offending values.
+#[derive(Debug, Default)]
+struct Violations {
+    rows: usize,
+    sample_rows: Vec<usize>,
+    samples: Vec<Value>,
+}
+
+impl Violations {
+    fn record(&mut self, row: usize, sample: impl FnOnce() -> Value) {
+        self.rows += 1;
+        if self.sample_rows.len() < SAMPLE_LIMIT {
+            self.sample_rows.push(row);
+            self.samples.push(sample());
+        }
+    }
+
+    fn issue(&self, mut issue: Value, row_count: usize, explicit: bool) -> Value {
+        let percent = self.rows as f64 / row_count as f64 * 100.0;
+        issue["severity"] = json!("error");
+        issue["violating_rows"] = json!(self.rows);
+        issue["violating_percent"] = json!((percent * 100.0).round() / 100.0);
+        issue["sample_rows"] = json!(self.sample_rows);
+        if explicit {
+            issue["samples"] = json!(self.samples);
+        }
+        issue
+    }
+}
+
+struct ColumnCheck<'a> {
+    name: &'a str,
+    index: usize,
+    constraint: &'a ColumnConstraints,
+    trim: bool,
+    case_insensitive: bool,
+    pattern: Option<Regex>,
+    seen: HashSet<String>,
+    /// One entry per `COLUMN_CONSTRAINTS` name.
+    violations: [Violations; 5],
+}
+
+impl ColumnCheck<'_> {
+    /// Check one cell, returning which `COLUMN_CONSTRAINTS` it broke and the value checked.
+    fn observe<'c>(&mut self, row: usize, cell: &'c str) -> ([bool; 5], &'c str) {
+        let value = if self.trim { cell.trim() } else { cell };
+        let broken = self.broken(value);
+        for (violations, _) in self
+            .violations
+            .iter_mut()
+            .zip(broken)
+            .filter(|(_, broken)| *broken)
+        {
+            violations.record(row, || json!(value));
+        }
+        (broken, value)
+    }
+
+    fn broken(&mut self, value: &str) -> [bool; 5] {
+        let constraint = self.constraint;
+        if value.trim().is_empty() {
+            return [
+                constraint.not_null == Some(true),
+                false,
+                false,
+                false,
+                false,
+            ];
+        }
+
+        let duplicate = constraint.unique == Some(true) && {
+            let key = if self.case_insensitive {
+                value.to_lowercase()
+            } else {
+                value.to_string()
+            };
+            !self.seen.insert(key)
+        };
+        let disallowed = !constraint.allowed_values.is_empty()
+            && !constraint.allowed_values.iter().any(|allowed| {
+                allowed == value
+                    || (self.case_insensitive && allowed.to_lowercase() == value.to_lowercase())
+            });
+        let out_of_range = (constraint.min.is_some() || constraint.max.is_some())
+            && !parse_number(value).is_some_and(|number| {
+                constraint.min.is_none_or(|min| number >= min)
+                    && constraint.max.is_none_or(|max| number <= max)
+            });
+        let mismatched = self
+            .pattern
+            .as_ref()
+            .is_some_and(|pattern| !pattern.is_match(value));
+        [false, duplicate, disallowed, out_of_range, mismatched]
+    }
+
+    fn issues(&self, row_count: usize, explicit: bool) -> impl Iterator<Item = Value> + '_ {
+        COLUMN_CONSTRAINTS
+            .iter()
+            .zip(&self.violations)
+            .filter(|(_, violations)| violations.rows > 0)
+            .map(move |(constraint, violations)| {
+                violations.issue(
+                    json!({
+                        "kind": "constraint_violation",
+                        "column": self.name,
+                        "constraint": constraint
+                    }),
+                    row_count,
+                    explicit,
+                )
+            })
+    }
+}
+
+struct RuleCheck {
+    rule: ColumnRule,
+    left: usize,
+    right: usize,
+    violations: Violations,
+}
+
+impl RuleCheck {
+    /// Whether the row breaks the rule. Rows with a null on either side are left to
+    /// `not_null`.
+    fn observe(&mut self, row: usize, left: &str, right: &str) -> bool {
+        let (left, right) = (left.trim(), right.trim());
+        if left.is_empty() || right.is_empty() {
+            return false;
+        }
+        let broken = !self.rule.comparison.holds(compare_cells(left, right));
+        if broken {
+            self.violations
+                .record(row, || json!({ "left": left, "right": right }));
+        }
+        broken
+    }
+}
+
+pub fn run(
+    args: &CheckArgs,
+    no_witness: bool,
+    explicit: bool,
+) -> Result<CommandOutput, RefusalPayload> {
+    let resolved = resolve_profile(&args.profile)?;
+    let profile = resolved.profile;
+    let Some(constraints) = profile.constraints.as_ref() else {
+        return Err(RefusalPayload::missing_field("constraints"));
+    };
+
+    let rows = DatasetRows::open(&args.dataset, ReadOptions::from_profile(&profile))?;
+    let headers = rows.headers().clone();
+    let column_aliases = profile
+        .column_registry
+        .as_deref()
+        .map(|registry| {
+            load_column_registry_aliases(&resolve_registry_path(&resolved.path, registry))
+        })
+        .transpose()?;
+    let available = build_header_index(&headers, column_aliases.as_ref());
+
+    let mut issues = Vec::new();
+    let mut missing = HashSet::new();
+    let mut report_missing = |column: &str, issues: &mut Vec<Value>| {
+        if missing.insert(column.to_string()) {
+            issues.push(json!({
+                "kind": "missing_column",
+                "column": column,
+                "severity": "error"
+            }));
+        }
+    };
+
+    let mut columns = Vec::with_capacity(constraints.columns.len());
+    for (name, constraint) in &constraints.columns {
+        let Some(index) = available.column_index(name) else {
+            report_missing(name, &mut issues);
+            continue;
+        };
+        let equivalence = profile.equivalence_for(name);
+        columns.push(ColumnCheck {
+            name,
+            index,
+            constraint,
+            trim: equivalence.trim_strings.unwrap_or(false),
+            case_insensitive: equivalence.case_insensitive.unwrap_or(false),
+            pattern: constraint
+                .pattern
+                .as_deref()
+                .map(compile_pattern)
+                .transpose()
+                .map_err(|error| RefusalPayload::invalid_schema_single("constraints", error))?,
+            seen: HashSet::new(),
+            violations: Default::default(),
+        });
+    }
+
+    let mut rules = Vec::with_capacity(constraints.rules.len());
+    for rule in &constraints.rules {
+        let rule = ColumnRule::parse(rule)
+            .map_err(|error| RefusalPayload::invalid_schema_single("constraints.rules", error))?;
+        let (left, right) = (
+            available.column_index(&rule.left),
+            available.column_index(&rule.right),
+        );
+        let (Some(left), Some(right)) = (left, right) else {
+            for (column, index) in [(&rule.left, left), (&rule.right, right)] {
+                if index.is_none() {
+                    report_missing(column, &mut issues);
+                }
+            }
+            continue;
+        };
+        rules.push(RuleCheck {
+            rule,
+            left,
+            right,
+            violations: Violations::default(),
+        });
+    }
+
+    let mut export = args
+        .emit_violations
+        .as_deref()
+        .map(|path| {
+            let key = profile
+                .key
+                .iter()
+                .map(|column| Some((column.clone(), available.column_index(column)?)))
+                .collect::<Option<Vec<_>>>()
+                .unwrap_or_default();
+            ViolationExport::open(path, key, explicit)
+        })
+        .transpose()?;
+
+    let mut row_count = 0usize;
+    for record in rows {
+        let record = record?;
+        row_count += 1;
+        for column in &mut columns {
+            let (broken, value) =
+                column.observe(row_count, record.get(column.index).unwrap_or_default());
+            let Some(export) = export.as_mut() else {
+                continue;
+            };
+            for (constraint, _) in COLUMN_CONSTRAINTS
+                .iter()
+                .zip(broken)
+                .filter(|(_, broken)| *broken)
+            {
+                export.record(
+                    row_count,
+                    &record,
+                    RowViolation {
+                        kind: "constraint_violation",
+                        column: Some(column.name),
+                        rule: constraint,
+                        value,
+                    },
+                )?;
+            }
+        }
+        for rule in &mut rules {
+            let (left, right) = (
+                record.get(rule.left).unwrap_or_default(),
+                record.get(rule.right).unwrap_or_default(),
+            );
+            if rule.observe(row_count, left, right)
+                && let Some(export) = export.as_mut()
+            {
+                export.record(
+                    row_count,
+                    &record,
+                    RowViolation {
+                        kind: "rule_violation",
+                        column: None,
+                        rule: &rule.rule.rule,
+                        value: &format!(
+                            "{} {} {}",
+                            left.trim(),
+                            rule.rule.comparison.as_str(),
+                            right.trim()
+                        ),
+                    },
+                )?;
+            }
+        }
+    }
+
+    for column in &columns {
+        issues.extend(column.issues(row_count, explicit));
+    }
+    for rule in rules.iter().filter(|rule| rule.violations.rows > 0) {
+        issues.push(rule.violations.issue(
+            json!({
+                "kind": "rule_violation",
+                "rule": rule.rule.rule,
+                "columns": [rule.rule.left, rule.rule.right]
+            }),
+            row_count,
+            explicit,
+        ));
+    }
+
+    let mut result = json!({
+        "rows": row_count,
+        "checked": {
+            "columns": columns.len(),
+            "rules": rules.len()
+        },
+        "issues": issues
+    });
+    if let Some(export) = export {
+        result["violations"] = export.finish()?;
+    }
+    let witness_id = append_for_command(
+        "check",
+        &result,
+        vec![args.dataset.clone(), resolved.path.clone()],
+        json!({
+            "subcommand": "check",
+            "profile": args.profile
+        }),
+        no_witness,
+    );
+
+    Ok(CommandOutput::success(result)
+        .with_profile_ref(ProfileRef::from_profile(&profile))
+        .with_witness_id(witness_id))
+}
+
+fn parse_number(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+/// Order two non-null cells as numbers when both parse as numbers, as instants when both parse
+/// as dates or datetimes, and as strings otherwise.
+fn compare_cells(left: &str, right: &str) -> Ordering {
+    if let (Some(left), Some(right)) = (parse_number(left), parse_number(right)) {
+        return left.total_cmp(&right);
+    }
+    if let (Some((left, _)), Some((right, _))) = (parse_temporal(left), parse_temporal(right)) {
+        return left.cmp(&right);
+    }
+    left.cmp(right)
+}
@@ -1,3 +1,4 @@
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
@@ -52,12 +53,20 @@ pub enum Command {
     Validate(ValidateArgs),
     /// Validate a profile and check column presence against a dataset
     Lint(LintArgs),
+    /// Check a dataset against the constraints declared in a profile
+    Check(CheckArgs),
     /// Apply witnessed pre-parse slicing to a CSV-like dataset
     Slice(SliceArgs),
+    /// Apply profile normalization rules and emit a normalized CSV
+    Normalize(NormalizeArgs),
+    /// Emit a dataset scoped to a frozen profile's columns and equivalence rules
+    Apply(ApplyArgs),
     /// Show structural statistics for a dataset
     Stats(StatsArgs),
     /// Rank candidate key columns by uniqueness
     SuggestKey(SuggestKeyArgs),
+    /// Compare a dataset's statistics against a saved baseline
+    Drift(DriftArgs),
     /// Freeze a draft into an immutable, content-addressed profile
     Freeze(FreezeArgs),
     /// List available frozen profiles
@@ -132,6 +141,11 @@ pub enum DraftCommand {
 #[derive(Debug, Clone, ValueEnum)]
 pub enum DatasetFormat {
     Csv,
+    Xlsx,
+    Parquet,
+    Jsonl,
+    #[value(name = "fixed_width")]
+    FixedWidth,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -147,16 +161,20 @@ pub struct DraftNewArgs {
 
 #[derive(Debug, Clone, Args)]
 pub struct DraftInitArgs {
-    /// Path to the dataset to read headers from
+    /// Path to the dataset to read headers from (`-` reads stdin)
     pub dataset: PathBuf,
 
     /// Output path for the draft profile YAML
     #[arg(long)]
     pub out: PathBuf,
 
-    /// Dataset format
-    #[arg(long, value_enum, default_value_t = DatasetFormat::Csv)]
-    pub format: DatasetFormat,
+    /// Dataset format; inferred from the file extension when omitted
+    #[arg(long, value_enum)]
+    pub format: Option<DatasetFormat>,
+
+    /// Worksheet to read from an xlsx workbook (defaults to the first sheet)
+    #[arg(long)]
+    pub sheet: Option<String>,
 
     /// Key column name, or "auto" for automatic detection
     #[arg(long)]
@@ -169,6 +187,14 @@ pub struct DraftInitArgs {
     /// JSON output from `fingerprint peek --suggest` used to seed pre_parse directives
     #[arg(long = "from-peek")]
     pub from_peek: Option<PathBuf>,
+
+    /// Scan all data rows and seed column_types with inferred value types
+    #[arg(long = "infer-types")]
+    pub infer_types: bool,
+
+    /// JSONL records scanned to discover field paths
+    #[arg(long = "path-sample", default_value_t = 1000)]
+    pub path_sample: usize,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -182,14 +208,32 @@ pub struct LintArgs {
     /// Path to the profile YAML to lint
     pub profile: PathBuf,
 
-    /// Path to the dataset to check columns against
+    /// Path to the dataset to check columns against (`-` reads stdin)
     #[arg(long)]
     pub against: PathBuf,
+
+    /// Write offending rows to this CSV (or `.jsonl`) report
+    #[arg(long, value_name = "PATH")]
+    pub emit_violations: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct CheckArgs {
+    /// Path to the dataset to check (`-` reads stdin)
+    pub dataset: PathBuf,
+
+    /// Profile path or ID whose `constraints` to check
+    #[arg(long)]
+    pub profile: String,
+
+    /// Write offending rows to this CSV (or `.jsonl`) report
+    #[arg(long, value_name = "PATH")]
+    pub emit_violations: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Args)]
 pub struct SliceArgs {
-    /// Path to the dataset to slice
+    /// Path to the dataset to slice (`-` reads stdin)
     pub file: PathBuf,
 
     /// Frozen or draft profile ID/path with pre_parse directives
@@ -236,10 +280,14 @@ pub struct SliceArgs {
     #[arg(long)]
     pub delimiter: Option<String>,
 
-    /// Encoding label. Only utf-8 is supported.
+    /// Source encoding label (e.g. utf-8, windows-1252, latin1, utf-16le); a BOM overrides it
     #[arg(long)]
     pub encoding: Option<String>,
 
+    /// Worksheet to slice from an xlsx workbook; overrides the profile's sheet
+    #[arg(long)]
+    pub sheet: Option<String>,
+
     /// Output CSV path. If omitted in human mode, clean CSV is written to stdout.
     #[arg(long)]
     pub out: Option<PathBuf>,
@@ -265,24 +313,94 @@ pub enum HeaderMergeStrategyArg {
     FirstNonEmpty,
 }
 
+#[derive(Debug, Clone, Args)]
+pub struct NormalizeArgs {
+    /// Path to the dataset to normalize
+    pub dataset: PathBuf,
+
+    /// Profile YAML whose normalize rules (and pre_parse directives) are applied
+    #[arg(long)]
+    pub profile: PathBuf,
+
+    /// Output CSV path. If omitted in human mode, normalized CSV is written to stdout.
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ApplyArgs {
+    /// Path to the dataset to scope
+    pub dataset: PathBuf,
+
+    /// Frozen profile ID or path
+    #[arg(long)]
+    pub profile: String,
+
+    /// Output CSV path. If omitted in human mode, scoped CSV is written to stdout.
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+
+    /// Optional JSON lineage manifest path
+    #[arg(long = "emit-manifest")]
+    pub emit_manifest: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone, Args)]
 pub struct StatsArgs {
-    /// Path to the dataset to analyze
+    /// Path to the dataset to analyze (`-` reads stdin)
     pub dataset: PathBuf,
 
     /// Scope stats to columns in this profile
     #[arg(long)]
     pub profile: Option<PathBuf>,
+
+    /// Estimate distinct counts with a HyperLogLog sketch instead of holding every value
+    #[arg(long)]
+    pub approx: bool,
+
+    /// Analyze a seeded reservoir sample of this many rows instead of every row
+    #[arg(long, value_name = "ROWS")]
+    pub sample: Option<NonZeroUsize>,
+
+    /// Write a statistics baseline for `profile drift`, tied to the frozen `--profile`
+    #[arg(long, value_name = "PATH")]
+    pub save_baseline: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct DriftArgs {
+    /// Path to the dataset to compare (`-` reads stdin)
+    pub dataset: PathBuf,
+
+    /// Baseline written by `stats --save-baseline`
+    #[arg(long)]
+    pub baseline: PathBuf,
+
+    /// Profile path or ID to read the dataset with; defaults to the baseline's profile
+    #[arg(long)]
+    pub profile: Option<String>,
 }
 
 #[derive(Debug, Clone, Args)]
 pub struct SuggestKeyArgs {
-    /// Path to the dataset to analyze
+    /// Path to the dataset to analyze (`-` reads stdin)
     pub dataset: PathBuf,
 
     /// Number of top candidates to return
     #[arg(long, default_value_t = 5)]
     pub top: usize,
+
+    /// Read the dataset through this profile's pre_parse directives
+    #[arg(long)]
+    pub profile: Option<PathBuf>,
+
+    /// Estimate distinct counts with a HyperLogLog sketch instead of holding every value
+    #[arg(long)]
+    pub approx: bool,
+
+    /// Analyze a seeded reservoir sample of this many rows instead of every row
+    #[arg(long, value_name = "ROWS")]
+    pub sample: Option<NonZeroUsize>,
 }
 
 #[derive(Debug, Clone, Args)]
@@ -301,6 +419,10 @@ pub struct FreezeArgs {
     /// Output path for the frozen profile YAML
     #[arg(long)]
     pub out: PathBuf,
+
+    /// Dataset whose header every column selector must match before freezing
+    #[arg(long)]
+    pub reference: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Args, Default)]
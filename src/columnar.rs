@@ -0,0 +1,172 @@
+use std::fs::File;
+use std::path::Path;
+
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDate};
+use parquet::basic::{ConvertedType, LogicalType, Type as PhysicalType};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use parquet::record::reader::RowIter;
+use parquet::schema::types::Type;
+
+use crate::compression::{Compression, is_stdin, read_dataset};
+use crate::refusal::RefusalPayload;
+use crate::schema::ColumnType;
+
+/// Days from 0001-01-01 (chrono's CE day 1) to 1970-01-01.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Whether `path` names a Parquet file by its extension.
+pub(crate) fn is_parquet_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("parquet") || extension.eq_ignore_ascii_case("pq")
+        })
+}
+
+/// Rows of a Parquet file rendered to text cells, decoded one row group at a time.
+pub(crate) struct ParquetRows {
+    rows: RowIter<'static>,
+    path: String,
+}
+
+/// Open a Parquet file and return its top-level column names plus a row stream.
+pub(crate) fn open_parquet(path: &Path) -> Result<(Vec<String>, ParquetRows), RefusalPayload> {
+    let reader = open_reader(path)?;
+    let headers = reader
+        .metadata()
+        .file_metadata()
+        .schema()
+        .get_fields()
+        .iter()
+        .map(|field| field.name().to_owned())
+        .collect();
+    Ok((
+        headers,
+        ParquetRows {
+            rows: RowIter::from_file_into(reader),
+            path: path.display().to_string(),
+        },
+    ))
+}
+
+/// The column type each top-level Parquet column declares through its physical and logical
+/// types; `None` for nested groups and types with no profile equivalent.
+pub(crate) fn schema_column_types(
+    path: &Path,
+) -> Result<Vec<(String, Option<ColumnType>)>, RefusalPayload> {
+    let reader = open_reader(path)?;
+    Ok(reader
+        .metadata()
+        .file_metadata()
+        .schema()
+        .get_fields()
+        .iter()
+        .map(|field| (field.name().to_owned(), declared_type(field)))
+        .collect())
+}
+
+/// Parquet needs random access to its footer, so a compressed file or standard input is read
+/// into memory; a plain file is read from disk as row groups are needed.
+fn open_reader(path: &Path) -> Result<Box<dyn FileReader>, RefusalPayload> {
+    let refusal = |error: parquet::errors::ParquetError| {
+        RefusalPayload::io(path.display().to_string(), error.to_string())
+    };
+    if is_stdin(path) || Compression::for_path(path).is_some() {
+        let bytes = Bytes::from(read_dataset(path)?);
+        return Ok(Box::new(SerializedFileReader::new(bytes).map_err(refusal)?));
+    }
+    let file = File::open(path)
+        .map_err(|error| RefusalPayload::io(path.display().to_string(), error.to_string()))?;
+    Ok(Box::new(SerializedFileReader::new(file).map_err(refusal)?))
+}
+
+fn declared_type(field: &Type) -> Option<ColumnType> {
+    if !field.is_primitive() {
+        return None;
+    }
+    let info = field.get_basic_info();
+    match info.logical_type() {
+        Some(LogicalType::String | LogicalType::Enum | LogicalType::Json) => {
+            return Some(ColumnType::String);
+        }
+        Some(LogicalType::Decimal { .. }) => return Some(ColumnType::Decimal),
+        Some(LogicalType::Date) => return Some(ColumnType::Date),
+        Some(LogicalType::Timestamp { .. }) => return Some(ColumnType::Datetime),
+        Some(LogicalType::Integer { .. }) => return Some(ColumnType::Integer),
+        Some(_) => return None,
+        None => {}
+    }
+    match info.converted_type() {
+        ConvertedType::UTF8 | ConvertedType::ENUM | ConvertedType::JSON => {
+            return Some(ColumnType::String);
+        }
+        ConvertedType::DECIMAL => return Some(ColumnType::Decimal),
+        ConvertedType::DATE => return Some(ColumnType::Date),
+        ConvertedType::INT_8
+        | ConvertedType::INT_16
+        | ConvertedType::INT_32
+        | ConvertedType::INT_64
+        | ConvertedType::UINT_8
+        | ConvertedType::UINT_16
+        | ConvertedType::UINT_32
+        | ConvertedType::UINT_64 => return Some(ColumnType::Integer),
+        ConvertedType::TIMESTAMP_MILLIS | ConvertedType::TIMESTAMP_MICROS => {
+            return Some(ColumnType::Datetime);
+        }
+        ConvertedType::NONE => {}
+        _ => return None,
+    }
+    match field.get_physical_type() {
+        PhysicalType::BOOLEAN => Some(ColumnType::Boolean),
+        PhysicalType::INT32 | PhysicalType::INT64 => Some(ColumnType::Integer),
+        PhysicalType::FLOAT | PhysicalType::DOUBLE => Some(ColumnType::Decimal),
+        PhysicalType::INT96 => Some(ColumnType::Datetime),
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => None,
+    }
+}
+
+impl Iterator for ParquetRows {
+    type Item = Result<Vec<String>, RefusalPayload>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = match self.rows.next()? {
+            Ok(row) => row,
+            Err(error) => {
+                return Some(Err(RefusalPayload::io(
+                    self.path.clone(),
+                    error.to_string(),
+                )));
+            }
+        };
+        Some(Ok(row
+            .get_column_iter()
+            .map(|(_, field)| field_text(field))
+            .collect()))
+    }
+}
+
+/// Render a value the way it would read in a CSV export: nulls empty, strings unquoted, and
+/// dates and timestamps as ISO 8601 so type inference sees them as such.
+fn field_text(field: &Field) -> String {
+    match field {
+        Field::Null => String::new(),
+        Field::Str(value) => value.clone(),
+        Field::Bytes(value) => String::from_utf8_lossy(value.data()).into_owned(),
+        Field::Float(value) => value.to_string(),
+        Field::Double(value) => value.to_string(),
+        Field::Date(days) => days
+            .checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+            .and_then(NaiveDate::from_num_days_from_ce_opt)
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| days.to_string()),
+        Field::TimestampMillis(millis) => DateTime::from_timestamp_millis(*millis)
+            .map(|timestamp| timestamp.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+            .unwrap_or_else(|| millis.to_string()),
+        Field::TimestampMicros(micros) => DateTime::from_timestamp_micros(*micros)
+            .map(|timestamp| timestamp.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+            .unwrap_or_else(|| micros.to_string()),
+        other => other.to_string(),
+    }
+}
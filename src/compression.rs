@@ -0,0 +1,123 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use flate2::read::MultiGzDecoder;
+use zstd::stream::read::Decoder as ZstdDecoder;
+
+use crate::refusal::RefusalPayload;
+
+/// A dataset compression recognized by file extension and undone transparently on read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    pub fn for_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        if extension.eq_ignore_ascii_case("gz") {
+            Some(Self::Gzip)
+        } else if extension.eq_ignore_ascii_case("zst") {
+            Some(Self::Zstd)
+        } else {
+            None
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+
+    pub fn decoder<'a, R: Read + 'a>(self, reader: R) -> io::Result<Box<dyn Read + 'a>> {
+        Ok(match self {
+            Self::Gzip => Box::new(MultiGzDecoder::new(reader)),
+            Self::Zstd => Box::new(ZstdDecoder::new(reader)?),
+        })
+    }
+}
+
+/// A dataset's content as a byte stream, decompressed when its extension says so.
+pub(crate) type DatasetReader = Box<dyn Read>;
+
+/// The dataset path that reads standard input.
+pub(crate) const STDIN_PATH: &str = "-";
+
+pub(crate) fn is_stdin(path: &Path) -> bool {
+    path.as_os_str() == STDIN_PATH
+}
+
+/// Standard input, read to the end on first use so a command can make several passes over it
+/// (encoding sniff, header read, key suggestion) and the witness can hash what was read.
+pub(crate) fn stdin_bytes() -> Result<&'static [u8], RefusalPayload> {
+    static STDIN: OnceLock<Result<Vec<u8>, String>> = OnceLock::new();
+    STDIN
+        .get_or_init(|| {
+            let mut bytes = Vec::new();
+            io::stdin()
+                .lock()
+                .read_to_end(&mut bytes)
+                .map(|_| bytes)
+                .map_err(|error| error.to_string())
+        })
+        .as_deref()
+        .map_err(|error| RefusalPayload::io("stdin", error.clone()))
+}
+
+/// `path` without a trailing `.gz`/`.zst`, so `tape.csv.gz` infers its format from `.csv`.
+pub(crate) fn uncompressed_path(path: &Path) -> Cow<'_, Path> {
+    match Compression::for_path(path) {
+        Some(_) => Cow::Owned(path.with_extension("")),
+        None => Cow::Borrowed(path),
+    }
+}
+
+/// Open a dataset for streaming reads, decompressing `.gz` and `.zst` files on the fly; `-`
+/// reads standard input, which is never decompressed.
+pub(crate) fn open_dataset(path: &Path) -> Result<DatasetReader, RefusalPayload> {
+    if is_stdin(path) {
+        return Ok(Box::new(Cursor::new(stdin_bytes()?)));
+    }
+    let file = File::open(path).map_err(|error| io_refusal(path, error))?;
+    match Compression::for_path(path) {
+        Some(compression) => compression
+            .decoder(BufReader::new(file))
+            .map_err(|error| io_refusal(path, error)),
+        None => Ok(Box::new(file)),
+    }
+}
+
+/// Read a whole dataset into memory, decompressed, for readers that need random access.
+pub(crate) fn read_dataset(path: &Path) -> Result<Vec<u8>, RefusalPayload> {
+    let mut bytes = Vec::new();
+    open_dataset(path)?
+        .read_to_end(&mut bytes)
+        .map_err(|error| io_refusal(path, error))?;
+    Ok(bytes)
+}
+
+/// Decompress bytes already read from `path`; uncompressed files are borrowed as-is.
+pub(crate) fn decompress_bytes<'a>(
+    path: &Path,
+    bytes: &'a [u8],
+) -> Result<Cow<'a, [u8]>, RefusalPayload> {
+    let Some(compression) = Compression::for_path(path) else {
+        return Ok(Cow::Borrowed(bytes));
+    };
+    let mut decompressed = Vec::new();
+    compression
+        .decoder(bytes)
+        .and_then(|mut decoder| decoder.read_to_end(&mut decompressed))
+        .map_err(|error| io_refusal(path, error))?;
+    Ok(Cow::Owned(decompressed))
+}
+
+fn io_refusal(path: &Path, error: io::Error) -> RefusalPayload {
+    RefusalPayload::io(path.display().to_string(), error.to_string())
+}
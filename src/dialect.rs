@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use crate::encoding::{SourceEncoding, open_decoded};
+use crate::refusal::RefusalPayload;
+
+/// Delimiters tried when a profile declares none, in tie-break order.
+const CANDIDATE_DELIMITERS: [u8; 4] = [b',', b'\t', b';', b'|'];
+/// Quote characters tried, in tie-break order; all but the first must open a field in the
+/// sample to be considered, so an unbalanced double quote still fails the parse.
+const CANDIDATE_QUOTES: [u8; 2] = [b'"', b'\''];
+/// Bytes of decoded text sampled from the top of the file.
+const SAMPLE_BYTES: usize = 64 * 1024;
+
+/// How a delimited file splits into records and fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Dialect {
+    pub delimiter: u8,
+    pub quote: u8,
+    pub has_header: bool,
+    /// Whether the delimiter came from `pre_parse.slice.delimiter` rather than the sample.
+    pub delimiter_declared: bool,
+}
+
+impl Dialect {
+    pub fn summary(&self) -> Value {
+        json!({
+            "delimiter": char::from(self.delimiter).to_string(),
+            "quote": char::from(self.quote).to_string(),
+            "has_header": self.has_header,
+            "delimiter_source": if self.delimiter_declared { "declared" } else { "sniffed" }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QuoteState {
+    FieldStart,
+    Unquoted,
+    Quoted,
+    QuoteInQuoted,
+}
+
+/// One step of the quoting state machine shared by the sniffer and the slice reader: a
+/// doubled quote inside a quoted field is an escaped quote, and a newline outside quotes ends
+/// the record.
+pub(crate) fn advance(state: QuoteState, byte: u8, dialect: Dialect) -> QuoteState {
+    match (state, byte) {
+        (QuoteState::Quoted, byte) if byte == dialect.quote => QuoteState::QuoteInQuoted,
+        (QuoteState::Quoted, _) => QuoteState::Quoted,
+        (QuoteState::FieldStart | QuoteState::QuoteInQuoted, byte) if byte == dialect.quote => {
+            QuoteState::Quoted
+        }
+        (_, byte) if byte == dialect.delimiter || byte == b'\n' => QuoteState::FieldStart,
+        _ => QuoteState::Unquoted,
+    }
+}
+
+/// Detect the delimiter, quote character and header presence from the top of the file. A
+/// declared delimiter is kept as-is; header presence is only sniffed when `detect_header` is
+/// set, since slice directives name their header rows explicitly.
+pub(crate) fn sniff_dialect(
+    path: &Path,
+    source: SourceEncoding,
+    declared_delimiter: Option<u8>,
+    detect_header: bool,
+) -> Result<Dialect, RefusalPayload> {
+    let sample = read_sample(path, source)?;
+    let delimiters =
+        declared_delimiter.map_or(CANDIDATE_DELIMITERS.to_vec(), |delimiter| vec![delimiter]);
+
+    let mut best: Option<(usize, Dialect)> = None;
+    for &delimiter in &delimiters {
+        for (rank, quote) in CANDIDATE_QUOTES.into_iter().enumerate() {
+            let dialect = Dialect {
+                delimiter,
+                quote,
+                has_header: true,
+                delimiter_declared: declared_delimiter.is_some(),
+            };
+            if rank > 0 && !opens_quoted_field(&sample, dialect) {
+                continue;
+            }
+            let score = consistency(&split_records(&sample, dialect));
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, dialect));
+            }
+        }
+    }
+    let (_, mut dialect) = best.expect("at least one candidate dialect");
+    if detect_header {
+        dialect.has_header = looks_like_header(&split_records(&sample, dialect));
+    }
+    Ok(dialect)
+}
+
+/// Decoded text up to `SAMPLE_BYTES`, cut back to the last complete line when the file is
+/// longer. Invalid UTF-8 is replaced here and left for the reader to refuse.
+fn read_sample(path: &Path, source: SourceEncoding) -> Result<String, RefusalPayload> {
+    let mut reader = BufReader::new(open_decoded(path, source)?);
+    let mut sample = Vec::new();
+    while sample.len() < SAMPLE_BYTES {
+        let read = reader
+            .read_until(b'\n', &mut sample)
+            .map_err(|error| RefusalPayload::io(path.display().to_string(), error.to_string()))?;
+        if read == 0 {
+            return Ok(String::from_utf8_lossy(&sample).into_owned());
+        }
+    }
+    if let Some(end) = sample.iter().rposition(|&byte| byte == b'\n') {
+        sample.truncate(end + 1);
+    }
+    Ok(String::from_utf8_lossy(&sample).into_owned())
+}
+
+/// Non-blank records of the sample split into fields.
+fn split_records(sample: &str, dialect: Dialect) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = Vec::new();
+    let mut state = QuoteState::FieldStart;
+    for byte in sample.bytes().filter(|&byte| byte != b'\r') {
+        let next = advance(state, byte, dialect);
+        match (state, next) {
+            (_, QuoteState::FieldStart) => {
+                fields.push(String::from_utf8_lossy(&field).into_owned());
+                field.clear();
+                if byte == b'\n' {
+                    records.push(std::mem::take(&mut fields));
+                }
+            }
+            (QuoteState::FieldStart, QuoteState::Quoted)
+            | (QuoteState::Quoted, QuoteState::QuoteInQuoted) => {}
+            _ => field.push(byte),
+        }
+        state = next;
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(String::from_utf8_lossy(&field).into_owned());
+        records.push(fields);
+    }
+    records.retain(|record| !(record.len() == 1 && record[0].trim().is_empty()));
+    records
+}
+
+fn opens_quoted_field(sample: &str, dialect: Dialect) -> bool {
+    let mut state = QuoteState::FieldStart;
+    sample.bytes().any(|byte| {
+        let next = advance(state, byte, dialect);
+        let opened = state == QuoteState::FieldStart && next == QuoteState::Quoted;
+        state = next;
+        opened
+    })
+}
+
+/// Records sharing the most common field count, when that count splits lines at all.
+fn consistency(records: &[Vec<String>]) -> usize {
+    let mut counts = BTreeMap::<usize, usize>::new();
+    for record in records {
+        *counts.entry(record.len()).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .filter(|(width, _)| *width > 1)
+        .map(|(_, records)| records)
+        .max()
+        .unwrap_or(0)
+}
+
+/// A first record is taken as data only when, in columns whose sampled values are all
+/// numeric, its cells are numeric more often than not; otherwise it is a header.
+fn looks_like_header(records: &[Vec<String>]) -> bool {
+    let Some((first, rest)) = records.split_first() else {
+        return true;
+    };
+    let (mut header_votes, mut data_votes) = (0usize, 0usize);
+    for (index, cell) in first.iter().enumerate() {
+        let mut values = rest
+            .iter()
+            .filter_map(|record| record.get(index))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+            .peekable();
+        if values.peek().is_none() || !values.all(is_numeric) {
+            continue;
+        }
+        if is_numeric(cell.trim()) {
+            data_votes += 1;
+        } else {
+            header_votes += 1;
+        }
+    }
+    data_votes <= header_votes
+}
+
+fn is_numeric(value: &str) -> bool {
+    value.replace(',', "").parse::<f64>().is_ok()
+}
@@ -36,6 +36,22 @@ pub fn run(args: &DiffArgs, _no_witness: bool) -> Result<Value, RefusalPayload>
 fn compare_profiles(a: &Profile, b: &Profile) -> Vec<ProfileDifference> {
     let mut differences = Vec::new();
 
+    if a.extends != b.extends {
+        differences.push(ProfileDifference {
+            field: "extends".to_string(),
+            a_value: json!(a.extends),
+            b_value: json!(b.extends),
+        });
+    }
+
+    if a.extends_sha256 != b.extends_sha256 {
+        differences.push(ProfileDifference {
+            field: "extends_sha256".to_string(),
+            a_value: json!(a.extends_sha256),
+            b_value: json!(b.extends_sha256),
+        });
+    }
+
     // Compare format
     if a.format != b.format {
         differences.push(ProfileDifference {
@@ -45,6 +61,22 @@ fn compare_profiles(a: &Profile, b: &Profile) -> Vec<ProfileDifference> {
         });
     }
 
+    if a.sheet != b.sheet {
+        differences.push(ProfileDifference {
+            field: "sheet".to_string(),
+            a_value: json!(a.sheet),
+            b_value: json!(b.sheet),
+        });
+    }
+
+    if a.fixed_width != b.fixed_width {
+        differences.push(ProfileDifference {
+            field: "fixed_width".to_string(),
+            a_value: json!(a.fixed_width),
+            b_value: json!(b.fixed_width),
+        });
+    }
+
     if a.column_registry != b.column_registry {
         differences.push(ProfileDifference {
             field: "column_registry".to_string(),
@@ -105,5 +137,53 @@ fn compare_profiles(a: &Profile, b: &Profile) -> Vec<ProfileDifference> {
         });
     }
 
+    if a.exclude_columns != b.exclude_columns {
+        differences.push(ProfileDifference {
+            field: "exclude_columns".to_string(),
+            a_value: json!(a.exclude_columns),
+            b_value: json!(b.exclude_columns),
+        });
+    }
+
+    if a.column_types != b.column_types {
+        differences.push(ProfileDifference {
+            field: "column_types".to_string(),
+            a_value: json!(a.column_types),
+            b_value: json!(b.column_types),
+        });
+    }
+
+    if a.normalize != b.normalize {
+        differences.push(ProfileDifference {
+            field: "normalize".to_string(),
+            a_value: json!(a.normalize),
+            b_value: json!(b.normalize),
+        });
+    }
+
+    if a.column_equivalence != b.column_equivalence {
+        differences.push(ProfileDifference {
+            field: "column_equivalence".to_string(),
+            a_value: json!(a.column_equivalence),
+            b_value: json!(b.column_equivalence),
+        });
+    }
+
+    if a.drift != b.drift {
+        differences.push(ProfileDifference {
+            field: "drift".to_string(),
+            a_value: json!(a.drift),
+            b_value: json!(b.drift),
+        });
+    }
+
+    if a.constraints != b.constraints {
+        differences.push(ProfileDifference {
+            field: "constraints".to_string(),
+            a_value: json!(a.constraints),
+            b_value: json!(b.constraints),
+        });
+    }
+
     differences
 }
@@ -6,13 +6,15 @@ use serde_json::json;
 use sha2::{Digest, Sha256};
 
 use crate::cli::args::EmitDiscoveryArgs;
+use crate::compression::{decompress_bytes, open_dataset};
 use crate::output::json::CommandOutput;
 use crate::refusal::RefusalPayload;
 
 pub fn run(args: &EmitDiscoveryArgs) -> Result<CommandOutput, RefusalPayload> {
-    let source_bytes = fs::read(&args.source_file).map_err(|error| {
+    let stored_bytes = fs::read(&args.source_file).map_err(|error| {
         RefusalPayload::io(args.source_file.display().to_string(), error.to_string())
     })?;
+    let source_bytes = decompress_bytes(&args.source_file, &stored_bytes)?;
     let source_text = std::str::from_utf8(&source_bytes).map_err(|_| {
         RefusalPayload::csv_parse(
             args.source_file.display().to_string(),
@@ -55,7 +57,7 @@ pub fn run(args: &EmitDiscoveryArgs) -> Result<CommandOutput, RefusalPayload> {
             "column_count": sliced.headers.len(),
             "headers": sliced.headers,
             "evidence": {
-                "source_file_sha256": sha256_prefixed(&source_bytes),
+                "source_file_sha256": sha256_prefixed(&stored_bytes),
                 "lines_scanned": source_lines.len(),
                 "consistent_column_count_below_offset": true,
                 "preamble_lines": preamble_lines,
@@ -77,10 +79,7 @@ fn parse_sliced_csv(path: &Path) -> Result<SlicedCsv, RefusalPayload> {
     let mut reader = ReaderBuilder::new()
         .has_headers(true)
         .flexible(true)
-        .from_path(path)
-        .map_err(|error| {
-            RefusalPayload::csv_parse(path.display().to_string(), error.to_string())
-        })?;
+        .from_reader(open_dataset(path)?);
 
     let headers_record = reader
         .headers()
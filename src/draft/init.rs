@@ -1,43 +1,40 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::fs::File;
 
 use serde::Serialize;
 use serde_json::{Value, json};
 
-use crate::cli::args::{DatasetFormat, DraftInitArgs, SuggestKeyArgs};
+use crate::cli::args::{DatasetFormat, DraftInitArgs};
+use crate::columnar::schema_column_types;
 use crate::refusal::RefusalPayload;
 use crate::schema::{
-    Equivalence, Profile, ProfileFormat, ProfileStatus, ValidationMode,
+    ColumnType, Equivalence, PreParse, Profile, ProfileFormat, ProfileStatus, ValidationMode,
     canonicalize_header_sequence, canonicalize_profile_column, load_column_registry_aliases,
     validate_profile,
 };
+use crate::slice::{DatasetRows, ReadOptions, format_for_path};
+use crate::stats::infer::TypeAccumulator;
 use crate::stats::suggest_key;
 
 pub fn run(args: &DraftInitArgs, _no_witness: bool) -> Result<Value, RefusalPayload> {
     let pre_parse = load_pre_parse_from_peek(args)?;
-    let headers = if let Some(pre_parse) = pre_parse.as_ref() {
-        csv::StringRecord::from(crate::slice::headers_from_pre_parse(
-            &args.dataset,
-            pre_parse,
-        )?)
-    } else {
-        let file = File::open(&args.dataset).map_err(|error| {
-            RefusalPayload::io(args.dataset.display().to_string(), error.to_string())
-        })?;
-        let mut reader = csv::Reader::from_reader(file);
-        reader
-            .headers()
-            .map_err(|error| {
-                RefusalPayload::csv_parse(args.dataset.display().to_string(), error.to_string())
-            })?
-            .clone()
-    };
-    if headers.is_empty() {
-        return Err(RefusalPayload::empty_with_reason(
-            args.dataset.display().to_string(),
-            "no header row",
+    let format = resolve_profile_format(args);
+    if format == ProfileFormat::FixedWidth {
+        return Err(RefusalPayload::invalid_schema_single(
+            "format",
+            "fixed_width columns cannot be read from the data; start from `profile draft new --format fixed_width` and declare the spans",
         ));
     }
+    let options = ReadOptions {
+        format: Some(format),
+        pre_parse: pre_parse.as_ref(),
+        sheet: args.sheet.as_deref(),
+        fixed_width: None,
+        path_sample: Some(args.path_sample),
+    };
+    let rows = DatasetRows::open(&args.dataset, options)?;
+    let headers = rows.headers().clone();
+    let dialect = rows.dialect();
 
     let column_aliases = args
         .column_registry
@@ -45,7 +42,15 @@ pub fn run(args: &DraftInitArgs, _no_witness: bool) -> Result<Value, RefusalPayl
         .map(load_column_registry_aliases)
         .transpose()?;
     let include_columns = canonicalize_header_sequence(&headers, column_aliases.as_ref());
-    let key = resolve_key(args, column_aliases.as_ref())?;
+    let key = resolve_key(args, options, column_aliases.as_ref())?;
+    let mut column_types = if args.infer_types {
+        infer_column_types(args, rows, &headers, column_aliases.as_ref())?
+    } else {
+        BTreeMap::new()
+    };
+    if format == ProfileFormat::Parquet {
+        seed_parquet_column_types(args, &mut column_types, column_aliases.as_ref())?;
+    }
 
     let profile = Profile {
         schema_version: 1,
@@ -54,8 +59,12 @@ pub fn run(args: &DraftInitArgs, _no_witness: bool) -> Result<Value, RefusalPayl
         profile_family: None,
         profile_sha256: None,
         frozen: None,
+        extends: None,
+        extends_sha256: None,
         status: ProfileStatus::Draft,
-        format: resolve_profile_format(&args.format)?,
+        format,
+        sheet: args.sheet.clone(),
+        fixed_width: None,
         column_registry: args
             .column_registry
             .as_ref()
@@ -70,6 +79,12 @@ pub fn run(args: &DraftInitArgs, _no_witness: bool) -> Result<Value, RefusalPayl
         }),
         key,
         include_columns,
+        column_types,
+        normalize: BTreeMap::new(),
+        column_equivalence: BTreeMap::new(),
+        drift: None,
+        constraints: None,
+        exclude_columns: Vec::new(),
     };
     validate_profile(&profile, ValidationMode::Validate)?;
 
@@ -77,14 +92,16 @@ pub fn run(args: &DraftInitArgs, _no_witness: bool) -> Result<Value, RefusalPayl
     fs::write(&args.out, yaml)
         .map_err(|error| RefusalPayload::io(args.out.display().to_string(), error.to_string()))?;
 
-    Ok(json!({
+    let mut result = json!({
         "path": args.out.display().to_string()
-    }))
+    });
+    if let Some(dialect) = dialect {
+        result["dialect"] = dialect.summary();
+    }
+    Ok(result)
 }
 
-fn load_pre_parse_from_peek(
-    args: &DraftInitArgs,
-) -> Result<Option<crate::schema::PreParse>, RefusalPayload> {
+fn load_pre_parse_from_peek(args: &DraftInitArgs) -> Result<Option<PreParse>, RefusalPayload> {
     let Some(path) = args.from_peek.as_deref() else {
         return Ok(None);
     };
@@ -128,7 +145,10 @@ fn load_pre_parse_from_peek(
         header_merge: None,
         data_starts_at: read_usize(suggestion, "data_starts_at"),
         delimiter: None,
-        encoding: None,
+        encoding: suggestion
+            .get("encoding")
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned),
         preamble_capture: Some(true),
         unit_rows_capture: Some(true),
         unit_rows: suggestion
@@ -152,7 +172,7 @@ fn load_pre_parse_from_peek(
                 header_rows_pattern: Vec::new(),
             });
 
-    Ok(Some(crate::schema::PreParse {
+    Ok(Some(PreParse {
         expected_shape,
         slice,
     }))
@@ -167,11 +187,12 @@ fn read_usize(value: &Value, key: &str) -> Option<usize> {
 
 fn resolve_key(
     args: &DraftInitArgs,
-    column_aliases: Option<&std::collections::HashMap<String, String>>,
+    options: ReadOptions<'_>,
+    column_aliases: Option<&HashMap<String, String>>,
 ) -> Result<Vec<String>, RefusalPayload> {
     match args.key.as_deref() {
         None => Ok(Vec::new()),
-        Some("auto") => resolve_auto_key(args, column_aliases),
+        Some("auto") => resolve_auto_key(args, options, column_aliases),
         Some(explicit_key) => Ok(vec![canonicalize_profile_column(
             explicit_key,
             column_aliases,
@@ -181,45 +202,102 @@ fn resolve_key(
 
 fn resolve_auto_key(
     args: &DraftInitArgs,
-    column_aliases: Option<&std::collections::HashMap<String, String>>,
+    options: ReadOptions<'_>,
+    column_aliases: Option<&HashMap<String, String>>,
 ) -> Result<Vec<String>, RefusalPayload> {
-    let suggest_args = SuggestKeyArgs {
-        dataset: args.dataset.clone(),
-        top: 1,
-    };
-    let suggest_output = suggest_key::run(&suggest_args, true)?;
+    let candidates = suggest_key::rank_candidates(&args.dataset, options)?;
 
-    let candidate = suggest_output
-        .result
-        .get("candidates")
-        .and_then(Value::as_array)
-        .and_then(|candidates| candidates.first());
+    if let Some(candidate) = candidates.first()
+        && candidate.viable
+        && !candidate.column.is_empty()
+    {
+        return Ok(vec![canonicalize_profile_column(
+            &candidate.column,
+            column_aliases,
+        )]);
+    }
 
-    let Some(candidate) = candidate else {
-        eprintln!("Warning: no viable key candidate found; using key: []");
-        return Ok(Vec::new());
-    };
+    // No single column is a key; fall back to the best viable column combination.
+    let composites = suggest_key::rank_composites(&args.dataset, options, &candidates)?;
+    match composites.first() {
+        Some(composite) if composite.viable => Ok(composite
+            .columns
+            .iter()
+            .map(|column| canonicalize_profile_column(column, column_aliases))
+            .collect()),
+        _ => {
+            eprintln!("Warning: no viable key candidate found; using key: []");
+            Ok(Vec::new())
+        }
+    }
+}
 
-    let viable = candidate
-        .get("viable")
-        .and_then(Value::as_bool)
-        .unwrap_or(false);
-    let column = candidate
-        .get("column")
-        .and_then(Value::as_str)
-        .unwrap_or("");
+fn infer_column_types(
+    args: &DraftInitArgs,
+    rows: DatasetRows,
+    headers: &csv::StringRecord,
+    column_aliases: Option<&HashMap<String, String>>,
+) -> Result<BTreeMap<String, ColumnType>, RefusalPayload> {
+    let mut accumulators = vec![TypeAccumulator::default(); headers.len()];
+    let mut row_count = 0usize;
 
-    if viable && !column.is_empty() {
-        Ok(vec![canonicalize_profile_column(column, column_aliases)])
-    } else {
-        eprintln!("Warning: no viable key candidate found; using key: []");
-        Ok(Vec::new())
+    for record in rows {
+        let record = record?;
+        row_count += 1;
+        for (accumulator, value) in accumulators.iter_mut().zip(record.iter()) {
+            accumulator.observe(value);
+        }
+    }
+
+    if row_count == 0 {
+        return Err(RefusalPayload::empty_with_reason(
+            args.dataset.display().to_string(),
+            "no data rows",
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    let mut column_types = BTreeMap::new();
+    for (header, accumulator) in headers.iter().zip(accumulators.iter()) {
+        let column = canonicalize_profile_column(header, column_aliases);
+        if !seen.insert(column.clone()) {
+            continue;
+        }
+        if let Some(observed) = accumulator.observed_type() {
+            column_types.insert(column, observed);
+        }
+    }
+
+    Ok(column_types)
+}
+
+/// Parquet declares column types in its schema; they win over types inferred from values.
+fn seed_parquet_column_types(
+    args: &DraftInitArgs,
+    column_types: &mut BTreeMap<String, ColumnType>,
+    column_aliases: Option<&HashMap<String, String>>,
+) -> Result<(), RefusalPayload> {
+    let mut seen = HashSet::new();
+    for (name, declared) in schema_column_types(&args.dataset)? {
+        let column = canonicalize_profile_column(&name, column_aliases);
+        if !seen.insert(column.clone()) {
+            continue;
+        }
+        if let Some(declared) = declared {
+            column_types.insert(column, declared);
+        }
     }
+    Ok(())
 }
 
-fn resolve_profile_format(format: &DatasetFormat) -> Result<ProfileFormat, RefusalPayload> {
-    match format {
-        DatasetFormat::Csv => Ok(ProfileFormat::Csv),
+fn resolve_profile_format(args: &DraftInitArgs) -> ProfileFormat {
+    match args.format {
+        Some(DatasetFormat::Csv) => ProfileFormat::Csv,
+        Some(DatasetFormat::Xlsx) => ProfileFormat::Xlsx,
+        Some(DatasetFormat::Parquet) => ProfileFormat::Parquet,
+        Some(DatasetFormat::Jsonl) => ProfileFormat::Jsonl,
+        Some(DatasetFormat::FixedWidth) => ProfileFormat::FixedWidth,
+        None => format_for_path(&args.dataset),
     }
 }
 
@@ -228,11 +306,13 @@ fn render_yaml(profile: &Profile) -> Result<String, RefusalPayload> {
         schema_version: profile.schema_version,
         status: profile.status,
         format: profile.format,
+        sheet: profile.sheet.as_deref(),
         column_registry: profile.column_registry.as_deref(),
         pre_parse: profile.pre_parse.as_ref(),
         equivalence: profile.equivalence.as_ref(),
         key: profile.key.as_slice(),
         include_columns: profile.include_columns.as_slice(),
+        column_types: &profile.column_types,
     };
 
     let rendered = serde_yaml::to_string(&template).map_err(|error| {
@@ -253,11 +333,15 @@ struct DraftTemplate<'a> {
     status: ProfileStatus,
     format: ProfileFormat,
     #[serde(skip_serializing_if = "Option::is_none")]
+    sheet: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     column_registry: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pre_parse: Option<&'a crate::schema::PreParse>,
+    pre_parse: Option<&'a PreParse>,
     #[serde(skip_serializing_if = "Option::is_none")]
     equivalence: Option<&'a Equivalence>,
     key: &'a [String],
     include_columns: &'a [String],
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    column_types: &'a BTreeMap<String, ColumnType>,
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs;
 
 use serde::Serialize;
@@ -6,7 +7,8 @@ use serde_json::{Value, json};
 use crate::cli::args::{DatasetFormat, DraftNewArgs};
 use crate::refusal::RefusalPayload;
 use crate::schema::{
-    Equivalence, Profile, ProfileFormat, ProfileStatus, ValidationMode, validate_profile,
+    Equivalence, FixedWidth, Profile, ProfileFormat, ProfileStatus, ValidationMode,
+    validate_profile,
 };
 
 pub fn run(args: &DraftNewArgs, _no_witness: bool) -> Result<Value, RefusalPayload> {
@@ -32,8 +34,14 @@ fn build_draft_template(format: &DatasetFormat) -> Result<Profile, RefusalPayloa
         profile_family: None,
         profile_sha256: None,
         frozen: None,
+        extends: None,
+        extends_sha256: None,
         status: ProfileStatus::Draft,
         format: resolved_format,
+        sheet: None,
+        fixed_width: (resolved_format == ProfileFormat::FixedWidth).then(|| FixedWidth {
+            columns: Vec::new(),
+        }),
         column_registry: None,
         fingerprint_ref: None,
         pre_parse: None,
@@ -45,12 +53,22 @@ fn build_draft_template(format: &DatasetFormat) -> Result<Profile, RefusalPayloa
         }),
         key: Vec::new(),
         include_columns: Vec::new(),
+        column_types: BTreeMap::new(),
+        normalize: BTreeMap::new(),
+        column_equivalence: BTreeMap::new(),
+        drift: None,
+        constraints: None,
+        exclude_columns: Vec::new(),
     })
 }
 
 fn resolve_profile_format(format: &DatasetFormat) -> Result<ProfileFormat, RefusalPayload> {
     match format {
         DatasetFormat::Csv => Ok(ProfileFormat::Csv),
+        DatasetFormat::Xlsx => Ok(ProfileFormat::Xlsx),
+        DatasetFormat::Parquet => Ok(ProfileFormat::Parquet),
+        DatasetFormat::Jsonl => Ok(ProfileFormat::Jsonl),
+        DatasetFormat::FixedWidth => Ok(ProfileFormat::FixedWidth),
     }
 }
 
@@ -59,6 +77,7 @@ fn render_yaml(profile: &Profile) -> Result<String, RefusalPayload> {
         schema_version: profile.schema_version,
         status: profile.status,
         format: profile.format,
+        fixed_width: profile.fixed_width.as_ref(),
         column_registry: profile.column_registry.as_deref(),
         pre_parse: profile.pre_parse.as_ref(),
         equivalence: profile.equivalence.as_ref(),
@@ -84,6 +103,8 @@ struct DraftTemplate<'a> {
     status: ProfileStatus,
     format: ProfileFormat,
     #[serde(skip_serializing_if = "Option::is_none")]
+    fixed_width: Option<&'a FixedWidth>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     column_registry: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pre_parse: Option<&'a crate::schema::PreParse>,
@@ -0,0 +1,85 @@
+use std::io::Read;
+use std::path::Path;
+
+use encoding_rs::{Encoding, UTF_8};
+use encoding_rs_io::{DecodeReaderBytes, DecodeReaderBytesBuilder};
+
+use crate::compression::{DatasetReader, open_dataset};
+use crate::refusal::RefusalPayload;
+
+/// Dataset bytes decompressed and decoded to UTF-8 while they stream.
+pub(crate) type DecodedFile = DecodeReaderBytes<DatasetReader, Vec<u8>>;
+
+/// The text encoding a dataset is read with: a byte-order mark wins over the declared label.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SourceEncoding {
+    pub encoding: &'static Encoding,
+    pub bom: bool,
+}
+
+impl SourceEncoding {
+    pub fn name(self) -> String {
+        self.encoding.name().to_ascii_lowercase()
+    }
+}
+
+/// Resolve a WHATWG encoding label such as `windows-1252`, `latin1` or `utf-16le`.
+pub(crate) fn encoding_for_label(label: &str) -> Option<&'static Encoding> {
+    Encoding::for_label(label.trim().as_bytes())
+}
+
+pub(crate) fn sniff_encoding(
+    path: &Path,
+    declared: Option<&str>,
+) -> Result<SourceEncoding, RefusalPayload> {
+    let mut prefix = [0u8; 3];
+    let mut read = 0;
+    let mut file = open_dataset(path)?;
+    while read < prefix.len() {
+        match file.read(&mut prefix[read..]) {
+            Ok(0) => break,
+            Ok(count) => read += count,
+            Err(error) => {
+                return Err(RefusalPayload::io(
+                    path.display().to_string(),
+                    error.to_string(),
+                ));
+            }
+        }
+    }
+
+    if let Some((encoding, _)) = Encoding::for_bom(&prefix[..read]) {
+        return Ok(SourceEncoding {
+            encoding,
+            bom: true,
+        });
+    }
+
+    let encoding = match declared {
+        Some(label) => encoding_for_label(label).ok_or_else(|| {
+            RefusalPayload::invalid_schema_single(
+                "pre_parse.slice.encoding",
+                format!("unsupported encoding '{label}'"),
+            )
+        })?,
+        None => UTF_8,
+    };
+    Ok(SourceEncoding {
+        encoding,
+        bom: false,
+    })
+}
+
+/// Open `path` for reading as UTF-8 text. Plain UTF-8 passes through untouched so invalid bytes
+/// still surface as read errors; other encodings are transcoded and any BOM is stripped.
+pub(crate) fn open_decoded(
+    path: &Path,
+    source: SourceEncoding,
+) -> Result<DecodedFile, RefusalPayload> {
+    let file = open_dataset(path)?;
+    let encoding = (source.bom || source.encoding != UTF_8).then_some(source.encoding);
+    Ok(DecodeReaderBytesBuilder::new()
+        .encoding(encoding)
+        .bom_sniffing(true)
+        .build(file))
+}
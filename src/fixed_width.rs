@@ -0,0 +1,79 @@
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::encoding::{DecodedFile, SourceEncoding, open_decoded};
+use crate::refusal::RefusalPayload;
+use crate::schema::FixedWidthColumn;
+
+/// Lines of a fixed-width file cut into the profile's column spans and numbered by physical
+/// line. Blank lines surface as empty rows so directive row numbers match the raw file.
+pub(crate) struct FixedWidthRows {
+    reader: BufReader<DecodedFile>,
+    path: PathBuf,
+    columns: Rc<[FixedWidthColumn]>,
+    lines_read: usize,
+    buffer: String,
+}
+
+impl FixedWidthRows {
+    pub fn open(
+        path: &Path,
+        columns: Rc<[FixedWidthColumn]>,
+        source: SourceEncoding,
+    ) -> Result<Self, RefusalPayload> {
+        let file = open_decoded(path, source)?;
+        Ok(Self {
+            reader: BufReader::new(file),
+            path: path.to_path_buf(),
+            columns,
+            lines_read: 0,
+            buffer: String::new(),
+        })
+    }
+
+    pub fn lines_read(&self) -> usize {
+        self.lines_read
+    }
+}
+
+impl Iterator for FixedWidthRows {
+    type Item = Result<(usize, Vec<String>), RefusalPayload>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.clear();
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => return None,
+            Ok(_) => self.lines_read += 1,
+            Err(error) => {
+                return Some(Err(RefusalPayload::io(
+                    self.path.display().to_string(),
+                    error.to_string(),
+                )));
+            }
+        }
+        let text = self.buffer.trim_end_matches(['\n', '\r']);
+        if text.trim().is_empty() {
+            return Some(Ok((self.lines_read, Vec::new())));
+        }
+        Some(Ok((self.lines_read, cut_spans(text, &self.columns))))
+    }
+}
+
+/// Cut one line into trimmed cells by character position; spans past the end of a short
+/// line read as empty.
+fn cut_spans(text: &str, columns: &[FixedWidthColumn]) -> Vec<String> {
+    let characters = text.chars().collect::<Vec<_>>();
+    columns
+        .iter()
+        .map(|column| {
+            let start = (column.start - 1).min(characters.len());
+            let end = column.end().unwrap_or(usize::MAX).min(characters.len());
+            characters[start..end]
+                .iter()
+                .collect::<String>()
+                .trim()
+                .to_owned()
+        })
+        .collect()
+}
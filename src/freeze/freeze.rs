@@ -4,10 +4,14 @@ use std::fs;
 use crate::cli::args::FreezeArgs;
 use crate::output::json::CommandOutput;
 use crate::refusal::RefusalPayload;
+use crate::resolve::resolver::apply_extends;
 use crate::schema::{
-    ProfileStatus, ValidationMode, canonical_yaml, compute_profile_sha256, is_valid_profile_family,
-    parse_profile_yaml, validate_profile,
+    Profile, ProfileStatus, SelectorMatch, ValidationMode, build_header_index, canonical_yaml,
+    canonicalize_header_sequence, compute_profile_sha256, has_column_selectors,
+    is_valid_profile_family, load_column_registry_aliases, parse_profile_yaml,
+    resolve_profile_columns, resolve_registry_path, validate_profile,
 };
+use crate::slice::{DatasetRows, ReadOptions};
 use crate::witness::append::append_for_command;
 
 pub fn run(args: &FreezeArgs, no_witness: bool) -> Result<CommandOutput, RefusalPayload> {
@@ -23,7 +27,7 @@ pub fn run(args: &FreezeArgs, no_witness: bool) -> Result<CommandOutput, Refusal
     let content = fs::read_to_string(&args.draft)
         .map_err(|error| RefusalPayload::io(args.draft.display().to_string(), error.to_string()))?;
 
-    let mut profile = parse_profile_yaml(&content)?;
+    let profile = parse_profile_yaml(&content)?;
 
     // Check if already frozen
     if profile.is_frozen() {
@@ -32,6 +36,9 @@ pub fn run(args: &FreezeArgs, no_witness: bool) -> Result<CommandOutput, Refusal
         return Err(RefusalPayload::already_frozen(profile_id, profile_sha256));
     }
 
+    // Merge the frozen parent, pinning its hash into the child's canonical form
+    let mut profile = apply_extends(profile, &args.draft)?;
+
     // Validate family syntax
     if !is_valid_profile_family(&args.family) {
         return Err(RefusalPayload::bad_version(
@@ -59,6 +66,9 @@ pub fn run(args: &FreezeArgs, no_witness: bool) -> Result<CommandOutput, Refusal
     // Validate the profile with freeze validation mode
     validate_profile(&profile, ValidationMode::Freeze)?;
 
+    // Every glob/regex selector must match something in the reference header
+    let selectors = check_selectors(args, &profile)?;
+
     // Serialize the final profile (not canonical - readable format)
     let output_yaml = profile.to_yaml().map_err(|error| {
         RefusalPayload::invalid_schema_single(
@@ -71,15 +81,28 @@ pub fn run(args: &FreezeArgs, no_witness: bool) -> Result<CommandOutput, Refusal
     fs::write(&args.out, output_yaml)
         .map_err(|error| RefusalPayload::io(args.out.display().to_string(), error.to_string()))?;
 
-    let result = json!({
+    let mut result = json!({
         "path": args.out.display().to_string(),
         "profile_id": profile.profile_id,
         "profile_sha256": profile.profile_sha256
     });
+    if let Some(extends) = &profile.extends {
+        result["extends"] = json!({
+            "profile_id": extends,
+            "profile_sha256": profile.extends_sha256
+        });
+    }
+    if let Some(selectors) = selectors {
+        result["selectors"] = json!(selectors);
+    }
+    let mut inputs = vec![args.draft.clone()];
+    if let Some(reference) = &args.reference {
+        inputs.push(reference.clone());
+    }
     let witness_id = append_for_command(
         "freeze",
         &result,
-        vec![args.draft.clone()],
+        inputs,
         json!({
             "subcommand": "freeze",
             "family": args.family,
@@ -90,3 +113,41 @@ pub fn run(args: &FreezeArgs, no_witness: bool) -> Result<CommandOutput, Refusal
 
     Ok(CommandOutput::success(result).with_witness_id(witness_id))
 }
+
+fn check_selectors(
+    args: &FreezeArgs,
+    profile: &Profile,
+) -> Result<Option<Vec<SelectorMatch>>, RefusalPayload> {
+    let Some(reference) = args.reference.as_deref() else {
+        if has_column_selectors(profile) {
+            return Err(RefusalPayload::invalid_schema_single(
+                "include_columns",
+                "column selectors require --reference <DATASET> to freeze",
+            ));
+        }
+        return Ok(None);
+    };
+
+    let rows = DatasetRows::open(reference, ReadOptions::from_profile(profile))?;
+    let column_aliases = profile
+        .column_registry
+        .as_deref()
+        .map(|registry| load_column_registry_aliases(&resolve_registry_path(&args.draft, registry)))
+        .transpose()?;
+    let available = build_header_index(rows.headers(), column_aliases.as_ref());
+    let header_names = canonicalize_header_sequence(rows.headers(), column_aliases.as_ref());
+    let selection = resolve_profile_columns(profile, &header_names, &available);
+
+    let unmatched = selection
+        .unmatched_selectors()
+        .map(|selector| selector.selector.clone())
+        .collect::<Vec<_>>();
+    if !unmatched.is_empty() {
+        return Err(RefusalPayload::column_not_found(
+            unmatched,
+            available.available(),
+        ));
+    }
+
+    Ok(Some(selection.selectors))
+}
@@ -0,0 +1,220 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, BufReader, Lines};
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::compression::{DatasetReader, open_dataset};
+use crate::refusal::RefusalPayload;
+
+/// Whether `path` names a JSON Lines file by its extension.
+pub(crate) fn is_jsonl_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("jsonl") || extension.eq_ignore_ascii_case("ndjson")
+        })
+}
+
+/// The dotted spelling of a JSON pointer column (`/borrower/address/zip` reads as
+/// `borrower.address.zip`); any other name is returned unchanged.
+pub(crate) fn field_path_name(column: &str) -> Cow<'_, str> {
+    match column.strip_prefix('/') {
+        Some(pointer) => Cow::Owned(
+            pointer
+                .split('/')
+                .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+                .collect::<Vec<_>>()
+                .join("."),
+        ),
+        None => Cow::Borrowed(column),
+    }
+}
+
+/// A leaf field path discovered in a JSONL file, with how many records held each JSON kind
+/// of value there. Nulls are not counted.
+#[derive(Debug, Clone)]
+pub(crate) struct FieldPath {
+    pub name: String,
+    pub kinds: BTreeMap<&'static str, usize>,
+    segments: Vec<String>,
+}
+
+/// Records of a JSON Lines file flattened onto a fixed set of field paths; a path a record
+/// does not have reads as an empty cell.
+pub(crate) struct JsonlRows {
+    records: JsonlRecords,
+    paths: Vec<Vec<String>>,
+}
+
+/// Open a JSONL file: discover the union of leaf field paths over the first `sample` records
+/// (all of them when unset), then stream every record onto those paths. Paths are listed in
+/// name order, since object key order carries no meaning in JSON; a dotted key that spells
+/// the same name as a nested path (`{"a.b": 1}` and `{"a": {"b": 2}}`) refuses.
+pub(crate) fn open_jsonl(
+    path: &Path,
+    sample: Option<usize>,
+) -> Result<(Vec<FieldPath>, JsonlRows), RefusalPayload> {
+    let mut fields = Vec::<FieldPath>::new();
+    let mut positions = HashMap::<Vec<String>, usize>::new();
+    let mut names = HashMap::<String, usize>::new();
+    let mut collision = None;
+    for record in JsonlRecords::open(path)?.take(sample.unwrap_or(usize::MAX)) {
+        let record = record?;
+        let mut segments = Vec::new();
+        visit_leaves(&record, &mut segments, &mut |segments, value| {
+            let position = match positions.get(segments) {
+                Some(&position) => position,
+                None => {
+                    let name = segments.join(".");
+                    let position = fields.len();
+                    if let Some(&other) = names.get(&name) {
+                        collision.get_or_insert((other, segments.to_vec()));
+                    }
+                    names.entry(name.clone()).or_insert(position);
+                    positions.insert(segments.to_vec(), position);
+                    fields.push(FieldPath {
+                        name,
+                        kinds: BTreeMap::new(),
+                        segments: segments.to_vec(),
+                    });
+                    position
+                }
+            };
+            if let Some(kind) = value_kind(value) {
+                *fields[position].kinds.entry(kind).or_default() += 1;
+            }
+        });
+        if let Some((other, segments)) = collision.take() {
+            return Err(RefusalPayload::csv_parse(
+                path.display().to_string(),
+                format!(
+                    "keys {:?} and {:?} both flatten to the field path '{}'",
+                    fields[other].segments, segments, fields[other].name
+                ),
+            ));
+        }
+    }
+
+    fields.sort_by(|left, right| left.name.cmp(&right.name));
+
+    let rows = JsonlRows {
+        records: JsonlRecords::open(path)?,
+        paths: fields.iter().map(|field| field.segments.clone()).collect(),
+    };
+    Ok((fields, rows))
+}
+
+impl Iterator for JsonlRows {
+    type Item = Result<Vec<String>, RefusalPayload>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = match self.records.next()? {
+            Ok(record) => record,
+            Err(refusal) => return Some(Err(refusal)),
+        };
+        Some(Ok(self
+            .paths
+            .iter()
+            .map(|segments| {
+                segments
+                    .iter()
+                    .try_fold(&record, |value, segment| value.as_object()?.get(segment))
+                    .map(value_text)
+                    .unwrap_or_default()
+            })
+            .collect()))
+    }
+}
+
+/// One JSON object per non-blank line; anything else refuses with the offending line number.
+struct JsonlRecords {
+    lines: Lines<BufReader<DatasetReader>>,
+    line_number: usize,
+    path: String,
+}
+
+impl JsonlRecords {
+    fn open(path: &Path) -> Result<Self, RefusalPayload> {
+        Ok(Self {
+            lines: BufReader::new(open_dataset(path)?).lines(),
+            line_number: 0,
+            path: path.display().to_string(),
+        })
+    }
+}
+
+impl Iterator for JsonlRecords {
+    type Item = Result<Value, RefusalPayload>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(error) => {
+                    return Some(Err(RefusalPayload::io(
+                        self.path.clone(),
+                        error.to_string(),
+                    )));
+                }
+            };
+            self.line_number += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Some(match serde_json::from_str::<Value>(&line) {
+                Ok(record) if record.is_object() => Ok(record),
+                Ok(_) => Err(RefusalPayload::csv_parse(
+                    self.path.clone(),
+                    format!("line {}: expected a JSON object", self.line_number),
+                )),
+                Err(error) => Err(RefusalPayload::csv_parse(
+                    self.path.clone(),
+                    format!("line {}: {error}", self.line_number),
+                )),
+            });
+        }
+    }
+}
+
+/// Call `visit` for every leaf under `value`. Non-empty objects are descended into; arrays,
+/// empty objects, scalars, and nulls are leaves.
+fn visit_leaves(
+    value: &Value,
+    segments: &mut Vec<String>,
+    visit: &mut impl FnMut(&[String], &Value),
+) {
+    match value {
+        Value::Object(fields) if !fields.is_empty() => {
+            for (key, field) in fields {
+                segments.push(key.clone());
+                visit_leaves(field, segments, visit);
+                segments.pop();
+            }
+        }
+        leaf if !segments.is_empty() => visit(segments, leaf),
+        _ => {}
+    }
+}
+
+fn value_kind(value: &Value) -> Option<&'static str> {
+    match value {
+        Value::Null => None,
+        Value::Bool(_) => Some("boolean"),
+        Value::Number(_) => Some("number"),
+        Value::String(_) => Some("string"),
+        Value::Array(_) => Some("array"),
+        Value::Object(_) => Some("object"),
+    }
+}
+
+/// Render a value the way it would read in a CSV export: nulls empty, strings unquoted, and
+/// arrays and objects as compact JSON.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
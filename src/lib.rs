@@ -10,14 +10,23 @@ use crate::cli::exit::{EXIT_REFUSAL, EXIT_SUCCESS};
 use crate::output::json::CommandOutput;
 use crate::refusal::RefusalPayload;
 
+pub mod apply;
+pub mod check;
 pub mod cli;
+pub(crate) mod columnar;
+pub(crate) mod compression;
+pub(crate) mod dialect;
 pub mod diff;
 pub mod discovery;
 pub mod doctor;
 pub mod draft;
+pub(crate) mod encoding;
+pub(crate) mod fixed_width;
 pub mod freeze;
+pub(crate) mod jsonl;
 pub mod lint;
 pub mod network;
+pub mod normalize;
 pub mod output;
 pub(crate) mod paths;
 pub mod refusal;
@@ -26,6 +35,7 @@ pub mod schema;
 pub mod slice;
 pub mod stats;
 pub mod witness;
+pub(crate) mod workbook;
 
 type HandlerResult = Result<CommandOutput, RefusalPayload>;
 
@@ -109,10 +119,16 @@ fn dispatch(
             }
         },
         Command::Validate(args) => lint::validate::run(args, no_witness),
-        Command::Lint(args) => lint::lint::run(args, no_witness),
+        Command::Lint(args) => lint::lint::run(args, no_witness, explicit),
+        Command::Check(args) => check::check::run(args, no_witness, explicit),
         Command::Slice(args) => slice::run(args, no_witness, explicit, json_output),
+        Command::Apply(args) => apply::apply::run(args, no_witness, explicit, json_output),
+        Command::Normalize(args) => {
+            normalize::normalize::run(args, no_witness, explicit, json_output)
+        }
         Command::Stats(args) => stats::stats::run(args, no_witness, explicit),
         Command::SuggestKey(args) => stats::suggest_key::run(args, no_witness),
+        Command::Drift(args) => stats::drift::run(args, no_witness, explicit),
         Command::Freeze(args) => freeze::freeze::run(args, no_witness),
         Command::List(args) => resolve::list::run(args, no_witness).map(CommandOutput::success),
         Command::Show(args) => resolve::show::run(args, no_witness),
@@ -161,9 +177,13 @@ fn command_name(command: &Command) -> &'static str {
         },
         Command::Validate(_) => "validate",
         Command::Lint(_) => "lint",
+        Command::Check(_) => "check",
         Command::Slice(_) => "slice",
+        Command::Normalize(_) => "normalize",
+        Command::Apply(_) => "apply",
         Command::Stats(_) => "stats",
         Command::SuggestKey(_) => "suggest-key",
+        Command::Drift(_) => "drift",
         Command::Freeze(_) => "freeze",
         Command::List(_) => "list",
         Command::Show(_) => "show",
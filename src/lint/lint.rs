@@ -1,41 +1,98 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::fs::File;
 
-use serde_json::json;
+use csv::StringRecord;
+use serde_json::{Value, json};
 
 use crate::cli::args::LintArgs;
+use crate::jsonl::{FieldPath, field_path_name};
+use crate::lint::violations::{RowViolation, ViolationExport};
 use crate::output::json::{CommandOutput, ProfileRef};
 use crate::refusal::RefusalPayload;
+use crate::resolve::resolver::apply_extends;
 use crate::schema::{
-    ValidationMode, build_header_index, load_column_registry_aliases, parse_profile_yaml,
+    ColumnType, EquivalenceOrder, HeaderIndex, Profile, ResolvedColumns, ValidationMode,
+    build_header_index, canonicalize_header_sequence, canonicalize_profile_column,
+    load_column_registry_aliases, parse_profile_yaml, resolve_profile_columns,
     resolve_registry_path, validate_profile,
 };
+use crate::slice::{DatasetRows, ReadOptions};
+use crate::stats::infer::TypeAccumulator;
 use crate::witness::append::append_for_command;
 
-pub fn run(args: &LintArgs, no_witness: bool) -> Result<CommandOutput, RefusalPayload> {
+const DUPLICATE_KEY_SAMPLE_LIMIT: usize = 5;
+
+/// How one row's key tuple relates to the rows before it.
+enum KeyObservation {
+    Distinct,
+    Duplicate,
+    /// Positions of the blank key columns.
+    Null(Vec<usize>),
+}
+
+#[derive(Debug, Default)]
+struct KeyAccumulator {
+    seen: HashMap<Vec<String>, usize>,
+    duplicate_samples: Vec<Vec<String>>,
+    duplicated_keys: usize,
+    duplicate_rows: usize,
+    null_rows: usize,
+    null_counts: Vec<usize>,
+}
+
+impl KeyAccumulator {
+    fn new(width: usize) -> Self {
+        Self {
+            null_counts: vec![0; width],
+            ..Self::default()
+        }
+    }
+
+    fn observe(&mut self, tuple: Vec<String>) -> KeyObservation {
+        let nulls = tuple
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.trim().is_empty())
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        if !nulls.is_empty() {
+            for position in &nulls {
+                self.null_counts[*position] += 1;
+            }
+            self.null_rows += 1;
+            return KeyObservation::Null(nulls);
+        }
+
+        let Some(occurrences) = self.seen.get_mut(&tuple) else {
+            self.seen.insert(tuple, 1);
+            return KeyObservation::Distinct;
+        };
+        *occurrences += 1;
+        self.duplicate_rows += 1;
+        if *occurrences == 2 {
+            self.duplicated_keys += 1;
+            if self.duplicate_samples.len() < DUPLICATE_KEY_SAMPLE_LIMIT {
+                self.duplicate_samples.push(tuple);
+            }
+        }
+        KeyObservation::Duplicate
+    }
+}
+
+pub fn run(
+    args: &LintArgs,
+    no_witness: bool,
+    explicit: bool,
+) -> Result<CommandOutput, RefusalPayload> {
     let profile_content = fs::read_to_string(&args.profile).map_err(|error| {
         RefusalPayload::io(args.profile.display().to_string(), error.to_string())
     })?;
-    let profile = parse_profile_yaml(&profile_content)?;
+    let profile = apply_extends(parse_profile_yaml(&profile_content)?, &args.profile)?;
     validate_profile(&profile, ValidationMode::Validate)?;
 
-    let file = File::open(&args.against).map_err(|error| {
-        RefusalPayload::io(args.against.display().to_string(), error.to_string())
-    })?;
-    let mut reader = csv::Reader::from_reader(file);
-
-    let headers = reader
-        .headers()
-        .map_err(|error| {
-            RefusalPayload::csv_parse(args.against.display().to_string(), error.to_string())
-        })?
-        .clone();
-    if headers.is_empty() {
-        return Err(RefusalPayload::empty_with_reason(
-            args.against.display().to_string(),
-            "no header row",
-        ));
-    }
+    let rows = DatasetRows::open(&args.against, ReadOptions::from_profile(&profile))?;
+    let headers = rows.headers().clone();
+    let dialect = rows.dialect();
 
     let column_aliases = profile
         .column_registry
@@ -45,29 +102,109 @@ pub fn run(args: &LintArgs, no_witness: bool) -> Result<CommandOutput, RefusalPa
         })
         .transpose()?;
     let available = build_header_index(&headers, column_aliases.as_ref());
+    let header_names = canonicalize_header_sequence(&headers, column_aliases.as_ref());
+    let resolved = resolve_profile_columns(&profile, &header_names, &available);
     let mut issues = Vec::new();
 
-    for column in &profile.include_columns {
-        if available.column_index(column).is_none() {
-            issues.push(json!({
-                "kind": "missing_column",
-                "column": column,
-                "severity": "error"
-            }));
-        }
+    for column in &resolved.missing {
+        issues.push(json!({
+            "kind": "missing_column",
+            "column": column,
+            "severity": "error"
+        }));
+    }
+    for selector in resolved.unmatched_selectors() {
+        // An exclusion that matches nothing removes nothing; an inclusion that matches nothing
+        // silently drops every column it was meant to cover.
+        let severity = if selector.list == "include_columns" {
+            "warning"
+        } else {
+            "info"
+        };
+        issues.push(json!({
+            "kind": "unmatched_selector",
+            "selector": selector.selector,
+            "list": selector.list,
+            "severity": severity
+        }));
     }
 
+    let mut key_indexes = Vec::with_capacity(profile.key.len());
     for column in &profile.key {
-        if available.column_index(column).is_none() {
-            issues.push(json!({
+        match available.column_index(column) {
+            Some(index) => key_indexes.push(index),
+            None => issues.push(json!({
                 "kind": "missing_key",
                 "column": column,
                 "severity": "error"
-            }));
+            })),
+        }
+    }
+
+    issues.extend(header_drift_issues(
+        &profile,
+        &resolved,
+        &headers,
+        column_aliases.as_ref(),
+        &available,
+    ));
+    if let Some(field_paths) = rows.field_paths() {
+        issues.extend(path_type_issues(&resolved, field_paths, &available));
+    }
+
+    let key_indexes =
+        (!profile.key.is_empty() && key_indexes.len() == profile.key.len()).then_some(key_indexes);
+    let typed_columns = resolved
+        .columns
+        .iter()
+        .filter_map(|column| {
+            let declared = *profile.column_types.get(column)?;
+            let index = available.column_index(column)?;
+            Some(TypedColumn {
+                name: column.clone(),
+                declared,
+                index,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut export = args
+        .emit_violations
+        .as_deref()
+        .map(|path| {
+            let key = key_indexes
+                .iter()
+                .flatten()
+                .zip(&profile.key)
+                .map(|(index, column)| (column.clone(), *index))
+                .collect();
+            ViolationExport::open(path, key, explicit)
+        })
+        .transpose()?;
+    if key_indexes.is_some() || !typed_columns.is_empty() {
+        let scan = scan_dataset(
+            rows,
+            key_indexes.as_deref(),
+            &profile.key,
+            &typed_columns,
+            export.as_mut(),
+        )?;
+        if let Some(keys) = scan.keys.as_ref() {
+            issues.extend(key_issues(&profile.key, keys, explicit));
         }
+        issues.extend(type_issues(&typed_columns, &scan));
     }
 
-    let result = json!({ "issues": issues });
+    let mut result = json!({ "issues": issues });
+    if let Some(export) = export {
+        result["violations"] = export.finish()?;
+    }
+    if !resolved.selectors.is_empty() {
+        result["selectors"] = json!(resolved.selectors);
+    }
+    if let Some(dialect) = dialect {
+        result["dialect"] = dialect.summary();
+    }
     let witness_id = append_for_command(
         "lint",
         &result,
@@ -83,3 +220,257 @@ pub fn run(args: &LintArgs, no_witness: bool) -> Result<CommandOutput, RefusalPa
         .with_profile_ref(ProfileRef::from_profile(&profile))
         .with_witness_id(witness_id))
 }
+
+/// Whether a lint result should exit ISSUES_FOUND; `info` issues alone do not.
+pub fn has_actionable_issues(result: &Value) -> bool {
+    result
+        .get("issues")
+        .and_then(Value::as_array)
+        .is_some_and(|issues| {
+            issues
+                .iter()
+                .any(|issue| issue.get("severity").and_then(Value::as_str) != Some("info"))
+        })
+}
+
+fn header_drift_issues(
+    profile: &Profile,
+    selection: &ResolvedColumns,
+    headers: &StringRecord,
+    column_aliases: Option<&HashMap<String, String>>,
+    available: &HeaderIndex,
+) -> Vec<Value> {
+    let resolved = selection
+        .columns
+        .iter()
+        .filter_map(|column| Some((column.as_str(), available.column_index(column)?)))
+        .collect::<Vec<_>>();
+    let last_profiled = resolved.iter().map(|(_, index)| *index).max();
+    let is_declared = |name: &str| {
+        selection
+            .columns
+            .iter()
+            .chain(&profile.key)
+            .any(|column| field_path_name(column) == name)
+            || selection.excluded.iter().any(|column| column == name)
+    };
+
+    let mut issues = Vec::new();
+    let mut seen = HashSet::new();
+    for (index, header) in headers.iter().enumerate() {
+        let canonical = canonicalize_profile_column(header, column_aliases);
+        if is_declared(header) || is_declared(&canonical) || !seen.insert(header) {
+            continue;
+        }
+        // New columns wedged between profiled columns shift positions; trailing ones do not.
+        let severity = if last_profiled.is_some_and(|last| index < last) {
+            "warning"
+        } else {
+            "info"
+        };
+        issues.push(json!({
+            "kind": "unexpected_column",
+            "column": header,
+            "severity": severity,
+            "position": index + 1
+        }));
+    }
+
+    let order = profile
+        .equivalence
+        .as_ref()
+        .and_then(|equivalence| equivalence.order);
+    if matches!(order, Some(EquivalenceOrder::OrderSensitive)) {
+        let expected = resolved
+            .iter()
+            .map(|(column, _)| *column)
+            .collect::<Vec<_>>();
+        let mut observed = resolved.clone();
+        observed.sort_by_key(|(_, index)| *index);
+        let observed = observed
+            .into_iter()
+            .map(|(column, _)| column)
+            .collect::<Vec<_>>();
+        if expected != observed {
+            issues.push(json!({
+                "kind": "column_order_changed",
+                "severity": "warning",
+                "expected": expected,
+                "observed": observed
+            }));
+        }
+    }
+
+    issues
+}
+
+/// Profiled JSONL paths whose values are not one JSON kind across records, e.g. a number in
+/// some events and a string in others.
+fn path_type_issues(
+    selection: &ResolvedColumns,
+    field_paths: &[FieldPath],
+    available: &HeaderIndex,
+) -> Vec<Value> {
+    selection
+        .columns
+        .iter()
+        .filter_map(|column| {
+            let field = field_paths.get(available.column_index(column)?)?;
+            (field.kinds.len() > 1).then(|| {
+                json!({
+                    "kind": "path_type_changed",
+                    "column": column,
+                    "severity": "warning",
+                    "observed_kinds": field.kinds
+                })
+            })
+        })
+        .collect()
+}
+
+struct TypedColumn {
+    name: String,
+    declared: ColumnType,
+    index: usize,
+}
+
+struct DatasetScan {
+    rows: usize,
+    keys: Option<KeyAccumulator>,
+    types: Vec<TypeAccumulator>,
+}
+
+/// Scan every row for key and type problems, writing each offending row to `export` if given.
+fn scan_dataset(
+    rows: DatasetRows,
+    key_indexes: Option<&[usize]>,
+    key: &[String],
+    typed_columns: &[TypedColumn],
+    mut export: Option<&mut ViolationExport>,
+) -> Result<DatasetScan, RefusalPayload> {
+    let mut scan = DatasetScan {
+        rows: 0,
+        keys: key_indexes.map(|indexes| KeyAccumulator::new(indexes.len())),
+        types: vec![TypeAccumulator::default(); typed_columns.len()],
+    };
+
+    for record in rows {
+        let record = record?;
+        scan.rows += 1;
+
+        if let (Some(indexes), Some(keys)) = (key_indexes, scan.keys.as_mut()) {
+            let tuple = indexes
+                .iter()
+                .map(|index| record.get(*index).unwrap_or_default().to_string())
+                .collect::<Vec<_>>();
+            let observation = keys.observe(tuple);
+            if let Some(export) = export.as_deref_mut() {
+                match observation {
+                    KeyObservation::Distinct => {}
+                    KeyObservation::Duplicate => export.record(
+                        scan.rows,
+                        &record,
+                        RowViolation {
+                            kind: "duplicate_key",
+                            column: None,
+                            rule: "unique",
+                            value: "",
+                        },
+                    )?,
+                    KeyObservation::Null(positions) => {
+                        for position in positions {
+                            export.record(
+                                scan.rows,
+                                &record,
+                                RowViolation {
+                                    kind: "null_key",
+                                    column: Some(&key[position]),
+                                    rule: "not_null",
+                                    value: "",
+                                },
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+        for (column, types) in typed_columns.iter().zip(scan.types.iter_mut()) {
+            let value = record.get(column.index).unwrap_or_default();
+            let observed = types.observe(value);
+            if let (Some(export), Some(observed)) = (export.as_deref_mut(), observed)
+                && !column.declared.accepts(observed)
+            {
+                export.record(
+                    scan.rows,
+                    &record,
+                    RowViolation {
+                        kind: "type_mismatch",
+                        column: Some(&column.name),
+                        rule: &format!("type:{}", column.declared.as_str()),
+                        value,
+                    },
+                )?;
+            }
+        }
+    }
+
+    Ok(scan)
+}
+
+fn key_issues(key: &[String], accumulator: &KeyAccumulator, explicit: bool) -> Vec<Value> {
+    let mut issues = Vec::new();
+
+    if accumulator.duplicate_rows > 0 {
+        let mut issue = json!({
+            "kind": "duplicate_key",
+            "columns": key,
+            "severity": "error",
+            "duplicate_rows": accumulator.duplicate_rows,
+            "duplicated_keys": accumulator.duplicated_keys
+        });
+        if explicit {
+            issue["samples"] = json!(accumulator.duplicate_samples);
+        }
+        issues.push(issue);
+    }
+
+    if accumulator.null_rows > 0 {
+        let null_columns = key
+            .iter()
+            .zip(accumulator.null_counts.iter())
+            .filter(|(_, count)| **count > 0)
+            .map(|(column, count)| json!({ "column": column, "null_rows": count }))
+            .collect::<Vec<_>>();
+        issues.push(json!({
+            "kind": "null_key",
+            "columns": key,
+            "severity": "error",
+            "null_rows": accumulator.null_rows,
+            "null_columns": null_columns
+        }));
+    }
+
+    issues
+}
+
+fn type_issues(typed_columns: &[TypedColumn], scan: &DatasetScan) -> Vec<Value> {
+    typed_columns
+        .iter()
+        .zip(scan.types.iter())
+        .filter_map(|(column, types)| {
+            let offending_rows = types.non_null() - types.accepted_by(column.declared);
+            (offending_rows > 0).then(|| {
+                let percent = offending_rows as f64 / scan.rows as f64 * 100.0;
+                json!({
+                    "kind": "type_mismatch",
+                    "column": column.name,
+                    "severity": "error",
+                    "declared_type": column.declared.as_str(),
+                    "observed_type": types.observed_type().map(ColumnType::as_str),
+                    "offending_rows": offending_rows,
+                    "offending_percent": (percent * 100.0).round() / 100.0
+                })
+            })
+        })
+        .collect()
+}
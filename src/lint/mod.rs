@@ -1,3 +1,4 @@
 #[allow(clippy::module_inception)]
 pub mod lint;
 pub mod validate;
+pub mod violations;
@@ -5,6 +5,7 @@ use serde_json::json;
 use crate::cli::args::ValidateArgs;
 use crate::output::json::{CommandOutput, ProfileRef};
 use crate::refusal::RefusalPayload;
+use crate::resolve::resolver::apply_extends;
 use crate::schema::{ValidationMode, parse_profile_yaml, validate_profile};
 use crate::witness::append::append_for_command;
 
@@ -12,7 +13,7 @@ pub fn run(args: &ValidateArgs, no_witness: bool) -> Result<CommandOutput, Refus
     let path = args.file.display().to_string();
     let content = fs::read_to_string(&args.file)
         .map_err(|error| RefusalPayload::io(path, error.to_string()))?;
-    let profile = parse_profile_yaml(&content)?;
+    let profile = apply_extends(parse_profile_yaml(&content)?, &args.file)?;
     validate_profile(&profile, ValidationMode::Validate)?;
 
     let result = json!({
@@ -0,0 +1,178 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use csv::StringRecord;
+use serde_json::{Map, Value, json};
+
+use crate::refusal::RefusalPayload;
+use crate::slice::CsvSink;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    Csv,
+    Jsonl,
+}
+
+impl ExportFormat {
+    /// JSONL for `.jsonl` / `.ndjson` paths, CSV otherwise.
+    fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension)
+                if extension.eq_ignore_ascii_case("jsonl")
+                    || extension.eq_ignore_ascii_case("ndjson") =>
+            {
+                Self::Jsonl
+            }
+            _ => Self::Csv,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
+
+enum Output {
+    Csv(Box<csv::Writer<CsvSink>>),
+    Jsonl(Box<CsvSink>),
+}
+
+/// One offending row: the issue `kind` it feeds, the `column` it concerns (if any), the `rule`
+/// it broke and the offending `value`.
+pub(crate) struct RowViolation<'a> {
+    pub kind: &'a str,
+    pub column: Option<&'a str>,
+    pub rule: &'a str,
+    pub value: &'a str,
+}
+
+/// Streams row-level violations to `--emit-violations`, one record per offending row and rule,
+/// hashing the bytes as they are written. Key tuples and values are written only with
+/// `--explicit`; otherwise those fields are left empty.
+pub(crate) struct ViolationExport {
+    path: PathBuf,
+    format: ExportFormat,
+    key: Vec<(String, usize)>,
+    explicit: bool,
+    output: Output,
+    rows: usize,
+}
+
+impl ViolationExport {
+    /// `key` pairs each profile key column with its index in the dataset header; it is empty
+    /// when the profile declares no key or the dataset lacks one of its columns.
+    pub(crate) fn open(
+        path: &Path,
+        key: Vec<(String, usize)>,
+        explicit: bool,
+    ) -> Result<Self, RefusalPayload> {
+        let format = ExportFormat::for_path(path);
+        let sink = CsvSink::open(Some(path), false)?;
+        let output = match format {
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(sink);
+                let header = ["row"]
+                    .into_iter()
+                    .map(str::to_string)
+                    .chain(key.iter().map(|(column, _)| format!("key.{column}")))
+                    .chain(["kind", "column", "rule", "value"].map(str::to_string))
+                    .collect::<Vec<_>>();
+                writer
+                    .write_record(&header)
+                    .map_err(|error| write_error(path, error))?;
+                Output::Csv(Box::new(writer))
+            }
+            ExportFormat::Jsonl => Output::Jsonl(Box::new(sink)),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            format,
+            key,
+            explicit,
+            output,
+            rows: 0,
+        })
+    }
+
+    /// Record that 1-based data row `row`, read as `record`, broke `violation`.
+    pub(crate) fn record(
+        &mut self,
+        row: usize,
+        record: &StringRecord,
+        violation: RowViolation<'_>,
+    ) -> Result<(), RefusalPayload> {
+        self.rows += 1;
+        let key = self
+            .key
+            .iter()
+            .map(|(column, index)| {
+                let value = self
+                    .explicit
+                    .then(|| record.get(*index).unwrap_or_default());
+                (column.as_str(), value)
+            })
+            .collect::<Vec<_>>();
+        let value = self.explicit.then_some(violation.value);
+
+        match &mut self.output {
+            Output::Csv(writer) => {
+                let row = row.to_string();
+                let fields = [row.as_str()]
+                    .into_iter()
+                    .chain(key.iter().map(|(_, value)| value.unwrap_or_default()))
+                    .chain([
+                        violation.kind,
+                        violation.column.unwrap_or_default(),
+                        violation.rule,
+                        value.unwrap_or_default(),
+                    ]);
+                writer
+                    .write_record(fields)
+                    .map_err(|error| write_error(&self.path, error))
+            }
+            Output::Jsonl(sink) => {
+                let key = (!key.is_empty()).then(|| {
+                    key.iter()
+                        .map(|(column, value)| ((*column).to_string(), json!(value)))
+                        .collect::<Map<_, _>>()
+                });
+                let line = json!({
+                    "row": row,
+                    "key": key,
+                    "kind": violation.kind,
+                    "column": violation.column,
+                    "rule": violation.rule,
+                    "value": value
+                });
+                serde_json::to_writer(&mut **sink, &line)
+                    .map_err(|error| write_error(&self.path, error))?;
+                sink.write_all(b"\n")
+                    .map_err(|error| write_error(&self.path, error))
+            }
+        }
+    }
+
+    /// Flush the export and summarize it as `{path, format, rows, hash}` for the result.
+    pub(crate) fn finish(self) -> Result<Value, RefusalPayload> {
+        let sink = match self.output {
+            Output::Csv(writer) => (*writer).into_inner().map_err(|error| {
+                RefusalPayload::io(self.path.display().to_string(), error.to_string())
+            })?,
+            Output::Jsonl(sink) => *sink,
+        };
+        let (hash, _) = sink.finish()?;
+        Ok(json!({
+            "path": self.path.display().to_string(),
+            "format": self.format.as_str(),
+            "rows": self.rows,
+            "hash": hash
+        }))
+    }
+}
+
+fn write_error(path: &Path, error: impl ToString) -> RefusalPayload {
+    RefusalPayload::io(path.display().to_string(), error.to_string())
+}
@@ -0,0 +1,186 @@
+use std::fs;
+
+use serde_json::{Value, json};
+
+use crate::cli::args::NormalizeArgs;
+use crate::normalize::rules::{CellOutcome, normalize_value};
+use crate::output::json::{CommandOutput, ProfileRef};
+use crate::refusal::RefusalPayload;
+use crate::resolve::resolver::apply_extends;
+use crate::schema::{
+    NormalizeRule, ValidationMode, build_header_index, load_column_registry_aliases,
+    parse_profile_yaml, resolve_registry_path, validate_profile,
+};
+use crate::slice::{CsvSink, DatasetRows, ReadOptions};
+use crate::witness::append::append_for_command;
+
+struct NormalizedColumn<'a> {
+    name: &'a str,
+    index: usize,
+    rule: &'a NormalizeRule,
+    trim: bool,
+    changed_values: usize,
+    nulled_values: usize,
+    unparsed_dates: usize,
+}
+
+impl NormalizedColumn<'_> {
+    fn record(&mut self, outcome: CellOutcome) {
+        match outcome {
+            CellOutcome::Unchanged => {}
+            CellOutcome::Changed => self.changed_values += 1,
+            CellOutcome::Nulled => self.nulled_values += 1,
+            CellOutcome::DateUnparsed => self.unparsed_dates += 1,
+        }
+    }
+}
+
+pub fn run(
+    args: &NormalizeArgs,
+    no_witness: bool,
+    explicit: bool,
+    json_output: bool,
+) -> Result<CommandOutput, RefusalPayload> {
+    let profile_content = fs::read_to_string(&args.profile).map_err(|error| {
+        RefusalPayload::io(args.profile.display().to_string(), error.to_string())
+    })?;
+    let profile = apply_extends(parse_profile_yaml(&profile_content)?, &args.profile)?;
+    validate_profile(&profile, ValidationMode::Validate)?;
+
+    let rows = DatasetRows::open(&args.dataset, ReadOptions::from_profile(&profile))?;
+    let headers = rows.headers().clone();
+
+    let column_aliases = profile
+        .column_registry
+        .as_deref()
+        .map(|registry| {
+            load_column_registry_aliases(&resolve_registry_path(&args.profile, registry))
+        })
+        .transpose()?;
+    let available = build_header_index(&headers, column_aliases.as_ref());
+
+    let mut columns = Vec::with_capacity(profile.normalize.len());
+    let mut missing = Vec::new();
+    for (name, rule) in &profile.normalize {
+        match available.column_index(name) {
+            Some(index) => columns.push(NormalizedColumn {
+                name,
+                index,
+                rule,
+                trim: profile.equivalence_for(name).trim_strings.unwrap_or(false),
+                changed_values: 0,
+                nulled_values: 0,
+                unparsed_dates: 0,
+            }),
+            None => missing.push(name.clone()),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(RefusalPayload::column_not_found(
+            missing,
+            available.available(),
+        ));
+    }
+    columns.sort_by_key(|column| column.index);
+
+    let keep_csv = explicit || (!json_output && args.out.is_none());
+    let mut writer = csv::Writer::from_writer(CsvSink::open(args.out.as_deref(), keep_csv)?);
+    writer.write_record(&headers).map_err(|error| {
+        RefusalPayload::csv_parse("normalize", format!("failed to write header: {error}"))
+    })?;
+
+    let mut row_count = 0usize;
+    for record in rows {
+        let record = record?;
+        row_count += 1;
+        let mut cells = record.iter().map(ToOwned::to_owned).collect::<Vec<_>>();
+        for column in &mut columns {
+            if let Some(cell) = cells.get_mut(column.index) {
+                let (value, outcome) = normalize_value(column.rule, cell, column.trim);
+                column.record(outcome);
+                *cell = value;
+            }
+        }
+        writer.write_record(&cells).map_err(|error| {
+            RefusalPayload::csv_parse("normalize", format!("failed to write row: {error}"))
+        })?;
+    }
+
+    writer.flush().map_err(|error| {
+        RefusalPayload::io("normalize", format!("failed to flush output CSV: {error}"))
+    })?;
+    let (output_hash, csv_bytes) = writer
+        .into_inner()
+        .map_err(|error| {
+            RefusalPayload::io(
+                "normalize",
+                format!("failed to finalize output CSV: {error}"),
+            )
+        })?
+        .finish()?;
+
+    let column_summaries = columns
+        .iter()
+        .map(|column| {
+            json!({
+                "column": column.name,
+                "changed_values": column.changed_values,
+                "nulled_values": column.nulled_values,
+                "unparsed_dates": column.unparsed_dates
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = json!({
+        "input_path": args.dataset.display().to_string(),
+        "output_path": args.out.as_ref().map(|path| path.display().to_string()),
+        "profile_id": profile.profile_id.clone(),
+        "rows": row_count,
+        "columns": column_summaries,
+        "output_hash": output_hash
+    });
+
+    if let Some(csv_bytes) = csv_bytes {
+        result["normalized_csv"] =
+            Value::String(String::from_utf8(csv_bytes).map_err(|error| {
+                RefusalPayload::invalid_schema_single(
+                    "normalize",
+                    format!("normalized output was not valid UTF-8: {error}"),
+                )
+            })?);
+    }
+
+    let mut witness_result = result.clone();
+    if let Some(object) = witness_result.as_object_mut() {
+        object.remove("normalized_csv");
+    }
+    let witness_id = append_for_command(
+        "normalize",
+        &witness_result,
+        vec![args.dataset.clone(), args.profile.clone()],
+        json!({
+            "subcommand": "normalize",
+            "profile": args.profile.display().to_string()
+        }),
+        no_witness,
+    );
+
+    Ok(CommandOutput::success(result)
+        .with_profile_ref(ProfileRef::from_profile(&profile))
+        .with_witness_id(witness_id))
+}
+
+/// True when a `date_format` rule left values it could not parse in the output.
+pub fn has_unparsed_values(result: &Value) -> bool {
+    result
+        .get("columns")
+        .and_then(Value::as_array)
+        .is_some_and(|columns| {
+            columns.iter().any(|column| {
+                column
+                    .get("unparsed_dates")
+                    .and_then(Value::as_u64)
+                    .is_some_and(|count| count > 0)
+            })
+        })
+}
@@ -0,0 +1,80 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::schema::{CaseFold, NormalizeRule};
+
+const CURRENCY_SYMBOLS: [char; 12] = ['$', '€', '£', '¥', '¢', '₹', '₩', '₽', '₺', '₪', '₱', '₦'];
+const ISO_DATE: &str = "%Y-%m-%d";
+const ISO_DATETIME: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// What normalization did to a single cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellOutcome {
+    Unchanged,
+    Changed,
+    Nulled,
+    /// `date_format` was set but the value was neither in that format nor already ISO 8601.
+    DateUnparsed,
+}
+
+/// Apply `rule` to one cell. Steps run in the order the rule fields are declared: null tokens,
+/// currency symbols, thousands separator, date format, then case folding.
+pub fn normalize_value(rule: &NormalizeRule, value: &str, trim: bool) -> (String, CellOutcome) {
+    let trimmed = value.trim();
+    if !trimmed.is_empty() && rule.null_tokens.iter().any(|token| token.trim() == trimmed) {
+        return (String::new(), CellOutcome::Nulled);
+    }
+
+    let mut normalized = if trim { trimmed } else { value }.to_owned();
+    if rule.strip_currency == Some(true) {
+        normalized = normalized
+            .chars()
+            .filter(|ch| !CURRENCY_SYMBOLS.contains(ch))
+            .collect::<String>()
+            .trim()
+            .to_owned();
+    }
+    if let Some(separator) = rule.thousands_separator.as_deref() {
+        normalized = normalized.replace(separator, "");
+    }
+
+    let mut date_unparsed = false;
+    if let Some(format) = rule.date_format.as_deref()
+        && !normalized.trim().is_empty()
+    {
+        match canonical_date(normalized.trim(), format) {
+            Some(date) => normalized = date,
+            None => date_unparsed = true,
+        }
+    }
+
+    normalized = match rule.case {
+        Some(CaseFold::Lower) => normalized.to_lowercase(),
+        Some(CaseFold::Upper) => normalized.to_uppercase(),
+        None => normalized,
+    };
+
+    let outcome = if date_unparsed {
+        CellOutcome::DateUnparsed
+    } else if normalized == value {
+        CellOutcome::Unchanged
+    } else {
+        CellOutcome::Changed
+    };
+    (normalized, outcome)
+}
+
+fn canonical_date(value: &str, format: &str) -> Option<String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+        return Some(date.format(ISO_DATE).to_string());
+    }
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+        return Some(datetime.format(ISO_DATETIME).to_string());
+    }
+    [ISO_DATE, ISO_DATETIME]
+        .iter()
+        .any(|iso| {
+            NaiveDate::parse_from_str(value, iso).is_ok()
+                || NaiveDateTime::parse_from_str(value, iso).is_ok()
+        })
+        .then(|| value.to_owned())
+}
@@ -26,8 +26,12 @@ fn emit_human_value(subcommand: &str, value: &Value) {
     match subcommand {
         "validate" => emit_validate_result(value),
         "slice" => emit_slice_result(value),
+        "normalize" => emit_normalize_result(value),
+        "apply" => emit_apply_result(value),
         "stats" => emit_stats_result(value),
         "suggest-key" => emit_suggest_key_result(value),
+        "drift" => emit_drift_result(value),
+        "check" => emit_check_result(value),
         "freeze" => emit_freeze_result(value),
         "list" => emit_list_result(value),
         "show" => emit_show_result(value),
@@ -48,6 +52,60 @@ fn emit_human_value(subcommand: &str, value: &Value) {
     }
 }
 
+fn emit_normalize_result(value: &Value) {
+    if let Some(csv) = value.get("normalized_csv").and_then(Value::as_str) {
+        print!("{}", csv);
+        return;
+    }
+
+    if let Some(path) = value.get("output_path").and_then(Value::as_str) {
+        println!("✓ Wrote normalized CSV: {}", path);
+    }
+    if let Some(columns) = value.get("columns").and_then(Value::as_array) {
+        for column in columns {
+            println!(
+                "  {}: {} changed, {} nulled, {} unparsed dates",
+                column.get("column").and_then(Value::as_str).unwrap_or(""),
+                column
+                    .get("changed_values")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0),
+                column
+                    .get("nulled_values")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0),
+                column
+                    .get("unparsed_dates")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0)
+            );
+        }
+    }
+    if let Some(output_hash) = value.get("output_hash").and_then(Value::as_str) {
+        println!("  Output hash: {}", output_hash);
+    }
+}
+
+fn emit_apply_result(value: &Value) {
+    if let Some(csv) = value.get("scoped_csv").and_then(Value::as_str) {
+        print!("{}", csv);
+        return;
+    }
+
+    if let Some(path) = value.get("output_path").and_then(Value::as_str) {
+        println!("✓ Wrote scoped dataset: {}", path);
+    }
+    if let Some(profile_id) = value.get("profile_id").and_then(Value::as_str) {
+        println!("  Profile: {}", profile_id);
+    }
+    if let Some(manifest) = value.get("manifest_path").and_then(Value::as_str) {
+        println!("  Manifest: {}", manifest);
+    }
+    if let Some(output_hash) = value.get("output_hash").and_then(Value::as_str) {
+        println!("  Output hash: {}", output_hash);
+    }
+}
+
 fn emit_slice_result(value: &Value) {
     if let Some(csv) = value.get("slice_csv").and_then(Value::as_str) {
         print!("{}", csv);
@@ -130,9 +188,44 @@ fn emit_validate_result(value: &Value) {
 fn emit_stats_result(value: &Value) {
     if let Some(obj) = value.as_object() {
         println!("Profile Statistics:");
-        if let Some(columns) = obj.get("columns").and_then(|v| v.as_u64()) {
+        if let Some(rows) = obj.get("row_count").and_then(|v| v.as_u64()) {
+            println!("  Rows: {}", rows);
+        }
+        if let Some(columns) = obj.get("column_count").and_then(|v| v.as_u64()) {
             println!("  Total columns: {}", columns);
         }
+        for column in obj
+            .get("columns")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let name = column.get("name").and_then(|v| v.as_str()).unwrap_or("");
+            let inferred = column
+                .pointer("/type/inferred")
+                .and_then(|v| v.as_str())
+                .unwrap_or("empty");
+            let confidence = column
+                .pointer("/type/confidence")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0);
+            let null_rate = column
+                .get("null_rate")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0);
+            let uniqueness = column
+                .get("uniqueness")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0);
+            println!(
+                "  {}: {} ({:.0}%), nulls={:.1}%, unique={:.1}%",
+                name,
+                inferred,
+                confidence * 100.0,
+                null_rate * 100.0,
+                uniqueness * 100.0
+            );
+        }
         if let Some(key_columns) = obj.get("key_columns").and_then(|v| v.as_u64()) {
             println!("  Key columns: {}", key_columns);
         }
@@ -148,6 +241,63 @@ fn emit_stats_result(value: &Value) {
     }
 }
 
+fn emit_check_result(value: &Value) {
+    let rows = value.get("rows").and_then(Value::as_u64).unwrap_or(0);
+    let issues = value
+        .get("issues")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    if issues.is_empty() {
+        println!("✓ All constraints hold ({} rows)", rows);
+        return;
+    }
+    println!("Constraint violations ({} rows checked):", rows);
+    for issue in issues {
+        let subject = issue
+            .get("rule")
+            .or_else(|| issue.get("column"))
+            .and_then(Value::as_str)
+            .unwrap_or("");
+        match issue.get("violating_rows").and_then(Value::as_u64) {
+            Some(violating) => {
+                let constraint = issue
+                    .get("constraint")
+                    .and_then(Value::as_str)
+                    .unwrap_or("rule");
+                println!("  ✗ {}: {} ({} rows)", subject, constraint, violating);
+            }
+            None => println!("  ✗ {}: missing column", subject),
+        }
+    }
+}
+
+fn emit_drift_result(value: &Value) {
+    let issues = value
+        .get("issues")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    if issues.is_empty() {
+        println!("✓ No drift beyond the profile's thresholds");
+        return;
+    }
+    println!("Drift beyond thresholds:");
+    for issue in issues {
+        let column = issue.get("column").and_then(Value::as_str).unwrap_or("");
+        let measure = issue.get("measure").and_then(Value::as_str).unwrap_or("");
+        let observed = issue.get("value").and_then(Value::as_f64).unwrap_or(0.0);
+        let threshold = issue
+            .get("threshold")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0);
+        println!(
+            "  ✗ {}: {} = {:.4} (threshold {:.4})",
+            column, measure, observed, threshold
+        );
+    }
+}
+
 fn emit_suggest_key_result(value: &Value) {
     if let Some(obj) = value.as_object() {
         if let Some(candidates) = obj.get("candidates").and_then(|v| v.as_array()) {
@@ -176,6 +326,39 @@ fn emit_suggest_key_result(value: &Value) {
                 }
             }
         }
+        if let Some(composites) = obj.get("composite_candidates").and_then(|v| v.as_array())
+            && !composites.is_empty()
+        {
+            println!("Composite key suggestions:");
+            for composite in composites {
+                let columns = composite
+                    .get("columns")
+                    .and_then(|v| v.as_array())
+                    .map(|columns| {
+                        columns
+                            .iter()
+                            .filter_map(|column| column.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default();
+                let uniqueness = composite
+                    .get("uniqueness")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0);
+                let viable = composite
+                    .get("viable")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                let rank = composite.get("rank").and_then(|v| v.as_u64()).unwrap_or(0);
+
+                let status = if viable { "✓" } else { "✗" };
+                println!(
+                    "  {}. {} ({}) (uniqueness: {:.2})",
+                    rank, status, columns, uniqueness
+                );
+            }
+        }
     } else {
         // Fallback
         println!(
@@ -385,10 +568,10 @@ fn emit_pull_result(value: &Value) {
 
 fn is_issues_found(subcommand: &str, value: &Value) -> bool {
     match subcommand {
-        "lint" => value
-            .get("issues")
-            .and_then(Value::as_array)
-            .is_some_and(|issues| !issues.is_empty()),
+        "lint" => crate::lint::lint::has_actionable_issues(value),
+        "normalize" => crate::normalize::normalize::has_unparsed_values(value),
+        "drift" => crate::stats::drift::has_drift_issues(value),
+        "check" => crate::lint::lint::has_actionable_issues(value),
         "diff" => value
             .get("differences")
             .or_else(|| value.get("changes"))
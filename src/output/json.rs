@@ -136,10 +136,10 @@ pub fn emit(subcommand: &str, result: Result<CommandOutput, RefusalPayload>) ->
 
 fn is_issues_found(subcommand: &str, value: &Value) -> bool {
     match subcommand {
-        "lint" => value
-            .get("issues")
-            .and_then(Value::as_array)
-            .is_some_and(|issues| !issues.is_empty()),
+        "lint" => crate::lint::lint::has_actionable_issues(value),
+        "normalize" => crate::normalize::normalize::has_unparsed_values(value),
+        "drift" => crate::stats::drift::has_drift_issues(value),
+        "check" => crate::lint::lint::has_actionable_issues(value),
         "diff" => value
             .get("differences")
             .or_else(|| value.get("changes"))
@@ -1,6 +1,7 @@
 use serde_json::{Value, json};
 
-/// Generate JSON Schema for the Profile YAML format
+/// Generate JSON Schema for the Profile YAML format. Nested definitions live in helpers so the
+/// `json!` expansion stays under the default recursion limit.
 pub fn generate_profile_schema() -> Value {
     json!({
         "$schema": "https://json-schema.org/draft/2020-12/schema",
@@ -40,6 +41,16 @@ pub fn generate_profile_schema() -> Value {
                 "pattern": "^sha256:[a-f0-9]{64}$",
                 "description": "SHA256 hash of canonical profile, required for frozen profiles"
             },
+            "extends": {
+                "type": "string",
+                "minLength": 1,
+                "description": "Frozen parent profile ID whose include_columns, exclude_columns, key, equivalence and per-column settings are merged beneath this profile's"
+            },
+            "extends_sha256": {
+                "type": "string",
+                "pattern": "^sha256:[a-f0-9]{64}$",
+                "description": "The parent's profile_sha256, recorded at freeze; required for frozen profiles that extend another"
+            },
             "status": {
                 "type": "string",
                 "enum": ["draft", "frozen"],
@@ -47,9 +58,15 @@ pub fn generate_profile_schema() -> Value {
             },
             "format": {
                 "type": "string",
-                "enum": ["csv"],
-                "description": "Dataset format (only CSV supported in v0.1)"
+                "enum": ["csv", "xlsx", "parquet", "jsonl", "fixed_width"],
+                "description": "Dataset format"
             },
+            "sheet": {
+                "type": "string",
+                "minLength": 1,
+                "description": "Worksheet name for xlsx datasets; the first sheet when omitted"
+            },
+            "fixed_width": fixed_width_schema(),
             "column_registry": {
                 "type": "string",
                 "minLength": 1,
@@ -60,65 +77,7 @@ pub fn generate_profile_schema() -> Value {
                 "minLength": 1,
                 "description": "Optional upstream fingerprint ID whose peek output informed pre_parse directives"
             },
-            "pre_parse": {
-                "type": "object",
-                "description": "Optional witnessed slicing directives for pre-parser cleanup before normal profile operations",
-                "properties": {
-                    "expected_shape": {
-                        "type": "object",
-                        "properties": {
-                            "modal_column_count": {"type": "integer", "minimum": 1},
-                            "first_data_row": {"type": "integer", "minimum": 1},
-                            "header_rows_pattern": {
-                                "type": "array",
-                                "items": {"type": "string"}
-                            }
-                        },
-                        "additionalProperties": false
-                    },
-                    "slice": {
-                        "type": "object",
-                        "properties": {
-                            "mode": {
-                                "type": "string",
-                                "enum": ["preamble_skip", "multi_row_header", "preamble_with_units"]
-                            },
-                            "skip_rows": {"type": "integer", "minimum": 1},
-                            "header_at_row": {"type": "integer", "minimum": 1},
-                            "header_rows": {
-                                "type": "array",
-                                "items": {"type": "integer", "minimum": 1}
-                            },
-                            "header_merge": {
-                                "type": "object",
-                                "properties": {
-                                    "strategy": {
-                                        "type": "string",
-                                        "enum": ["ffill_concat", "concat_only", "first_non_empty"]
-                                    },
-                                    "separator": {"type": "string", "minLength": 1},
-                                    "empty_placeholder": {"type": "string", "minLength": 1}
-                                },
-                                "required": ["strategy"],
-                                "additionalProperties": false
-                            },
-                            "data_starts_at": {"type": "integer", "minimum": 1},
-                            "delimiter": {"type": "string", "minLength": 1, "maxLength": 1},
-                            "encoding": {"type": "string"},
-                            "preamble_capture": {"type": "boolean"},
-                            "unit_rows_capture": {"type": "boolean"},
-                            "unit_rows": {
-                                "type": "array",
-                                "items": {"type": "integer", "minimum": 1}
-                            }
-                        },
-                        "required": ["mode"],
-                        "additionalProperties": false
-                    }
-                },
-                "required": ["slice"],
-                "additionalProperties": false
-            },
+            "pre_parse": pre_parse_schema(),
             "hashing": {
                 "type": "object",
                 "properties": {
@@ -167,10 +126,33 @@ pub fn generate_profile_schema() -> Value {
                     "minLength": 1
                 },
                 "minItems": 1,
-                "description": "Column names to include in analysis (required for frozen profiles)"
-            }
+                "description": "Column names to include in analysis (required for frozen profiles); entries may be glob:-prefixed globs such as glob:balance_* or re:-prefixed regular expressions; unprefixed entries are literal names"
+            },
+            "exclude_columns": {
+                "type": "array",
+                "items": {
+                    "type": "string",
+                    "minLength": 1
+                },
+                "description": "Column names, glob:-prefixed globs or re:-prefixed regular expressions removed from include_columns"
+            },
+            "column_types": {
+                "type": "object",
+                "additionalProperties": {
+                    "type": "string",
+                    "enum": ["integer", "decimal", "boolean", "date", "datetime", "string"]
+                },
+                "description": "Optional declared value type per include_columns entry, checked by lint for type drift"
+            },
+            "normalize": normalize_schema(),
+            "column_equivalence": column_equivalence_schema(),
+            "drift": drift_schema(),
+            "constraints": constraints_schema()
         },
         "additionalProperties": false,
+        "dependentRequired": {
+            "extends_sha256": ["extends"]
+        },
         "allOf": [
             {
                 "if": {
@@ -213,6 +195,241 @@ pub fn generate_profile_schema() -> Value {
     })
 }
 
+/// Per-column `normalize` rules.
+fn normalize_schema() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": {
+            "type": "object",
+            "properties": {
+                "null_tokens": {
+                    "type": "array",
+                    "items": {"type": "string", "minLength": 1},
+                    "description": "Values (after trimming) rewritten to empty"
+                },
+                "strip_currency": {
+                    "type": "boolean",
+                    "description": "Remove currency symbols such as $, € and £"
+                },
+                "thousands_separator": {
+                    "type": "string",
+                    "minLength": 1,
+                    "maxLength": 1,
+                    "description": "Grouping character removed from numbers"
+                },
+                "date_format": {
+                    "type": "string",
+                    "description": "strftime input format; parsed dates are rewritten as ISO 8601"
+                },
+                "case": {
+                    "type": "string",
+                    "enum": ["lower", "upper"]
+                }
+            },
+            "additionalProperties": false
+        },
+        "description": "Optional per-column value normalization rules applied by profile normalize"
+    })
+}
+
+/// Per-column `column_equivalence` overrides.
+fn column_equivalence_schema() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": {
+            "type": "object",
+            "properties": {
+                "float_decimals": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": 15,
+                    "description": "Overrides equivalence.float_decimals for this column"
+                },
+                "trim_strings": {
+                    "type": "boolean",
+                    "description": "Overrides equivalence.trim_strings for this column"
+                },
+                "case_insensitive": {
+                    "type": "boolean",
+                    "description": "Compare values ignoring case"
+                },
+                "tolerance": {
+                    "type": "object",
+                    "properties": {
+                        "absolute": {"type": "number", "minimum": 0},
+                        "relative": {"type": "number", "minimum": 0}
+                    },
+                    "minProperties": 1,
+                    "additionalProperties": false,
+                    "description": "Numeric values within either bound compare equal"
+                }
+            },
+            "additionalProperties": false
+        },
+        "description": "Optional per-column equivalence overrides; equivalence supplies the defaults"
+    })
+}
+
+/// `drift` thresholds enforced by profile drift.
+fn drift_schema() -> Value {
+    let limit =
+        |description: &str| json!({"type": "number", "minimum": 0, "description": description});
+    json!({
+        "type": "object",
+        "properties": {
+            "null_rate_delta": limit("Largest allowed absolute change in a column's null rate"),
+            "uniqueness_delta": limit("Largest allowed absolute change in a column's uniqueness"),
+            "psi": limit("Largest allowed population stability index of a column's distribution"),
+            "ks": limit("Largest allowed Kolmogorov-Smirnov distance of a numeric column"),
+            "new_value_rate": limit("Largest allowed share of non-null values that are categories absent from the baseline")
+        },
+        "minProperties": 1,
+        "additionalProperties": false,
+        "description": "Optional thresholds profile drift enforces against a stats baseline; unset measures are reported but never flagged"
+    })
+}
+
+/// `constraints` checked by profile check.
+fn constraints_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "columns": {
+                "type": "object",
+                "additionalProperties": {
+                    "type": "object",
+                    "properties": {
+                        "not_null": {
+                            "type": "boolean",
+                            "description": "Every row must hold a non-blank value"
+                        },
+                        "unique": {
+                            "type": "boolean",
+                            "description": "No non-null value may appear in more than one row"
+                        },
+                        "allowed_values": {
+                            "type": "array",
+                            "items": {"type": "string", "minLength": 1},
+                            "description": "The only values non-null cells may hold"
+                        },
+                        "min": {"type": "number", "description": "Smallest allowed numeric value"},
+                        "max": {"type": "number", "description": "Largest allowed numeric value"},
+                        "pattern": {
+                            "type": "string",
+                            "description": "Regular expression every non-null value must match in full"
+                        }
+                    },
+                    "minProperties": 1,
+                    "additionalProperties": false
+                },
+                "description": "Per-column constraints keyed by include_columns entry"
+            },
+            "rules": {
+                "type": "array",
+                "items": {
+                    "type": "string",
+                    "pattern": "^.+(<=|>=|==|!=|<|>).+$"
+                },
+                "description": "Cross-column comparisons such as 'maturity_date > origination_date'"
+            }
+        },
+        "minProperties": 1,
+        "additionalProperties": false,
+        "description": "Optional data-quality constraints checked row by row by profile check"
+    })
+}
+
+/// `pre_parse` slicing directives.
+fn fixed_width_schema() -> Value {
+    json!({
+        "type": "object",
+        "description": "Column spans for fixed_width datasets; spans must not overlap",
+        "properties": {
+            "columns": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string", "minLength": 1},
+                        "start": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "1-indexed character position of the first character"
+                        },
+                        "length": {"type": "integer", "minimum": 1}
+                    },
+                    "required": ["name", "start", "length"],
+                    "additionalProperties": false
+                }
+            }
+        },
+        "required": ["columns"],
+        "additionalProperties": false
+    })
+}
+
+fn pre_parse_schema() -> Value {
+    json!({
+        "type": "object",
+        "description": "Optional witnessed slicing directives for pre-parser cleanup before normal profile operations",
+        "properties": {
+            "expected_shape": {
+                "type": "object",
+                "properties": {
+                    "modal_column_count": {"type": "integer", "minimum": 1},
+                    "first_data_row": {"type": "integer", "minimum": 1},
+                    "header_rows_pattern": {
+                        "type": "array",
+                        "items": {"type": "string"}
+                    }
+                },
+                "additionalProperties": false
+            },
+            "slice": {
+                "type": "object",
+                "properties": {
+                    "mode": {
+                        "type": "string",
+                        "enum": ["preamble_skip", "multi_row_header", "preamble_with_units"]
+                    },
+                    "skip_rows": {"type": "integer", "minimum": 1},
+                    "header_at_row": {"type": "integer", "minimum": 1},
+                    "header_rows": {
+                        "type": "array",
+                        "items": {"type": "integer", "minimum": 1}
+                    },
+                    "header_merge": {
+                        "type": "object",
+                        "properties": {
+                            "strategy": {
+                                "type": "string",
+                                "enum": ["ffill_concat", "concat_only", "first_non_empty"]
+                            },
+                            "separator": {"type": "string", "minLength": 1},
+                            "empty_placeholder": {"type": "string", "minLength": 1}
+                        },
+                        "required": ["strategy"],
+                        "additionalProperties": false
+                    },
+                    "data_starts_at": {"type": "integer", "minimum": 1},
+                    "delimiter": {"type": "string", "minLength": 1, "maxLength": 1},
+                    "encoding": {"type": "string"},
+                    "preamble_capture": {"type": "boolean"},
+                    "unit_rows_capture": {"type": "boolean"},
+                    "unit_rows": {
+                        "type": "array",
+                        "items": {"type": "integer", "minimum": 1}
+                    }
+                },
+                "required": ["mode"],
+                "additionalProperties": false
+            }
+        },
+        "required": ["slice"],
+        "additionalProperties": false
+    })
+}
+
 /// Generate JSON Schema for profile.discovery.v0 payloads.
 pub fn generate_discovery_schema() -> Value {
     json!({
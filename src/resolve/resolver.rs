@@ -4,7 +4,10 @@ use std::path::{Path, PathBuf};
 use serde_json::{Value, json};
 
 use crate::refusal::RefusalPayload;
-use crate::schema::{Profile, ProfileStatus, ValidationMode, parse_profile_yaml, validate_profile};
+use crate::schema::{
+    Profile, ProfileStatus, ValidationMode, parse_profile_yaml, resolve_registry_path,
+    validate_profile,
+};
 
 #[derive(Debug, Clone)]
 pub struct ResolvedProfile {
@@ -26,6 +29,10 @@ pub fn resolve_profile(profile_ref: &str) -> Result<ResolvedProfile, RefusalPayl
         return parse_profile_from_path(input_path);
     }
 
+    find_frozen_profile(profile_ref)
+}
+
+fn find_frozen_profile(profile_ref: &str) -> Result<ResolvedProfile, RefusalPayload> {
     let profiles = list_frozen_profiles()?;
     profiles
         .into_iter()
@@ -38,6 +45,71 @@ pub fn resolve_profile(profile_ref: &str) -> Result<ResolvedProfile, RefusalPayl
         })
 }
 
+/// Merge the frozen parent a draft `extends` into it (see [`Profile::inherit`]), inheriting the
+/// parent's `column_registry` as an absolute path resolved against the parent's location, and
+/// record the parent's `profile_sha256`, refusing when a pinned `extends_sha256` no longer
+/// matches. `profile_path` is where the draft was read from; a path-form `extends` is resolved
+/// against it. Frozen profiles were merged when they were frozen and are returned unchanged.
+pub fn apply_extends(mut profile: Profile, profile_path: &Path) -> Result<Profile, RefusalPayload> {
+    let Some(parent_ref) = profile.extends.clone() else {
+        return Ok(profile);
+    };
+    if profile.is_frozen() {
+        return Ok(profile);
+    }
+
+    let parent = resolve_parent(&parent_ref, profile_path)?;
+    let parent_sha256 = parent
+        .profile
+        .profile_sha256
+        .clone()
+        .ok_or_else(|| RefusalPayload::missing_field("profile_sha256"))?;
+    if let Some(pinned) = profile.extends_sha256.as_deref()
+        && pinned != parent_sha256
+    {
+        return Err(RefusalPayload::invalid_schema_single(
+            "extends_sha256",
+            format!("parent profile '{parent_ref}' is {parent_sha256}, not the pinned {pinned}"),
+        ));
+    }
+
+    if profile.column_registry.is_none()
+        && let Some(registry) = parent.profile.column_registry.as_deref()
+    {
+        // Absolute, so it keeps pointing at the parent's registry wherever the child lives
+        let registry_path = std::path::absolute(resolve_registry_path(&parent.path, registry))
+            .map_err(|error| RefusalPayload::io(registry.to_string(), error.to_string()))?;
+        profile.column_registry = Some(registry_path.display().to_string());
+    }
+    profile.inherit(&parent.profile);
+    profile.extends_sha256 = Some(parent_sha256);
+    Ok(profile)
+}
+
+/// Load the parent named by `extends` without merging a parent of its own: only a frozen
+/// profile may be extended, and a frozen profile was merged when it was frozen, so draft
+/// chains and cycles are refused before they are followed. A path is resolved against the
+/// child's directory, like `column_registry`; anything else is a frozen `profile_id`.
+fn resolve_parent(parent_ref: &str, child_path: &Path) -> Result<ResolvedProfile, RefusalPayload> {
+    let path = resolve_registry_path(child_path, parent_ref);
+    let parent = if path.is_file() {
+        ResolvedProfile {
+            profile: read_profile(&path)?,
+            path,
+        }
+    } else {
+        find_frozen_profile(parent_ref)?
+    };
+    if !parent.profile.is_frozen() {
+        return Err(RefusalPayload::invalid_schema_single(
+            "extends",
+            format!("parent profile '{parent_ref}' is not frozen"),
+        ));
+    }
+    validate_profile(&parent.profile, ValidationMode::Validate)?;
+    Ok(parent)
+}
+
 pub fn list_frozen_profiles() -> Result<Vec<ResolvedProfile>, RefusalPayload> {
     let mut entries = Vec::new();
     let directory = default_profile_directory()?;
@@ -59,10 +131,11 @@ pub fn list_frozen_profiles() -> Result<Vec<ResolvedProfile>, RefusalPayload> {
             continue;
         }
 
-        if let Ok(resolved) = parse_profile_from_path(&path)
-            && matches!(resolved.profile.status, ProfileStatus::Frozen)
+        if let Ok(profile) = read_profile(&path)
+            && matches!(profile.status, ProfileStatus::Frozen)
+            && validate_profile(&profile, ValidationMode::Validate).is_ok()
         {
-            entries.push(resolved);
+            entries.push(ResolvedProfile { path, profile });
         }
     }
 
@@ -84,9 +157,7 @@ pub fn list_frozen_profiles() -> Result<Vec<ResolvedProfile>, RefusalPayload> {
 }
 
 fn parse_profile_from_path(path: &Path) -> Result<ResolvedProfile, RefusalPayload> {
-    let content = fs::read_to_string(path)
-        .map_err(|error| RefusalPayload::io(path.display().to_string(), error.to_string()))?;
-    let profile = parse_profile_yaml(&content)?;
+    let profile = apply_extends(read_profile(path)?, path)?;
     validate_profile(&profile, ValidationMode::Validate)?;
 
     Ok(ResolvedProfile {
@@ -95,6 +166,12 @@ fn parse_profile_from_path(path: &Path) -> Result<ResolvedProfile, RefusalPayloa
     })
 }
 
+fn read_profile(path: &Path) -> Result<Profile, RefusalPayload> {
+    let content = fs::read_to_string(path)
+        .map_err(|error| RefusalPayload::io(path.display().to_string(), error.to_string()))?;
+    parse_profile_yaml(&content)
+}
+
 fn default_profile_directory() -> Result<PathBuf, RefusalPayload> {
     crate::paths::profile_dir_for_read()
         .map_err(|error| RefusalPayload::io("profile directory".to_string(), error.to_string()))
@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
+
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 
 use crate::refusal::RefusalPayload;
 use crate::schema::profile::{
-    Equivalence, Hashing, PreParse, Profile, ProfileFormat, ProfileStatus,
+    ColumnEquivalence, ColumnType, Constraints, DriftThresholds, Equivalence, FixedWidth, Hashing,
+    NormalizeRule, PreParse, Profile, ProfileFormat, ProfileStatus,
 };
 use crate::schema::validate::{ValidationMode, validate_profile};
 
@@ -16,9 +19,17 @@ struct CanonicalProfile<'a> {
     profile_version: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     profile_family: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extends: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extends_sha256: Option<&'a str>,
     status: ProfileStatus,
     format: ProfileFormat,
     #[serde(skip_serializing_if = "Option::is_none")]
+    sheet: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fixed_width: Option<&'a FixedWidth>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     column_registry: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     fingerprint_ref: Option<&'a str>,
@@ -31,6 +42,18 @@ struct CanonicalProfile<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     key: Option<&'a [String]>,
     include_columns: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exclude_columns: Option<&'a [String]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column_types: Option<&'a BTreeMap<String, ColumnType>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    normalize: Option<&'a BTreeMap<String, NormalizeRule>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column_equivalence: Option<&'a BTreeMap<String, ColumnEquivalence>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drift: Option<&'a DriftThresholds>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    constraints: Option<&'a Constraints>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize)]
@@ -80,8 +103,12 @@ impl<'a> From<&'a Profile> for CanonicalProfile<'a> {
             profile_id: profile.profile_id.as_deref(),
             profile_version: profile.profile_version,
             profile_family: profile.profile_family.as_deref(),
+            extends: profile.extends.as_deref(),
+            extends_sha256: profile.extends_sha256.as_deref(),
             status: profile.status,
             format: profile.format,
+            sheet: profile.sheet.as_deref(),
+            fixed_width: profile.fixed_width.as_ref(),
             column_registry: profile.column_registry.as_deref(),
             fingerprint_ref: profile.fingerprint_ref.as_deref(),
             pre_parse: profile.pre_parse.as_ref(),
@@ -89,6 +116,14 @@ impl<'a> From<&'a Profile> for CanonicalProfile<'a> {
             equivalence: profile.equivalence.as_ref().map(CanonicalEquivalence::from),
             key: Some(profile.key.as_slice()),
             include_columns: &profile.include_columns,
+            exclude_columns: (!profile.exclude_columns.is_empty())
+                .then_some(profile.exclude_columns.as_slice()),
+            column_types: (!profile.column_types.is_empty()).then_some(&profile.column_types),
+            normalize: (!profile.normalize.is_empty()).then_some(&profile.normalize),
+            column_equivalence: (!profile.column_equivalence.is_empty())
+                .then_some(&profile.column_equivalence),
+            drift: profile.drift.as_ref(),
+            constraints: profile.constraints.as_ref(),
         }
     }
 }
@@ -0,0 +1,97 @@
+use std::cmp::Ordering;
+
+use regex::Regex;
+
+/// Operators a cross-column rule may use, longest spellings first so `<=` is not read as `<`.
+const OPERATORS: [(&str, Comparison); 6] = [
+    ("<=", Comparison::LessOrEqual),
+    (">=", Comparison::GreaterOrEqual),
+    ("==", Comparison::Equal),
+    ("!=", Comparison::NotEqual),
+    ("<", Comparison::Less),
+    (">", Comparison::Greater),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+        }
+    }
+
+    /// Whether `left <op> right` holds when `left` compares to `right` as `ordering`.
+    pub const fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Less => ordering.is_lt(),
+            Self::LessOrEqual => ordering.is_le(),
+            Self::Greater => ordering.is_gt(),
+            Self::GreaterOrEqual => ordering.is_ge(),
+            Self::Equal => ordering.is_eq(),
+            Self::NotEqual => ordering.is_ne(),
+        }
+    }
+}
+
+/// One `constraints.rules` entry: `<column> <operator> <column>`, e.g.
+/// `maturity_date > origination_date`.
+#[derive(Debug, Clone)]
+pub struct ColumnRule {
+    pub rule: String,
+    pub left: String,
+    pub comparison: Comparison,
+    pub right: String,
+}
+
+impl ColumnRule {
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let (position, (operator, comparison)) = rule
+            .char_indices()
+            .find_map(|(position, _)| {
+                OPERATORS
+                    .iter()
+                    .find(|(operator, _)| rule[position..].starts_with(operator))
+                    .map(|entry| (position, *entry))
+            })
+            .ok_or_else(|| {
+                format!("rule '{rule}' must compare two columns with <, <=, >, >=, == or !=")
+            })?;
+        let left = rule[..position].trim();
+        let right = rule[position + operator.len()..].trim();
+        if left.is_empty() || right.is_empty() {
+            return Err(format!("rule '{rule}' must name a column on each side"));
+        }
+        if left.ends_with(['<', '>', '=', '!']) || right.starts_with(['<', '>', '=', '!']) {
+            return Err(format!("rule '{rule}' has an unknown operator"));
+        }
+        if left == right {
+            return Err(format!("rule '{rule}' compares a column with itself"));
+        }
+        Ok(Self {
+            rule: rule.to_string(),
+            left: left.to_string(),
+            comparison,
+            right: right.to_string(),
+        })
+    }
+}
+
+/// Compile a `pattern` constraint; like `re:` selectors, it must match the whole value.
+pub fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    Regex::new(&format!("^(?:{pattern})$"))
+        .map_err(|error| format!("invalid pattern '{pattern}': {error}"))
+}
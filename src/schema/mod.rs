@@ -1,18 +1,26 @@
 pub mod canonical;
+pub mod constraint;
 pub mod profile;
 pub mod registry;
+pub mod selector;
 pub mod validate;
 
 pub use canonical::{canonical_bytes, canonical_yaml, compute_profile_sha256};
+pub use constraint::{ColumnRule, Comparison, compile_pattern};
 pub use profile::{
-    Equivalence, EquivalenceOrder, ExpectedShape, HashAlgorithm, Hashing, HeaderMerge,
-    HeaderMergeStrategy, PreParse, Profile, ProfileFormat, ProfileStatus, SliceDirectives,
-    SliceMode,
+    CaseFold, ColumnConstraints, ColumnEquivalence, ColumnType, Constraints, DriftThresholds,
+    Equivalence, EquivalenceOrder, ExpectedShape, FixedWidth, FixedWidthColumn, HashAlgorithm,
+    Hashing, HeaderMerge, HeaderMergeStrategy, NormalizeRule, PreParse, Profile, ProfileFormat,
+    ProfileStatus, SliceDirectives, SliceMode, Tolerance,
 };
 pub use registry::{
     HeaderIndex, build_header_index, canonicalize_header_sequence, canonicalize_profile_column,
     load_column_registry_aliases, resolve_registry_path,
 };
+pub use selector::{
+    ColumnSelector, ResolvedColumns, SelectorMatch, has_column_selectors, profile_selects_column,
+    resolve_profile_columns,
+};
 pub use validate::{
     ValidationMode, is_valid_profile_family, is_valid_profile_sha256, parse_profile_yaml,
     validate_profile,
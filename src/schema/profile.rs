@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -20,12 +22,21 @@ impl ProfileStatus {
 #[serde(rename_all = "lowercase")]
 pub enum ProfileFormat {
     Csv,
+    Xlsx,
+    Parquet,
+    Jsonl,
+    #[serde(rename = "fixed_width")]
+    FixedWidth,
 }
 
 impl ProfileFormat {
     pub const fn as_str(self) -> &'static str {
         match self {
             Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+            Self::Parquet => "parquet",
+            Self::Jsonl => "jsonl",
+            Self::FixedWidth => "fixed_width",
         }
     }
 }
@@ -77,7 +88,164 @@ pub struct Equivalence {
     pub trim_strings: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    Integer,
+    Decimal,
+    Boolean,
+    Date,
+    Datetime,
+    String,
+}
+
+impl ColumnType {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Integer => "integer",
+            Self::Decimal => "decimal",
+            Self::Boolean => "boolean",
+            Self::Date => "date",
+            Self::Datetime => "datetime",
+            Self::String => "string",
+        }
+    }
+
+    /// Whether a value inferred as `observed` is acceptable in a column declared as `self`.
+    pub const fn accepts(self, observed: ColumnType) -> bool {
+        match self {
+            Self::String => true,
+            Self::Decimal => matches!(observed, Self::Integer | Self::Decimal),
+            Self::Datetime => matches!(observed, Self::Date | Self::Datetime),
+            Self::Integer => matches!(observed, Self::Integer),
+            Self::Boolean => matches!(observed, Self::Boolean),
+            Self::Date => matches!(observed, Self::Date),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseFold {
+    Lower,
+    Upper,
+}
+
+impl CaseFold {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Lower => "lower",
+            Self::Upper => "upper",
+        }
+    }
+}
+
+/// Per-column value normalization, applied in field order after optional trimming.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct NormalizeRule {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub null_tokens: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strip_currency: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thousands_separator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case: Option<CaseFold>,
+}
+
+/// Numeric tolerance for comparing values; either bound accepts a pair of values.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Tolerance {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub absolute: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative: Option<f64>,
+}
+
+/// Per-column equivalence override. Unset fields fall back to the profile-wide `equivalence`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnEquivalence {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub float_decimals: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trim_strings: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case_insensitive: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<Tolerance>,
+}
+
+/// Limits `profile drift` enforces against a statistics baseline. Measures without a limit are
+/// still reported but never raise an issue.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DriftThresholds {
+    /// Largest allowed absolute change in a column's null rate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub null_rate_delta: Option<f64>,
+    /// Largest allowed absolute change in a column's uniqueness (distinct values per row).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uniqueness_delta: Option<f64>,
+    /// Largest allowed population stability index of a numeric or categorical distribution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub psi: Option<f64>,
+    /// Largest allowed Kolmogorov-Smirnov distance of a numeric distribution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ks: Option<f64>,
+    /// Largest allowed share of rows holding categorical values the baseline never saw.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_value_rate: Option<f64>,
+}
+
+impl DriftThresholds {
+    /// Each measure name with its limit, in a fixed order.
+    pub const fn measures(&self) -> [(&'static str, Option<f64>); 5] {
+        [
+            ("null_rate_delta", self.null_rate_delta),
+            ("uniqueness_delta", self.uniqueness_delta),
+            ("psi", self.psi),
+            ("ks", self.ks),
+            ("new_value_rate", self.new_value_rate),
+        ]
+    }
+}
+
+/// What valid data looks like, checked row by row by `profile check`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Constraints {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub columns: BTreeMap<String, ColumnConstraints>,
+    /// Cross-column comparisons such as `maturity_date > origination_date`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<String>,
+}
+
+/// Per-column value constraints. Apart from `not_null`, each applies to non-null cells only.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnConstraints {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_null: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_values: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// Regular expression every value must match in full.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Profile {
     pub schema_version: u32,
@@ -97,9 +265,25 @@ pub struct Profile {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub frozen: Option<bool>,
 
+    /// Frozen parent profile, by profile ID, whose columns, key and equivalence this one builds on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+
+    /// The parent's `profile_sha256`; recorded at freeze, or pinned by hand in a draft.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends_sha256: Option<String>,
+
     pub status: ProfileStatus,
     pub format: ProfileFormat,
 
+    /// Worksheet to read when `format` is `xlsx`; the first sheet when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sheet: Option<String>,
+
+    /// Column spans of a `fixed_width` dataset; required for that format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed_width: Option<FixedWidth>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub column_registry: Option<String>,
 
@@ -119,6 +303,24 @@ pub struct Profile {
     pub key: Vec<String>,
 
     pub include_columns: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_columns: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_types: BTreeMap<String, ColumnType>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub normalize: BTreeMap<String, NormalizeRule>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_equivalence: BTreeMap<String, ColumnEquivalence>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drift: Option<DriftThresholds>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<Constraints>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -129,6 +331,30 @@ pub struct PreParse {
     pub slice: SliceDirectives,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct FixedWidth {
+    pub columns: Vec<FixedWidthColumn>,
+}
+
+/// One named field of a fixed-width record: `length` characters starting at the 1-based
+/// character position `start`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct FixedWidthColumn {
+    pub name: String,
+    pub start: usize,
+    pub length: usize,
+}
+
+impl FixedWidthColumn {
+    /// The last character position the column covers, or `None` when the span is empty or
+    /// ends past `usize::MAX`.
+    pub fn end(&self) -> Option<usize> {
+        self.start.checked_add(self.length)?.checked_sub(1)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(deny_unknown_fields)]
 pub struct ExpectedShape {
@@ -225,6 +451,64 @@ impl Profile {
         matches!(self.status, ProfileStatus::Frozen)
     }
 
+    /// Equivalence for `column`: its override where set, otherwise the profile-wide settings.
+    pub fn equivalence_for(&self, column: &str) -> ColumnEquivalence {
+        let global = self.equivalence.as_ref();
+        let column = self.column_equivalence.get(column);
+        ColumnEquivalence {
+            float_decimals: column
+                .and_then(|column| column.float_decimals)
+                .or_else(|| global.and_then(|global| global.float_decimals)),
+            trim_strings: column
+                .and_then(|column| column.trim_strings)
+                .or_else(|| global.and_then(|global| global.trim_strings)),
+            case_insensitive: column.and_then(|column| column.case_insensitive),
+            tolerance: column.and_then(|column| column.tolerance),
+        }
+    }
+
+    /// Merge `parent` under this profile. `include_columns`, `exclude_columns` and
+    /// `constraints.rules` keep the parent's entries first and append this profile's new ones;
+    /// a non-empty `key` replaces the parent's; `equivalence` and `drift` fields set here
+    /// override the parent's; and `column_types`, `normalize`, `column_equivalence` and
+    /// `constraints.columns` merge per column, this profile's entry winning. The parent's
+    /// `column_registry` is carried over by the resolver, which knows where the parent lives.
+    /// Everything else is this profile's own.
+    pub fn inherit(&mut self, parent: &Profile) {
+        inherit_entries(&mut self.include_columns, &parent.include_columns);
+        inherit_entries(&mut self.exclude_columns, &parent.exclude_columns);
+
+        if self.key.is_empty() {
+            self.key.clone_from(&parent.key);
+        }
+
+        if let Some(inherited) = parent.equivalence.as_ref() {
+            let equivalence = self.equivalence.get_or_insert_with(Equivalence::default);
+            equivalence.order = equivalence.order.or(inherited.order);
+            equivalence.float_decimals = equivalence.float_decimals.or(inherited.float_decimals);
+            equivalence.trim_strings = equivalence.trim_strings.or(inherited.trim_strings);
+        }
+
+        inherit_columns(&mut self.column_types, &parent.column_types);
+        inherit_columns(&mut self.normalize, &parent.normalize);
+        inherit_columns(&mut self.column_equivalence, &parent.column_equivalence);
+
+        if let Some(inherited) = parent.drift {
+            let drift = self.drift.get_or_insert_with(DriftThresholds::default);
+            drift.null_rate_delta = drift.null_rate_delta.or(inherited.null_rate_delta);
+            drift.uniqueness_delta = drift.uniqueness_delta.or(inherited.uniqueness_delta);
+            drift.psi = drift.psi.or(inherited.psi);
+            drift.ks = drift.ks.or(inherited.ks);
+            drift.new_value_rate = drift.new_value_rate.or(inherited.new_value_rate);
+        }
+
+        if let Some(inherited) = parent.constraints.as_ref() {
+            let constraints = self.constraints.get_or_insert_with(Constraints::default);
+            inherit_columns(&mut constraints.columns, &inherited.columns);
+            inherit_entries(&mut constraints.rules, &inherited.rules);
+        }
+    }
+
     pub fn fill_freeze_defaults(&mut self) {
         if self.hashing.is_none() {
             self.hashing = Some(Hashing::default());
@@ -236,3 +520,19 @@ impl Profile {
         }
     }
 }
+
+/// Put the parent's `inherited` entries first, followed by the ones only `own` lists.
+fn inherit_entries(own: &mut Vec<String>, inherited: &[String]) {
+    let additions = std::mem::replace(own, inherited.to_vec());
+    for entry in additions {
+        if !own.contains(&entry) {
+            own.push(entry);
+        }
+    }
+}
+
+fn inherit_columns<T: Clone>(own: &mut BTreeMap<String, T>, inherited: &BTreeMap<String, T>) {
+    for (column, value) in inherited {
+        own.entry(column.clone()).or_insert_with(|| value.clone());
+    }
+}
@@ -5,6 +5,7 @@ use std::path::{Path, PathBuf};
 use csv::StringRecord;
 use serde::Deserialize;
 
+use crate::jsonl::field_path_name;
 use crate::refusal::RefusalPayload;
 
 const COLUMN_NAME_CANONICAL_TYPE: &str = "column_name";
@@ -24,8 +25,13 @@ pub struct HeaderIndex {
 }
 
 impl HeaderIndex {
+    /// A JSON pointer spelling such as `/borrower/zip` also finds the dotted JSONL field path
+    /// `borrower.zip`.
     pub fn column_index(&self, column: &str) -> Option<usize> {
-        self.lookup.get(column).copied()
+        self.lookup
+            .get(column)
+            .or_else(|| self.lookup.get(field_path_name(column).as_ref()))
+            .copied()
     }
 
     pub fn available(&self) -> Vec<String> {
@@ -0,0 +1,207 @@
+use globset::{Glob, GlobMatcher};
+use regex::Regex;
+use serde::Serialize;
+
+use crate::schema::profile::Profile;
+use crate::schema::registry::HeaderIndex;
+
+const GLOB_PREFIX: &str = "glob:";
+const REGEX_PREFIX: &str = "re:";
+
+/// One `include_columns` / `exclude_columns` entry: a literal column name, a `glob:`-prefixed
+/// glob such as `glob:balance_*`, or a `re:`-prefixed regular expression that must match the
+/// whole name. Unprefixed entries are always literal, so headers like `Balance [USD]` keep
+/// their meaning.
+#[derive(Debug, Clone)]
+pub struct ColumnSelector {
+    entry: String,
+    matcher: Matcher,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Name,
+    Glob(GlobMatcher),
+    Regex(Regex),
+}
+
+impl ColumnSelector {
+    pub fn parse(entry: &str) -> Result<Self, String> {
+        let matcher = if let Some(pattern) = entry.strip_prefix(REGEX_PREFIX) {
+            let regex = Regex::new(&format!("^(?:{pattern})$"))
+                .map_err(|error| format!("invalid regex selector '{entry}': {error}"))?;
+            Matcher::Regex(regex)
+        } else if let Some(pattern) = entry.strip_prefix(GLOB_PREFIX) {
+            let glob = Glob::new(pattern)
+                .map_err(|error| format!("invalid glob selector '{entry}': {error}"))?;
+            Matcher::Glob(glob.compile_matcher())
+        } else {
+            Matcher::Name
+        };
+        Ok(Self {
+            entry: entry.to_string(),
+            matcher,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.entry
+    }
+
+    pub fn is_pattern(&self) -> bool {
+        !matches!(self.matcher, Matcher::Name)
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self.matcher {
+            Matcher::Name => "name",
+            Matcher::Glob(_) => "glob",
+            Matcher::Regex(_) => "regex",
+        }
+    }
+
+    pub fn matches(&self, column: &str) -> bool {
+        match &self.matcher {
+            Matcher::Name => self.entry == column,
+            Matcher::Glob(glob) => glob.is_match(column),
+            Matcher::Regex(regex) => regex.is_match(column),
+        }
+    }
+}
+
+/// The concrete columns a pattern selector matched in one dataset header.
+#[derive(Debug, Clone, Serialize)]
+pub struct SelectorMatch {
+    pub selector: String,
+    pub kind: &'static str,
+    pub list: &'static str,
+    pub columns: Vec<String>,
+}
+
+/// `include_columns` minus `exclude_columns`, resolved against a dataset header.
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedColumns {
+    /// Concrete profile columns present in the dataset, in profile order.
+    pub columns: Vec<String>,
+    /// Literal `include_columns` entries the dataset does not have.
+    pub missing: Vec<String>,
+    /// Dataset columns dropped by `exclude_columns`.
+    pub excluded: Vec<String>,
+    pub selectors: Vec<SelectorMatch>,
+}
+
+impl ResolvedColumns {
+    pub fn unmatched_selectors(&self) -> impl Iterator<Item = &SelectorMatch> {
+        self.selectors
+            .iter()
+            .filter(|selector| selector.columns.is_empty())
+    }
+}
+
+pub fn has_column_selectors(profile: &Profile) -> bool {
+    profile
+        .include_columns
+        .iter()
+        .chain(&profile.exclude_columns)
+        .filter_map(|entry| ColumnSelector::parse(entry).ok())
+        .any(|selector| selector.is_pattern())
+}
+
+/// Whether `column` is selected by the profile without looking at a dataset.
+pub fn profile_selects_column(profile: &Profile, column: &str) -> bool {
+    let matches_any = |entries: &[String]| {
+        entries.iter().any(|entry| {
+            ColumnSelector::parse(entry).is_ok_and(|selector| selector.matches(column))
+        })
+    };
+    matches_any(&profile.include_columns) && !matches_any(&profile.exclude_columns)
+}
+
+/// Resolve the profile's column selection against `header_names` (canonicalized headers).
+/// Literal names are looked up through `available`, so registry aliases still apply.
+pub fn resolve_profile_columns(
+    profile: &Profile,
+    header_names: &[String],
+    available: &HeaderIndex,
+) -> ResolvedColumns {
+    let mut resolved = ResolvedColumns::default();
+    let mut selected = Vec::new();
+
+    for entry in &profile.include_columns {
+        let Ok(selector) = ColumnSelector::parse(entry) else {
+            continue;
+        };
+        if !selector.is_pattern() {
+            if available.column_index(entry).is_some() {
+                push_unique(&mut selected, entry);
+            } else {
+                resolved.missing.push(entry.clone());
+            }
+            continue;
+        }
+
+        let columns = header_names
+            .iter()
+            .filter(|name| selector.matches(name))
+            .cloned()
+            .collect::<Vec<_>>();
+        for column in &columns {
+            push_unique(&mut selected, column);
+        }
+        resolved
+            .selectors
+            .push(selector_match(&selector, "include_columns", columns));
+    }
+
+    let excludes = profile
+        .exclude_columns
+        .iter()
+        .filter_map(|entry| ColumnSelector::parse(entry).ok())
+        .collect::<Vec<_>>();
+    for selector in excludes.iter().filter(|selector| selector.is_pattern()) {
+        let columns = header_names
+            .iter()
+            .filter(|name| selector.matches(name))
+            .cloned()
+            .collect::<Vec<_>>();
+        resolved
+            .selectors
+            .push(selector_match(selector, "exclude_columns", columns));
+    }
+
+    for column in selected {
+        if excludes.iter().any(|selector| selector.matches(&column)) {
+            resolved.excluded.push(column);
+        } else {
+            resolved.columns.push(column);
+        }
+    }
+    for name in header_names {
+        if excludes.iter().any(|selector| selector.matches(name))
+            && !resolved.excluded.contains(name)
+        {
+            resolved.excluded.push(name.clone());
+        }
+    }
+
+    resolved
+}
+
+fn selector_match(
+    selector: &ColumnSelector,
+    list: &'static str,
+    columns: Vec<String>,
+) -> SelectorMatch {
+    SelectorMatch {
+        selector: selector.as_str().to_string(),
+        kind: selector.kind(),
+        list,
+        columns,
+    }
+}
+
+fn push_unique(columns: &mut Vec<String>, column: &str) {
+    if !columns.iter().any(|existing| existing == column) {
+        columns.push(column.to_string());
+    }
+}
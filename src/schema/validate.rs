@@ -1,7 +1,14 @@
+use std::collections::HashSet;
+
+use chrono::format::{Item, StrftimeItems};
+
+use crate::encoding::encoding_for_label;
 use crate::refusal::RefusalPayload;
+use crate::schema::constraint::{ColumnRule, compile_pattern};
 use crate::schema::profile::{
-    HashAlgorithm, HeaderMergeStrategy, Profile, ProfileStatus, SliceMode,
+    HashAlgorithm, HeaderMergeStrategy, Profile, ProfileFormat, ProfileStatus, SliceMode,
 };
+use crate::schema::selector::{ColumnSelector, profile_selects_column};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ValidationMode {
@@ -18,10 +25,21 @@ pub fn validate_profile(profile: &Profile, mode: ValidationMode) -> Result<(), R
         return Err(invalid_schema("schema_version", "must be 1"));
     }
 
-    if !matches!(profile.format, crate::schema::ProfileFormat::Csv) {
-        return Err(invalid_schema("format", "must be csv"));
+    if let Some(sheet) = profile.sheet.as_deref() {
+        if sheet.trim().is_empty() {
+            return Err(invalid_schema(
+                "sheet",
+                "must be a non-empty sheet name when set",
+            ));
+        }
+        if profile.format != ProfileFormat::Xlsx {
+            return Err(invalid_schema("sheet", "only applies to format xlsx"));
+        }
     }
 
+    validate_fixed_width(profile, mode)?;
+    validate_extends(profile)?;
+
     if profile
         .column_registry
         .as_deref()
@@ -61,6 +79,45 @@ pub fn validate_profile(profile: &Profile, mode: ValidationMode) -> Result<(), R
         ));
     }
 
+    if profile
+        .exclude_columns
+        .iter()
+        .any(|column| column.trim().is_empty())
+    {
+        return Err(invalid_schema(
+            "exclude_columns",
+            "entries must be non-empty strings",
+        ));
+    }
+
+    for (field, entries) in [
+        ("include_columns", &profile.include_columns),
+        ("exclude_columns", &profile.exclude_columns),
+    ] {
+        if let Some(error) = entries
+            .iter()
+            .find_map(|entry| ColumnSelector::parse(entry).err())
+        {
+            return Err(invalid_schema(field, error));
+        }
+    }
+
+    if let Some(column) = profile
+        .column_types
+        .keys()
+        .find(|column| !profile_selects_column(profile, column))
+    {
+        return Err(invalid_schema(
+            "column_types",
+            format!("column '{column}' is not selected by include_columns"),
+        ));
+    }
+
+    validate_normalize(profile)?;
+    validate_column_equivalence(profile)?;
+    validate_drift(profile)?;
+    validate_constraints(profile)?;
+
     if matches!(mode, ValidationMode::Freeze) && profile.include_columns.is_empty() {
         return Err(invalid_schema(
             "include_columns",
@@ -137,19 +194,166 @@ pub fn validate_profile(profile: &Profile, mode: ValidationMode) -> Result<(), R
     Ok(())
 }
 
+fn validate_extends(profile: &Profile) -> Result<(), RefusalPayload> {
+    let Some(extends) = profile.extends.as_deref() else {
+        if profile.extends_sha256.is_some() {
+            return Err(invalid_schema("extends_sha256", "requires extends"));
+        }
+        return Ok(());
+    };
+
+    if extends.trim().is_empty() {
+        return Err(invalid_schema(
+            "extends",
+            "must be a non-empty profile ID when set",
+        ));
+    }
+    if profile.profile_id.as_deref() == Some(extends) {
+        return Err(invalid_schema("extends", "a profile cannot extend itself"));
+    }
+
+    match profile.extends_sha256.as_deref() {
+        Some(sha) if !is_valid_profile_sha256(sha) => Err(invalid_schema(
+            "extends_sha256",
+            "extends_sha256 must match sha256:<64 lowercase hex chars>",
+        )),
+        None if profile.is_frozen() => Err(missing_field("extends_sha256")),
+        _ => Ok(()),
+    }
+}
+
+fn validate_fixed_width(profile: &Profile, mode: ValidationMode) -> Result<(), RefusalPayload> {
+    let Some(fixed_width) = profile.fixed_width.as_ref() else {
+        if profile.format == ProfileFormat::FixedWidth {
+            return Err(invalid_schema(
+                "fixed_width",
+                "column spans are required for format fixed_width",
+            ));
+        }
+        return Ok(());
+    };
+    if profile.format != ProfileFormat::FixedWidth {
+        return Err(invalid_schema(
+            "fixed_width",
+            "only applies to format fixed_width",
+        ));
+    }
+    if matches!(mode, ValidationMode::Freeze) && fixed_width.columns.is_empty() {
+        return Err(invalid_schema(
+            "fixed_width.columns",
+            "must be non-empty for freeze",
+        ));
+    }
+
+    let mut names = HashSet::new();
+    let mut spans = Vec::with_capacity(fixed_width.columns.len());
+    for column in &fixed_width.columns {
+        if column.name.trim().is_empty() {
+            return Err(invalid_schema(
+                "fixed_width.columns",
+                "names must be non-empty strings",
+            ));
+        }
+        if !names.insert(column.name.as_str()) {
+            return Err(invalid_schema(
+                "fixed_width.columns",
+                format!("column '{}' is declared more than once", column.name),
+            ));
+        }
+        if column.start == 0 {
+            return Err(invalid_schema(
+                "fixed_width.columns",
+                format!(
+                    "column '{}' start is a 1-indexed character position and must be positive",
+                    column.name
+                ),
+            ));
+        }
+        if column.length == 0 {
+            return Err(invalid_schema(
+                "fixed_width.columns",
+                format!("column '{}' length must be positive", column.name),
+            ));
+        }
+        let Some(end) = column.end() else {
+            return Err(invalid_schema(
+                "fixed_width.columns",
+                format!(
+                    "column '{}' ends past the largest character position",
+                    column.name
+                ),
+            ));
+        };
+        spans.push((column, end));
+    }
+
+    spans.sort_by_key(|(column, _)| column.start);
+    if let Some([(left, left_end), (right, right_end)]) =
+        spans.windows(2).find(|pair| pair[0].1 >= pair[1].0.start)
+    {
+        return Err(invalid_schema(
+            "fixed_width.columns",
+            format!(
+                "columns '{}' ({}-{left_end}) and '{}' ({}-{right_end}) overlap",
+                left.name, left.start, right.name, right.start
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
 fn validate_pre_parse(profile: &Profile) -> Result<(), RefusalPayload> {
     let Some(pre_parse) = profile.pre_parse.as_ref() else {
         return Ok(());
     };
+    if matches!(
+        profile.format,
+        ProfileFormat::Parquet | ProfileFormat::Jsonl
+    ) {
+        return Err(invalid_schema(
+            "pre_parse",
+            format!("does not apply to format {}", profile.format.as_str()),
+        ));
+    }
     let slice = &pre_parse.slice;
 
+    if profile.format == ProfileFormat::FixedWidth {
+        if slice.delimiter.is_some() {
+            return Err(invalid_schema(
+                "pre_parse.slice.delimiter",
+                "does not apply to format fixed_width",
+            ));
+        }
+        if slice.mode == SliceMode::MultiRowHeader || !slice.header_rows.is_empty() {
+            return Err(invalid_schema(
+                "pre_parse.slice.header_rows",
+                "fixed_width column names come from fixed_width.columns",
+            ));
+        }
+    }
+
+    if profile.format == ProfileFormat::Xlsx {
+        if slice.delimiter.is_some() {
+            return Err(invalid_schema(
+                "pre_parse.slice.delimiter",
+                "does not apply to format xlsx",
+            ));
+        }
+        if slice.encoding.is_some() {
+            return Err(invalid_schema(
+                "pre_parse.slice.encoding",
+                "does not apply to format xlsx",
+            ));
+        }
+    }
+
     if let Some(encoding) = slice.encoding.as_deref()
-        && !encoding.eq_ignore_ascii_case("utf-8")
-        && !encoding.eq_ignore_ascii_case("utf8")
+        && encoding_for_label(encoding).is_none()
     {
         return Err(invalid_schema(
             "pre_parse.slice.encoding",
-            "only utf-8 is currently supported",
+            format!("unsupported encoding '{encoding}'"),
         ));
     }
 
@@ -363,6 +567,183 @@ fn missing_field(field: &str) -> RefusalPayload {
     RefusalPayload::missing_field(field)
 }
 
+fn validate_normalize(profile: &Profile) -> Result<(), RefusalPayload> {
+    for (column, rule) in &profile.normalize {
+        if !profile_selects_column(profile, column) {
+            return Err(invalid_schema(
+                "normalize",
+                format!("column '{column}' is not selected by include_columns"),
+            ));
+        }
+
+        if rule.null_tokens.iter().any(|token| token.trim().is_empty()) {
+            return Err(invalid_schema(
+                format!("normalize.{column}.null_tokens"),
+                "entries must be non-empty strings",
+            ));
+        }
+
+        if let Some(separator) = rule.thousands_separator.as_deref() {
+            let mut chars = separator.chars();
+            let valid = matches!(
+                (chars.next(), chars.next()),
+                (Some(ch), None) if !ch.is_ascii_digit()
+            );
+            if !valid {
+                return Err(invalid_schema(
+                    format!("normalize.{column}.thousands_separator"),
+                    "must be exactly one non-digit character",
+                ));
+            }
+        }
+
+        if let Some(format) = rule.date_format.as_deref()
+            && (format.trim().is_empty()
+                || StrftimeItems::new(format).any(|item| matches!(item, Item::Error)))
+        {
+            return Err(invalid_schema(
+                format!("normalize.{column}.date_format"),
+                "must be a valid strftime format",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_column_equivalence(profile: &Profile) -> Result<(), RefusalPayload> {
+    for (column, equivalence) in &profile.column_equivalence {
+        if !profile_selects_column(profile, column) {
+            return Err(invalid_schema(
+                "column_equivalence",
+                format!("column '{column}' is not selected by include_columns"),
+            ));
+        }
+
+        let Some(tolerance) = equivalence.tolerance else {
+            continue;
+        };
+        if tolerance.absolute.is_none() && tolerance.relative.is_none() {
+            return Err(invalid_schema(
+                format!("column_equivalence.{column}.tolerance"),
+                "must set absolute or relative",
+            ));
+        }
+        for (name, bound) in [
+            ("absolute", tolerance.absolute),
+            ("relative", tolerance.relative),
+        ] {
+            if bound.is_some_and(|bound| !bound.is_finite() || bound < 0.0) {
+                return Err(invalid_schema(
+                    format!("column_equivalence.{column}.tolerance.{name}"),
+                    "must be a non-negative number",
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_drift(profile: &Profile) -> Result<(), RefusalPayload> {
+    let Some(drift) = profile.drift else {
+        return Ok(());
+    };
+    let measures = drift.measures();
+    if measures.iter().all(|(_, limit)| limit.is_none()) {
+        return Err(invalid_schema("drift", "must set at least one threshold"));
+    }
+    for (name, limit) in measures {
+        if limit.is_some_and(|limit| !limit.is_finite() || limit < 0.0) {
+            return Err(invalid_schema(
+                format!("drift.{name}"),
+                "must be a non-negative number",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_constraints(profile: &Profile) -> Result<(), RefusalPayload> {
+    let Some(constraints) = profile.constraints.as_ref() else {
+        return Ok(());
+    };
+    if constraints.columns.is_empty() && constraints.rules.is_empty() {
+        return Err(invalid_schema(
+            "constraints",
+            "must set column constraints or rules",
+        ));
+    }
+
+    for (column, constraint) in &constraints.columns {
+        if !profile_selects_column(profile, column) {
+            return Err(invalid_schema(
+                "constraints.columns",
+                format!("column '{column}' is not selected by include_columns"),
+            ));
+        }
+        let field = |name: &str| format!("constraints.columns.{column}.{name}");
+
+        if constraint.not_null.is_none()
+            && constraint.unique.is_none()
+            && constraint.allowed_values.is_empty()
+            && constraint.min.is_none()
+            && constraint.max.is_none()
+            && constraint.pattern.is_none()
+        {
+            return Err(invalid_schema(
+                format!("constraints.columns.{column}"),
+                "must set at least one constraint",
+            ));
+        }
+        if constraint
+            .allowed_values
+            .iter()
+            .any(|value| value.trim().is_empty())
+        {
+            return Err(invalid_schema(
+                field("allowed_values"),
+                "entries must be non-empty strings",
+            ));
+        }
+        for (name, bound) in [("min", constraint.min), ("max", constraint.max)] {
+            if bound.is_some_and(|bound| !bound.is_finite()) {
+                return Err(invalid_schema(field(name), "must be a finite number"));
+            }
+        }
+        if let (Some(min), Some(max)) = (constraint.min, constraint.max)
+            && min > max
+        {
+            return Err(invalid_schema(field("min"), "must not exceed max"));
+        }
+        if let Some(pattern) = constraint.pattern.as_deref()
+            && let Err(error) = compile_pattern(pattern)
+        {
+            return Err(invalid_schema(field("pattern"), error));
+        }
+    }
+
+    for rule in &constraints.rules {
+        let rule =
+            ColumnRule::parse(rule).map_err(|error| invalid_schema("constraints.rules", error))?;
+        if let Some(column) = [&rule.left, &rule.right]
+            .into_iter()
+            .find(|column| !profile_selects_column(profile, column))
+        {
+            return Err(invalid_schema(
+                "constraints.rules",
+                format!(
+                    "rule '{}' names column '{column}', which is not selected by include_columns",
+                    rule.rule
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 fn invalid_schema(field: impl Into<String>, error: impl Into<String>) -> RefusalPayload {
     RefusalPayload::invalid_schema_single(field, error)
 }
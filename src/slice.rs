@@ -1,28 +1,236 @@
+use std::collections::BTreeMap;
 use std::fs::{self, File};
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
+use csv::StringRecord;
 use serde_json::{Value, json};
 
 use crate::cli::args::{HeaderMergeStrategyArg, SliceArgs, SliceModeArg};
+use crate::columnar::{ParquetRows, is_parquet_path, open_parquet};
+use crate::compression::uncompressed_path;
+use crate::dialect::{Dialect, QuoteState, advance, sniff_dialect};
+use crate::encoding::{DecodedFile, SourceEncoding, open_decoded, sniff_encoding};
+use crate::fixed_width::FixedWidthRows;
+use crate::jsonl::{FieldPath, JsonlRows, is_jsonl_path, open_jsonl};
 use crate::output::json::{CommandOutput, ProfileRef};
 use crate::refusal::RefusalPayload;
-use crate::resolve::resolver::resolve_profile;
+use crate::resolve::resolver::{apply_extends, resolve_profile};
 use crate::schema::{
-    HeaderMerge, HeaderMergeStrategy, PreParse, Profile, SliceDirectives, SliceMode,
-    ValidationMode, parse_profile_yaml, validate_profile,
+    FixedWidth, FixedWidthColumn, HeaderMerge, HeaderMergeStrategy, PreParse, Profile,
+    ProfileFormat, SliceDirectives, SliceMode, ValidationMode, parse_profile_yaml,
+    validate_profile,
 };
 use crate::witness::append::append_for_command;
+use crate::workbook::{Worksheet, is_workbook_path, read_worksheet};
+
+/// How a dataset is read. Without a declared format the path's extension decides it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadOptions<'a> {
+    pub format: Option<ProfileFormat>,
+    pub pre_parse: Option<&'a PreParse>,
+    pub sheet: Option<&'a str>,
+    pub fixed_width: Option<&'a FixedWidth>,
+    /// JSONL records scanned to discover field paths; every record when unset.
+    pub path_sample: Option<usize>,
+}
+
+impl<'a> ReadOptions<'a> {
+    pub fn from_profile(profile: &'a Profile) -> Self {
+        Self {
+            format: Some(profile.format),
+            pre_parse: profile.pre_parse.as_ref(),
+            sheet: profile.sheet.as_deref(),
+            fixed_width: profile.fixed_width.as_ref(),
+            path_sample: None,
+        }
+    }
+
+    fn format_for(&self, path: &Path) -> ProfileFormat {
+        self.format.unwrap_or_else(|| format_for_path(path))
+    }
+}
+
+/// The format a dataset's extension implies, looking past a `.gz`/`.zst` suffix; anything
+/// unrecognized reads as delimited text.
+pub(crate) fn format_for_path(path: &Path) -> ProfileFormat {
+    let path = uncompressed_path(path);
+    let path = path.as_ref();
+    if is_workbook_path(path) {
+        ProfileFormat::Xlsx
+    } else if is_parquet_path(path) {
+        ProfileFormat::Parquet
+    } else if is_jsonl_path(path) {
+        ProfileFormat::Jsonl
+    } else {
+        ProfileFormat::Csv
+    }
+}
 
 pub fn headers_from_pre_parse(
     path: &Path,
     pre_parse: &PreParse,
 ) -> Result<Vec<String>, RefusalPayload> {
-    let delimiter = resolve_delimiter(&pre_parse.slice)?;
-    let rows = read_physical_rows(path, delimiter)?;
-    let plan = build_plan(&pre_parse.slice)?;
-    let slice = build_slice(&rows, &plan, &pre_parse.slice)?;
-    Ok(slice.headers)
+    let options = ReadOptions {
+        pre_parse: Some(pre_parse),
+        ..ReadOptions::default()
+    };
+    let origin = RowOrigin::open(path, &options, &pre_parse.slice)?;
+    let plan = build_plan(&pre_parse.slice, &origin)?;
+    Ok(scan_slice(path, &origin, &plan, &pre_parse.slice)?.headers)
+}
+
+/// Apply slice directives and return the merged header plus a stream of padded data rows.
+fn records_from_directives(
+    path: &Path,
+    origin: RowOrigin,
+    directives: &SliceDirectives,
+) -> Result<(Vec<String>, SliceDataRows), RefusalPayload> {
+    let plan = build_plan(directives, &origin)?;
+    let shape = scan_slice(path, &origin, &plan, directives)?;
+    let rows = SliceDataRows::open(path, &plan, &shape)?;
+    Ok((shape.headers, rows))
+}
+
+/// Dataset records read through a profile's `pre_parse` directives when it declares them,
+/// or straight from the header row otherwise. Workbook sheets always go through the slice
+/// engine; without directives their header is the first filled row. Fixed-width files go
+/// through it too, with column names taken from the profile's spans. JSONL records are
+/// flattened onto the dotted field paths discovered in them. Delimited files are read in the
+/// dialect sniffed from their first lines, keeping any delimiter `pre_parse` declares.
+pub struct DatasetRows {
+    headers: StringRecord,
+    source: RowSource,
+    field_paths: Option<Vec<FieldPath>>,
+    dialect: Option<Dialect>,
+    path: String,
+}
+
+enum RowSource {
+    Csv(Box<csv::StringRecordsIntoIter<DecodedFile>>),
+    PreParsed(SliceDataRows),
+    Parquet(ParquetRows),
+    Jsonl(JsonlRows),
+}
+
+impl DatasetRows {
+    pub fn open(path: &Path, options: ReadOptions<'_>) -> Result<Self, RefusalPayload> {
+        let mut field_paths = None;
+        let mut dialect = None;
+        let (headers, source) = match (options.format_for(path), options.pre_parse) {
+            (ProfileFormat::Parquet, _) => {
+                let (headers, rows) = open_parquet(path)?;
+                (StringRecord::from(headers), RowSource::Parquet(rows))
+            }
+            (ProfileFormat::Jsonl, _) => {
+                let (fields, rows) = open_jsonl(path, options.path_sample)?;
+                let headers = fields
+                    .iter()
+                    .map(|field| field.name.as_str())
+                    .collect::<StringRecord>();
+                field_paths = Some(fields);
+                (headers, RowSource::Jsonl(rows))
+            }
+            (ProfileFormat::Csv, None) => {
+                let encoding = sniff_encoding(path, None)?;
+                let sniffed = sniff_dialect(path, encoding, None, true)?;
+                let mut reader = csv::ReaderBuilder::new()
+                    .delimiter(sniffed.delimiter)
+                    .quote(sniffed.quote)
+                    .has_headers(sniffed.has_header)
+                    .from_reader(open_decoded(path, encoding)?);
+                let mut headers = reader
+                    .headers()
+                    .map_err(|error| {
+                        RefusalPayload::csv_parse(path.display().to_string(), error.to_string())
+                    })?
+                    .clone();
+                // A headerless file keeps its first record as data and numbers its columns.
+                if !sniffed.has_header {
+                    headers = (1..=headers.len())
+                        .map(|index| format!("column_{index}"))
+                        .collect();
+                }
+                dialect = Some(sniffed);
+                (headers, RowSource::Csv(Box::new(reader.into_records())))
+            }
+            (ProfileFormat::FixedWidth, pre_parse) => {
+                let directives = pre_parse.map_or_else(
+                    || default_directives(SliceMode::PreambleSkip),
+                    |pre_parse| pre_parse.slice.clone(),
+                );
+                let origin = RowOrigin::open(path, &options, &directives)?;
+                let (headers, rows) = records_from_directives(path, origin, &directives)?;
+                (StringRecord::from(headers), RowSource::PreParsed(rows))
+            }
+            (_, Some(pre_parse)) => {
+                let origin = RowOrigin::open(path, &options, &pre_parse.slice)?;
+                dialect = origin.dialect();
+                let (headers, rows) = records_from_directives(path, origin, &pre_parse.slice)?;
+                (StringRecord::from(headers), RowSource::PreParsed(rows))
+            }
+            (ProfileFormat::Xlsx, None) => {
+                let sheet = read_worksheet(path, options.sheet)?;
+                let header_at_row = sheet.first_filled_row().ok_or_else(|| {
+                    RefusalPayload::empty_with_reason(path.display().to_string(), "no rows")
+                })?;
+                let directives = SliceDirectives {
+                    header_at_row: Some(header_at_row),
+                    ..default_directives(SliceMode::PreambleSkip)
+                };
+                let origin = RowOrigin::Sheet(Rc::new(sheet));
+                let (headers, rows) = records_from_directives(path, origin, &directives)?;
+                (StringRecord::from(headers), RowSource::PreParsed(rows))
+            }
+        };
+
+        if headers.is_empty() {
+            return Err(RefusalPayload::empty_with_reason(
+                path.display().to_string(),
+                "no header row",
+            ));
+        }
+
+        Ok(Self {
+            headers,
+            source,
+            field_paths,
+            dialect,
+            path: path.display().to_string(),
+        })
+    }
+
+    pub fn headers(&self) -> &StringRecord {
+        &self.headers
+    }
+
+    /// The JSON kinds seen at each header's field path, for JSONL datasets.
+    pub(crate) fn field_paths(&self) -> Option<&[FieldPath]> {
+        self.field_paths.as_deref()
+    }
+
+    /// The dialect a delimited dataset is read in; `None` for other formats.
+    pub(crate) fn dialect(&self) -> Option<Dialect> {
+        self.dialect
+    }
+}
+
+impl Iterator for DatasetRows {
+    type Item = Result<StringRecord, RefusalPayload>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.source {
+            RowSource::Csv(records) => {
+                Some(records.next()?.map_err(|error| {
+                    RefusalPayload::csv_parse(self.path.clone(), error.to_string())
+                }))
+            }
+            RowSource::PreParsed(rows) => Some(rows.next()?.map(StringRecord::from)),
+            RowSource::Parquet(rows) => Some(rows.next()?.map(StringRecord::from)),
+            RowSource::Jsonl(rows) => Some(rows.next()?.map(StringRecord::from)),
+        }
+    }
 }
 
 pub fn run(
@@ -39,31 +247,29 @@ pub fn run(
     let (directives, mut warnings) = effective_directives(args, profile)?;
     validate_directives(&directives)?;
 
-    let delimiter = resolve_delimiter(&directives)?;
-    let rows = read_physical_rows(&args.file, delimiter)?;
-    let plan = build_plan(&directives)?;
-    let slice = build_slice(&rows, &plan, &directives)?;
-    if let Some(warning) = modal_column_count_warning(profile, slice.headers.len()) {
+    let options = ReadOptions {
+        format: profile.map(|profile| profile.format),
+        pre_parse: None,
+        sheet: args
+            .sheet
+            .as_deref()
+            .or_else(|| profile.and_then(|profile| profile.sheet.as_deref())),
+        fixed_width: profile.and_then(|profile| profile.fixed_width.as_ref()),
+        path_sample: None,
+    };
+    let origin = RowOrigin::open(&args.file, &options, &directives)?;
+    let plan = build_plan(&directives, &origin)?;
+    let shape = scan_slice(&args.file, &origin, &plan, &directives)?;
+    if let Some(warning) = modal_column_count_warning(profile, shape.headers.len()) {
         warnings.push(warning);
     }
-    let csv_bytes = render_csv(&slice.headers, &slice.data_rows)?;
-    let output_hash = format!("blake3:{}", blake3::hash(&csv_bytes).to_hex());
-
-    if let Some(out) = args.out.as_deref() {
-        fs::write(out, &csv_bytes)
-            .map_err(|error| RefusalPayload::io(out.display().to_string(), error.to_string()))?;
-    }
+    let keep_csv = explicit || (!json_output && args.out.is_none());
+    let sink = CsvSink::open(args.out.as_deref(), keep_csv)?;
+    let data_rows = SliceDataRows::open(&args.file, &plan, &shape)?;
+    let (output_hash, csv_bytes) = write_slice(&shape.headers, data_rows, sink)?.finish()?;
 
     if let Some(manifest_path) = args.emit_manifest.as_deref() {
-        let manifest = build_manifest(
-            args,
-            profile,
-            &directives,
-            &plan,
-            &slice,
-            &rows,
-            &output_hash,
-        );
+        let manifest = build_manifest(args, profile, &directives, &plan, &shape, &output_hash);
         let manifest_bytes = serde_json::to_vec_pretty(&manifest).map_err(|error| {
             RefusalPayload::invalid_schema_single(
                 "manifest",
@@ -84,15 +290,18 @@ pub fn run(
         "mode": directives.mode.as_str(),
         "directives": directive_summary(&directives),
         "rows": {
-            "input_physical_rows": rows.len(),
+            "input_physical_rows": shape.physical_rows,
             "header_rows": plan.header_rows,
             "unit_rows": plan.unit_rows,
             "data_starts_at": plan.data_starts_at,
-            "output_data_rows": slice.data_rows.len()
+            "output_data_rows": shape.data_rows
         },
-        "columns": slice.headers,
+        "columns": shape.headers,
         "output_hash": output_hash
     });
+    if let Some(dialect) = origin.dialect() {
+        result["dialect"] = dialect.summary();
+    }
     if !warnings.is_empty() {
         result["warnings"] = json!(warnings);
         if !json_output {
@@ -100,14 +309,13 @@ pub fn run(
         }
     }
 
-    if explicit || (!json_output && args.out.is_none()) {
-        result["slice_csv"] =
-            Value::String(String::from_utf8(csv_bytes.clone()).map_err(|error| {
-                RefusalPayload::invalid_schema_single(
-                    "slice",
-                    format!("slice output was not valid UTF-8: {error}"),
-                )
-            })?);
+    if let Some(csv_bytes) = csv_bytes {
+        result["slice_csv"] = Value::String(String::from_utf8(csv_bytes).map_err(|error| {
+            RefusalPayload::invalid_schema_single(
+                "slice",
+                format!("slice output was not valid UTF-8: {error}"),
+            )
+        })?);
     }
 
     let mut input_paths = vec![args.file.clone()];
@@ -151,7 +359,7 @@ fn resolve_slice_profile(args: &SliceArgs) -> Result<Option<SliceProfile>, Refus
             let content = fs::read_to_string(path).map_err(|error| {
                 RefusalPayload::io(path.display().to_string(), error.to_string())
             })?;
-            let profile = parse_profile_yaml(&content)?;
+            let profile = apply_extends(parse_profile_yaml(&content)?, path)?;
             validate_profile(&profile, ValidationMode::Validate)?;
             Ok(Some(SliceProfile {
                 path: path.to_path_buf(),
@@ -169,19 +377,7 @@ fn effective_directives(
     let mut directives = profile
         .and_then(|profile| profile.pre_parse.as_ref())
         .map(|pre_parse| pre_parse.slice.clone())
-        .unwrap_or_else(|| SliceDirectives {
-            mode: infer_mode_from_args(args),
-            skip_rows: None,
-            header_at_row: None,
-            header_rows: Vec::new(),
-            header_merge: None,
-            data_starts_at: None,
-            delimiter: None,
-            encoding: None,
-            preamble_capture: Some(true),
-            unit_rows_capture: Some(true),
-            unit_rows: Vec::new(),
-        });
+        .unwrap_or_else(|| default_directives(infer_mode_from_args(args)));
     let mut overridden_flags = Vec::new();
 
     if let Some(mode) = args.mode {
@@ -228,7 +424,10 @@ fn effective_directives(
         overridden_flags.push("--encoding");
     }
 
-    if profile.is_some() && directives.header_rows.is_empty() && directives.header_at_row.is_none()
+    // Fixed-width profiles name their columns in `fixed_width`, so they need no header row.
+    if profile.is_some_and(|profile| profile.format != ProfileFormat::FixedWidth)
+        && directives.header_rows.is_empty()
+        && directives.header_at_row.is_none()
     {
         return Err(RefusalPayload::invalid_schema_single(
             "pre_parse",
@@ -248,6 +447,22 @@ fn effective_directives(
     Ok((directives, warnings))
 }
 
+fn default_directives(mode: SliceMode) -> SliceDirectives {
+    SliceDirectives {
+        mode,
+        skip_rows: None,
+        header_at_row: None,
+        header_rows: Vec::new(),
+        header_merge: None,
+        data_starts_at: None,
+        delimiter: None,
+        encoding: None,
+        preamble_capture: Some(true),
+        unit_rows_capture: Some(true),
+        unit_rows: Vec::new(),
+    }
+}
+
 fn infer_mode_from_args(args: &SliceArgs) -> SliceMode {
     if args.header_rows.is_some() {
         SliceMode::MultiRowHeader
@@ -304,8 +519,12 @@ fn validate_directives(directives: &SliceDirectives) -> Result<(), RefusalPayloa
         profile_family: None,
         profile_sha256: None,
         frozen: None,
+        extends: None,
+        extends_sha256: None,
         status: crate::schema::ProfileStatus::Draft,
-        format: crate::schema::ProfileFormat::Csv,
+        format: ProfileFormat::Csv,
+        sheet: None,
+        fixed_width: None,
         column_registry: None,
         fingerprint_ref: None,
         pre_parse: Some(PreParse {
@@ -316,15 +535,22 @@ fn validate_directives(directives: &SliceDirectives) -> Result<(), RefusalPayloa
         equivalence: None,
         key: Vec::new(),
         include_columns: vec!["slice_placeholder".to_owned()],
+        column_types: BTreeMap::new(),
+        normalize: BTreeMap::new(),
+        column_equivalence: BTreeMap::new(),
+        drift: None,
+        constraints: None,
+        exclude_columns: Vec::new(),
     };
     validate_profile(&profile, ValidationMode::Validate)
 }
 
-fn resolve_delimiter(directives: &SliceDirectives) -> Result<u8, RefusalPayload> {
+/// The declared delimiter byte, if any; an undeclared one is sniffed from the file.
+fn resolve_delimiter(directives: &SliceDirectives) -> Result<Option<u8>, RefusalPayload> {
     match directives.delimiter.as_deref() {
-        None => Ok(b','),
-        Some("\\t") | Some("tab") => Ok(b'\t'),
-        Some(value) if value.chars().count() == 1 => Ok(value.as_bytes()[0]),
+        None => Ok(None),
+        Some("\\t") | Some("tab") => Ok(Some(b'\t')),
+        Some(value) if value.chars().count() == 1 => Ok(Some(value.as_bytes()[0])),
         Some(_) => Err(RefusalPayload::invalid_schema_single(
             "pre_parse.slice.delimiter",
             "delimiter must be one character, \\t, or tab",
@@ -332,49 +558,219 @@ fn resolve_delimiter(directives: &SliceDirectives) -> Result<u8, RefusalPayload>
     }
 }
 
-fn read_physical_rows(path: &Path, delimiter: u8) -> Result<Vec<Vec<String>>, RefusalPayload> {
-    let file = File::open(path)
-        .map_err(|error| RefusalPayload::io(path.display().to_string(), error.to_string()))?;
-    let mut buffered = BufReader::new(file);
-    let mut rows = Vec::new();
-    let mut line = String::new();
-    loop {
-        line.clear();
-        let read = buffered
-            .read_line(&mut line)
-            .map_err(|error| RefusalPayload::io(path.display().to_string(), error.to_string()))?;
-        if read == 0 {
-            break;
+/// Where a dataset's physical rows come from: a delimited text file, a fixed-width text file
+/// cut by the profile's column spans, or one worksheet of an xlsx workbook, which is loaded
+/// once and shared by both passes over it.
+#[derive(Debug, Clone)]
+enum RowOrigin {
+    Delimited {
+        dialect: Dialect,
+        encoding: SourceEncoding,
+    },
+    FixedWidth {
+        columns: Rc<[FixedWidthColumn]>,
+        encoding: SourceEncoding,
+    },
+    Sheet(Rc<Worksheet>),
+}
+
+impl RowOrigin {
+    fn open(
+        path: &Path,
+        options: &ReadOptions<'_>,
+        directives: &SliceDirectives,
+    ) -> Result<Self, RefusalPayload> {
+        match options.format_for(path) {
+            ProfileFormat::Csv => {
+                let encoding = sniff_encoding(path, directives.encoding.as_deref())?;
+                let dialect = sniff_dialect(path, encoding, resolve_delimiter(directives)?, false)?;
+                Ok(Self::Delimited { dialect, encoding })
+            }
+            ProfileFormat::FixedWidth => {
+                let fixed_width = options.fixed_width.ok_or_else(|| {
+                    RefusalPayload::invalid_schema_single(
+                        "fixed_width",
+                        "format fixed_width needs column spans from a profile",
+                    )
+                })?;
+                if directives.delimiter.is_some() {
+                    return Err(RefusalPayload::invalid_schema_single(
+                        "pre_parse.slice.delimiter",
+                        "does not apply to format fixed_width",
+                    ));
+                }
+                if directives.mode == SliceMode::MultiRowHeader
+                    || !directives.header_rows.is_empty()
+                {
+                    return Err(RefusalPayload::invalid_schema_single(
+                        "pre_parse.slice.header_rows",
+                        "fixed_width column names come from fixed_width.columns",
+                    ));
+                }
+                Ok(Self::FixedWidth {
+                    columns: fixed_width.columns.clone().into(),
+                    encoding: sniff_encoding(path, directives.encoding.as_deref())?,
+                })
+            }
+            ProfileFormat::Xlsx => Ok(Self::Sheet(Rc::new(read_worksheet(path, options.sheet)?))),
+            format @ (ProfileFormat::Parquet | ProfileFormat::Jsonl) => {
+                Err(RefusalPayload::invalid_schema_single(
+                    "format",
+                    format!(
+                        "slice directives apply to csv and xlsx datasets; {} has no preamble rows",
+                        format.as_str()
+                    ),
+                ))
+            }
         }
-        while line.ends_with('\n') || line.ends_with('\r') {
-            line.pop();
+    }
+
+    fn dialect(&self) -> Option<Dialect> {
+        match self {
+            Self::Delimited { dialect, .. } => Some(*dialect),
+            Self::FixedWidth { .. } | Self::Sheet(_) => None,
         }
-        if line.trim().is_empty() {
-            rows.push(Vec::new());
-            continue;
+    }
+}
+
+/// Rows numbered the way the source shows them: by starting line for delimited files, by line
+/// for fixed-width files, by sheet row for workbooks.
+enum PhysicalRows {
+    Delimited(Box<DelimitedRows>),
+    FixedWidth(Box<FixedWidthRows>),
+    Sheet {
+        sheet: Rc<Worksheet>,
+        rows_read: usize,
+    },
+}
+
+impl PhysicalRows {
+    fn open(path: &Path, origin: &RowOrigin) -> Result<Self, RefusalPayload> {
+        match origin {
+            RowOrigin::Delimited { dialect, encoding } => Ok(Self::Delimited(Box::new(
+                DelimitedRows::open(path, *dialect, *encoding)?,
+            ))),
+            RowOrigin::FixedWidth { columns, encoding } => Ok(Self::FixedWidth(Box::new(
+                FixedWidthRows::open(path, Rc::clone(columns), *encoding)?,
+            ))),
+            RowOrigin::Sheet(sheet) => Ok(Self::Sheet {
+                sheet: Rc::clone(sheet),
+                rows_read: 0,
+            }),
         }
-        let mut reader = csv::ReaderBuilder::new()
-            .has_headers(false)
-            .flexible(true)
-            .delimiter(delimiter)
-            .from_reader(line.as_bytes());
-        let record = reader
-            .records()
-            .next()
-            .transpose()
-            .map_err(|error| {
-                RefusalPayload::csv_parse(path.display().to_string(), error.to_string())
-            })?
-            .ok_or_else(|| RefusalPayload::empty(path.display().to_string()))?;
-        rows.push(record.iter().map(ToOwned::to_owned).collect());
-    }
-    if rows.is_empty() {
-        return Err(RefusalPayload::empty_with_reason(
-            path.display().to_string(),
-            "no rows",
-        ));
     }
-    Ok(rows)
+
+    fn rows_read(&self) -> usize {
+        match self {
+            Self::Delimited(rows) => rows.lines_read,
+            Self::FixedWidth(rows) => rows.lines_read(),
+            Self::Sheet { rows_read, .. } => *rows_read,
+        }
+    }
+}
+
+impl Iterator for PhysicalRows {
+    type Item = Result<(usize, Vec<String>), RefusalPayload>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Delimited(rows) => rows.next(),
+            Self::FixedWidth(rows) => rows.next(),
+            Self::Sheet { sheet, rows_read } => {
+                let cells = sheet.rows.get(*rows_read)?.clone();
+                *rows_read += 1;
+                Some(Ok((*rows_read, cells)))
+            }
+        }
+    }
+}
+
+/// Records of a delimited file numbered by the physical line they start on. Quoted fields may
+/// span lines; blank lines surface as empty rows so directive row numbers match the raw file.
+struct DelimitedRows {
+    reader: BufReader<DecodedFile>,
+    path: PathBuf,
+    dialect: Dialect,
+    lines_read: usize,
+    buffer: String,
+}
+
+impl DelimitedRows {
+    fn open(path: &Path, dialect: Dialect, source: SourceEncoding) -> Result<Self, RefusalPayload> {
+        let file = open_decoded(path, source)?;
+        Ok(Self {
+            reader: BufReader::new(file),
+            path: path.to_path_buf(),
+            dialect,
+            lines_read: 0,
+            buffer: String::new(),
+        })
+    }
+
+    fn read_record_text(&mut self) -> Result<Option<usize>, RefusalPayload> {
+        self.buffer.clear();
+        let row_number = self.lines_read + 1;
+        let mut state = QuoteState::FieldStart;
+        loop {
+            let start = self.buffer.len();
+            let read = self.reader.read_line(&mut self.buffer).map_err(|error| {
+                RefusalPayload::io(self.path.display().to_string(), error.to_string())
+            })?;
+            if read == 0 {
+                return Ok((start > 0).then_some(row_number));
+            }
+            self.lines_read += 1;
+            state = scan_quotes(&self.buffer[start..], self.dialect, state);
+            if state != QuoteState::Quoted {
+                return Ok(Some(row_number));
+            }
+        }
+    }
+}
+
+impl Iterator for DelimitedRows {
+    type Item = Result<(usize, Vec<String>), RefusalPayload>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row_number = match self.read_record_text() {
+            Ok(row_number) => row_number?,
+            Err(error) => return Some(Err(error)),
+        };
+        let text = self.buffer.trim_end_matches(['\n', '\r']);
+        if text.trim().is_empty() {
+            return Some(Ok((row_number, Vec::new())));
+        }
+        Some(Ok((row_number, parse_fields(text, self.dialect))))
+    }
+}
+
+/// Advance the quoting state across one physical line; `Quoted` at the end means the record
+/// continues on the next line.
+fn scan_quotes(line: &str, dialect: Dialect, state: QuoteState) -> QuoteState {
+    line.bytes()
+        .fold(state, |state, byte| advance(state, byte, dialect))
+}
+
+/// Split one complete record into fields with the same transitions `scan_quotes` follows.
+fn parse_fields(text: &str, dialect: Dialect) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = Vec::new();
+    let mut state = QuoteState::FieldStart;
+    for byte in text.bytes() {
+        let next = advance(state, byte, dialect);
+        match (state, next) {
+            (_, QuoteState::FieldStart) => {
+                fields.push(String::from_utf8_lossy(&field).into_owned());
+                field.clear();
+            }
+            (QuoteState::FieldStart, QuoteState::Quoted)
+            | (QuoteState::Quoted, QuoteState::QuoteInQuoted) => {}
+            _ => field.push(byte),
+        }
+        state = next;
+    }
+    fields.push(String::from_utf8_lossy(&field).into_owned());
+    fields
 }
 
 fn modal_column_count_warning(profile: Option<&Profile>, output_columns: usize) -> Option<String> {
@@ -403,7 +799,13 @@ struct SlicePlan {
     data_starts_at: usize,
 }
 
-fn build_plan(directives: &SliceDirectives) -> Result<SlicePlan, RefusalPayload> {
+fn build_plan(
+    directives: &SliceDirectives,
+    origin: &RowOrigin,
+) -> Result<SlicePlan, RefusalPayload> {
+    if matches!(origin, RowOrigin::FixedWidth { .. }) {
+        return Ok(fixed_width_plan(directives));
+    }
     let header_rows = match directives.mode {
         SliceMode::PreambleSkip | SliceMode::PreambleWithUnits => {
             vec![directives.header_at_row.unwrap_or_else(|| {
@@ -429,66 +831,180 @@ fn build_plan(directives: &SliceDirectives) -> Result<SlicePlan, RefusalPayload>
     })
 }
 
+/// Fixed-width column names come from the profile, so a header line in the file is only
+/// skipped; without one, data starts right after `skip_rows`.
+fn fixed_width_plan(directives: &SliceDirectives) -> SlicePlan {
+    let header_rows = directives.header_at_row.into_iter().collect::<Vec<_>>();
+    let data_starts_at = directives.data_starts_at.unwrap_or_else(|| {
+        header_rows
+            .iter()
+            .chain(&directives.unit_rows)
+            .copied()
+            .max()
+            .or(directives.skip_rows)
+            .map_or(1, |row| row + 1)
+    });
+    SlicePlan {
+        header_rows,
+        unit_rows: directives.unit_rows.clone(),
+        data_starts_at,
+    }
+}
+
+/// Everything about a slice that is known after one streaming pass over the input.
 #[derive(Debug, Clone)]
-struct SliceOutput {
+struct SliceShape {
     headers: Vec<String>,
-    data_rows: Vec<Vec<String>>,
+    width: usize,
+    origin: RowOrigin,
+    data_rows: usize,
+    physical_rows: usize,
+    preamble_rows: Vec<Vec<String>>,
+    unit_row_values: Vec<Vec<String>>,
 }
 
-fn build_slice(
-    rows: &[Vec<String>],
+fn scan_slice(
+    path: &Path,
+    origin: &RowOrigin,
     plan: &SlicePlan,
     directives: &SliceDirectives,
-) -> Result<SliceOutput, RefusalPayload> {
+) -> Result<SliceShape, RefusalPayload> {
+    let first_header_row = plan
+        .header_rows
+        .first()
+        .copied()
+        .unwrap_or(plan.data_starts_at);
+    let capture_preamble = directives.preamble_capture.unwrap_or(true);
+    let mut structural_rows = BTreeMap::new();
+    let mut preamble_rows = Vec::new();
+    let mut data_rows = 0usize;
+    let mut data_width = 0usize;
+
+    let mut rows = PhysicalRows::open(path, origin)?;
+    for row in &mut rows {
+        let (row_number, cells) = row?;
+        if capture_preamble && row_number < first_header_row {
+            preamble_rows.push(cells.clone());
+        }
+        if is_data_row(plan.data_starts_at, row_number, &cells) {
+            data_rows += 1;
+            data_width = data_width.max(cells.len());
+        }
+        if plan.header_rows.contains(&row_number) || plan.unit_rows.contains(&row_number) {
+            structural_rows.insert(row_number, cells);
+        }
+    }
+    let physical_rows = rows.rows_read();
+    if physical_rows == 0 {
+        return Err(RefusalPayload::empty_with_reason(
+            path.display().to_string(),
+            "no rows",
+        ));
+    }
+
     let header_source = plan
         .header_rows
         .iter()
-        .map(|row| physical_row(rows, *row))
+        .map(|row| structural_row(&structural_rows, *row, physical_rows))
         .collect::<Result<Vec<_>, _>>()?;
-    let data_rows = rows
-        .iter()
-        .enumerate()
-        .filter_map(|(index, row)| {
-            let row_number = index + 1;
-            (row_number >= plan.data_starts_at && !row.iter().all(|cell| cell.trim().is_empty()))
-                .then_some(row.clone())
-        })
-        .collect::<Vec<_>>();
-    if data_rows.is_empty() {
+    if data_rows == 0 {
         return Err(RefusalPayload::empty_with_reason(
             "slice",
             "no data rows after slice directives applied",
         ));
     }
-    let width = header_source
+    let (headers, width) = match origin {
+        RowOrigin::FixedWidth { columns, .. } => (
+            columns.iter().map(|column| column.name.clone()).collect(),
+            columns.len(),
+        ),
+        _ => {
+            let width = header_source
+                .iter()
+                .map(Vec::len)
+                .max()
+                .unwrap_or(0)
+                .max(data_width);
+            if width == 0 {
+                return Err(RefusalPayload::empty_with_reason(
+                    "slice",
+                    "no header columns found",
+                ));
+            }
+            (merge_headers(&header_source, width, directives), width)
+        }
+    };
+    let unit_row_values = plan
+        .unit_rows
         .iter()
-        .chain(data_rows.iter())
-        .map(Vec::len)
-        .max()
-        .unwrap_or(0);
-    if width == 0 {
-        return Err(RefusalPayload::empty_with_reason(
-            "slice",
-            "no header columns found",
-        ));
-    }
-    let headers = merge_headers(&header_source, width, directives);
-    let data_rows = data_rows
-        .into_iter()
-        .map(|row| pad_row(row, width))
+        .filter_map(|row| structural_rows.get(row).cloned())
         .collect::<Vec<_>>();
-    Ok(SliceOutput { headers, data_rows })
+
+    Ok(SliceShape {
+        headers,
+        width,
+        origin: origin.clone(),
+        data_rows,
+        physical_rows,
+        preamble_rows,
+        unit_row_values,
+    })
 }
 
-fn physical_row(rows: &[Vec<String>], row_number: usize) -> Result<Vec<String>, RefusalPayload> {
-    rows.get(row_number.saturating_sub(1))
-        .cloned()
-        .ok_or_else(|| {
-            RefusalPayload::invalid_schema_single(
-                "pre_parse.slice",
-                format!("row {row_number} is outside the input"),
-            )
+fn is_data_row(data_starts_at: usize, row_number: usize, cells: &[String]) -> bool {
+    row_number >= data_starts_at && !cells.iter().all(|cell| cell.trim().is_empty())
+}
+
+fn structural_row(
+    rows: &BTreeMap<usize, Vec<String>>,
+    row_number: usize,
+    physical_rows: usize,
+) -> Result<Vec<String>, RefusalPayload> {
+    if let Some(row) = rows.get(&row_number) {
+        return Ok(row.clone());
+    }
+    let detail = if row_number == 0 || row_number > physical_rows {
+        format!("row {row_number} is outside the input")
+    } else {
+        format!("row {row_number} continues a quoted field from an earlier row")
+    };
+    Err(RefusalPayload::invalid_schema_single(
+        "pre_parse.slice",
+        detail,
+    ))
+}
+
+/// Second streaming pass: data rows padded to the slice width.
+struct SliceDataRows {
+    rows: PhysicalRows,
+    data_starts_at: usize,
+    width: usize,
+}
+
+impl SliceDataRows {
+    fn open(path: &Path, plan: &SlicePlan, shape: &SliceShape) -> Result<Self, RefusalPayload> {
+        Ok(Self {
+            rows: PhysicalRows::open(path, &shape.origin)?,
+            data_starts_at: plan.data_starts_at,
+            width: shape.width,
         })
+    }
+}
+
+impl Iterator for SliceDataRows {
+    type Item = Result<Vec<String>, RefusalPayload>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (row_number, cells) = match self.rows.next()? {
+                Ok(row) => row,
+                Err(error) => return Some(Err(error)),
+            };
+            if is_data_row(self.data_starts_at, row_number, &cells) {
+                return Some(Ok(pad_row(cells, self.width)));
+            }
+        }
+    }
 }
 
 fn merge_headers(
@@ -553,13 +1069,75 @@ fn pad_row(mut row: Vec<String>, width: usize) -> Vec<String> {
     row
 }
 
-fn render_csv(headers: &[String], rows: &[Vec<String>]) -> Result<Vec<u8>, RefusalPayload> {
-    let mut writer = csv::Writer::from_writer(Vec::new());
+/// Hashes output CSV bytes as they stream to `--out` and, when requested, to memory.
+pub(crate) struct CsvSink {
+    hasher: blake3::Hasher,
+    out: Option<(PathBuf, BufWriter<File>)>,
+    buffer: Option<Vec<u8>>,
+}
+
+impl CsvSink {
+    pub(crate) fn open(out: Option<&Path>, keep_csv: bool) -> Result<Self, RefusalPayload> {
+        let out = out
+            .map(|path| {
+                File::create(path)
+                    .map(|file| (path.to_path_buf(), BufWriter::new(file)))
+                    .map_err(|error| {
+                        RefusalPayload::io(path.display().to_string(), error.to_string())
+                    })
+            })
+            .transpose()?;
+        Ok(Self {
+            hasher: blake3::Hasher::new(),
+            out,
+            buffer: keep_csv.then(Vec::new),
+        })
+    }
+
+    pub(crate) fn finish(mut self) -> Result<(String, Option<Vec<u8>>), RefusalPayload> {
+        if let Some((path, writer)) = self.out.as_mut() {
+            writer.flush().map_err(|error| {
+                RefusalPayload::io(path.display().to_string(), error.to_string())
+            })?;
+        }
+        Ok((
+            format!("blake3:{}", self.hasher.finalize().to_hex()),
+            self.buffer,
+        ))
+    }
+}
+
+impl Write for CsvSink {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.hasher.update(bytes);
+        if let Some((_, writer)) = self.out.as_mut() {
+            writer.write_all(bytes)?;
+        }
+        if let Some(buffer) = self.buffer.as_mut() {
+            buffer.extend_from_slice(bytes);
+        }
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.out.as_mut() {
+            Some((_, writer)) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+fn write_slice(
+    headers: &[String],
+    rows: SliceDataRows,
+    sink: CsvSink,
+) -> Result<CsvSink, RefusalPayload> {
+    let mut writer = csv::Writer::from_writer(sink);
     writer.write_record(headers).map_err(|error| {
         RefusalPayload::csv_parse("slice", format!("failed to write header: {error}"))
     })?;
     for row in rows {
-        writer.write_record(row).map_err(|error| {
+        writer.write_record(row?).map_err(|error| {
             RefusalPayload::csv_parse("slice", format!("failed to write row: {error}"))
         })?;
     }
@@ -576,34 +1154,16 @@ fn build_manifest(
     profile: Option<&Profile>,
     directives: &SliceDirectives,
     plan: &SlicePlan,
-    slice: &SliceOutput,
-    rows: &[Vec<String>],
+    shape: &SliceShape,
     output_hash: &str,
 ) -> Value {
-    let preamble_rows = if directives.preamble_capture.unwrap_or(true) {
-        rows.iter()
-            .take(
-                plan.header_rows
-                    .first()
-                    .copied()
-                    .unwrap_or(1)
-                    .saturating_sub(1),
-            )
-            .cloned()
-            .collect::<Vec<_>>()
-    } else {
-        Vec::new()
-    };
     let unit_rows = if directives.unit_rows_capture.unwrap_or(true) {
-        plan.unit_rows
-            .iter()
-            .filter_map(|row| rows.get(row.saturating_sub(1)).cloned())
-            .collect::<Vec<_>>()
+        shape.unit_row_values.clone()
     } else {
         Vec::new()
     };
 
-    json!({
+    let mut manifest = json!({
         "schema": "profile.slice_manifest.v1",
         "input_path": args.file.display().to_string(),
         "profile_id": profile.and_then(|profile| profile.profile_id.clone()),
@@ -612,12 +1172,23 @@ fn build_manifest(
         "header_rows": &plan.header_rows,
         "unit_rows": &plan.unit_rows,
         "data_starts_at": plan.data_starts_at,
-        "columns": &slice.headers,
-        "output_data_rows": slice.data_rows.len(),
+        "source_encoding": match &shape.origin {
+            RowOrigin::Delimited { encoding, .. } | RowOrigin::FixedWidth { encoding, .. } => json!({
+                "name": encoding.name(),
+                "bom": encoding.bom
+            }),
+            RowOrigin::Sheet(_) => Value::Null,
+        },
+        "columns": &shape.headers,
+        "output_data_rows": shape.data_rows,
         "output_hash": output_hash,
-        "preamble_rows": preamble_rows,
+        "preamble_rows": &shape.preamble_rows,
         "unit_row_values": unit_rows
-    })
+    });
+    if let RowOrigin::Sheet(sheet) = &shape.origin {
+        manifest["sheet"] = json!(sheet.name);
+    }
+    manifest
 }
 
 fn directive_summary(directives: &SliceDirectives) -> Value {
@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::refusal::RefusalPayload;
+use crate::schema::Profile;
+use crate::stats::stats::ColumnAccumulator;
+use crate::stats::summary::NumericDistribution;
+
+pub const BASELINE_VERSION: &str = "profile.baseline.v0";
+/// Numeric distributions are kept as shares of values between ventile cut points.
+const NUMERIC_BINS: usize = 20;
+/// Columns with at most this many distinct values keep their value frequencies.
+const CATEGORY_LIMIT: usize = 50;
+
+/// A statistics snapshot written by `stats --save-baseline` and read by `profile drift`. It is
+/// only meaningful under the frozen profile whose `profile_sha256` it records.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Baseline {
+    pub version: String,
+    pub profile_id: Option<String>,
+    pub profile_sha256: String,
+    /// The profile path as given to `stats`, tried before `profile_id` when drift resolves it.
+    pub profile_path: String,
+    pub row_count: usize,
+    pub columns: Vec<BaselineColumn>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaselineColumn {
+    pub name: String,
+    pub null_rate: f64,
+    pub uniqueness: f64,
+    /// Present for integer and decimal columns.
+    #[serde(default)]
+    pub numeric: Option<NumericBaseline>,
+    /// Share of non-null cells holding each value, for low-cardinality columns counted exactly.
+    #[serde(default)]
+    pub categories: Option<BTreeMap<String, f64>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NumericBaseline {
+    pub count: usize,
+    pub mean: f64,
+    pub stddev: f64,
+    /// Ascending, distinct bin cut points.
+    pub edges: Vec<f64>,
+    /// Share of values in each bin; one more entry than `edges`.
+    pub proportions: Vec<f64>,
+}
+
+impl Baseline {
+    pub fn new(
+        profile_path: &Path,
+        profile: &Profile,
+        row_count: usize,
+        columns: Vec<BaselineColumn>,
+    ) -> Self {
+        Self {
+            version: BASELINE_VERSION.to_string(),
+            profile_id: profile.profile_id.clone(),
+            profile_sha256: profile.profile_sha256.clone().unwrap_or_default(),
+            profile_path: profile_path.display().to_string(),
+            row_count,
+            columns,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, RefusalPayload> {
+        let content = fs::read_to_string(path)
+            .map_err(|error| RefusalPayload::io(path.display().to_string(), error.to_string()))?;
+        let baseline = serde_json::from_str::<Self>(&content).map_err(|error| {
+            RefusalPayload::invalid_schema_single("baseline", error.to_string())
+        })?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(RefusalPayload::invalid_schema_single(
+                "baseline.version",
+                format!("expected {BASELINE_VERSION}, found {}", baseline.version),
+            ));
+        }
+        Ok(baseline)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), RefusalPayload> {
+        let mut content = serde_json::to_string_pretty(self)
+            .map_err(|error| RefusalPayload::io(path.display().to_string(), error.to_string()))?;
+        content.push('\n');
+        fs::write(path, content)
+            .map_err(|error| RefusalPayload::io(path.display().to_string(), error.to_string()))
+    }
+}
+
+impl BaselineColumn {
+    /// Value shares of a column with at most `CATEGORY_LIMIT` distinct values, or `None` when
+    /// it has more or its values were only sketched.
+    pub(crate) fn categories(accumulator: &ColumnAccumulator) -> Option<BTreeMap<String, f64>> {
+        let counts = accumulator.value_counts()?;
+        if counts.is_empty() || counts.len() > CATEGORY_LIMIT {
+            return None;
+        }
+        let total = counts.values().sum::<usize>() as f64;
+        Some(
+            counts
+                .iter()
+                .map(|(value, count)| {
+                    (
+                        String::from_utf8_lossy(value).into_owned(),
+                        *count as f64 / total,
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+impl NumericBaseline {
+    pub(crate) fn from_distribution(distribution: &NumericDistribution) -> Self {
+        let mut edges = (1..NUMERIC_BINS)
+            .map(|bin| distribution.quantile(bin as f64 / NUMERIC_BINS as f64))
+            .collect::<Vec<_>>();
+        edges.dedup();
+        Self {
+            count: distribution.count,
+            mean: distribution.mean,
+            stddev: distribution.stddev,
+            proportions: distribution.proportions(&edges),
+            edges,
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use crate::cli::args::DriftArgs;
+use crate::output::json::{CommandOutput, ProfileRef};
+use crate::refusal::RefusalPayload;
+use crate::resolve::resolver::resolve_profile;
+use crate::stats::baseline::{Baseline, BaselineColumn, NumericBaseline};
+use crate::stats::sketch::Estimation;
+use crate::stats::stats::{ColumnAccumulator, scan_columns};
+use crate::witness::append::append_for_command;
+
+/// Bin shares are floored here before taking logs, so an empty bin on either side still yields
+/// a finite PSI.
+const PSI_FLOOR: f64 = 1e-4;
+
+/// Drift measures of one column against its baseline.
+struct ColumnDrift {
+    measures: BTreeMap<&'static str, f64>,
+    detail: Value,
+}
+
+pub fn run(
+    args: &DriftArgs,
+    no_witness: bool,
+    explicit: bool,
+) -> Result<CommandOutput, RefusalPayload> {
+    let baseline = Baseline::load(&args.baseline)?;
+    let reference = args.profile.clone().unwrap_or_else(|| {
+        if Path::new(&baseline.profile_path).exists() {
+            baseline.profile_path.clone()
+        } else {
+            baseline
+                .profile_id
+                .clone()
+                .unwrap_or_else(|| baseline.profile_path.clone())
+        }
+    });
+    let resolved = resolve_profile(&reference)?;
+    let profile = resolved.profile;
+    if profile.profile_sha256.as_deref() != Some(baseline.profile_sha256.as_str()) {
+        return Err(RefusalPayload::invalid_schema_single(
+            "profile_sha256",
+            format!(
+                "baseline was taken under {}, but {} is {}",
+                baseline.profile_sha256,
+                resolved.path.display(),
+                profile.profile_sha256.as_deref().unwrap_or("not frozen")
+            ),
+        ));
+    }
+
+    let scan = scan_columns(
+        &args.dataset,
+        Some((&resolved.path, &profile)),
+        Estimation::default(),
+    )?;
+    let row_count = scan.scanned.analyzed;
+    let mut current = scan.columns.into_iter().collect::<BTreeMap<_, _>>();
+    let thresholds = profile.drift.unwrap_or_default();
+
+    let mut columns = Vec::with_capacity(baseline.columns.len());
+    let mut issues = Vec::new();
+    for expected in &baseline.columns {
+        let Some(accumulator) = current.remove(&expected.name) else {
+            return Err(RefusalPayload::column_not_found(
+                vec![expected.name.clone()],
+                current.keys().cloned().collect(),
+            ));
+        };
+        let drift = measure_column(expected, accumulator, row_count, explicit);
+        for (measure, limit) in thresholds.measures() {
+            if let (Some(limit), Some(value)) = (limit, drift.measures.get(measure))
+                && *value > limit
+            {
+                issues.push(json!({
+                    "kind": "drift_exceeded",
+                    "column": expected.name,
+                    "measure": measure,
+                    "severity": "error",
+                    "value": value,
+                    "threshold": limit
+                }));
+            }
+        }
+        columns.push(drift.detail);
+    }
+
+    let result = json!({
+        "baseline": {
+            "path": args.baseline.display().to_string(),
+            "profile_sha256": baseline.profile_sha256,
+            "row_count": baseline.row_count
+        },
+        "row_count": row_count,
+        "thresholds": thresholds,
+        "columns": columns,
+        "issues": issues
+    });
+
+    let witness_id = append_for_command(
+        "drift",
+        &result,
+        vec![
+            args.dataset.clone(),
+            args.baseline.clone(),
+            resolved.path.clone(),
+        ],
+        json!({
+            "subcommand": "drift",
+            "baseline": args.baseline.display().to_string(),
+            "profile": reference
+        }),
+        no_witness,
+    );
+
+    Ok(CommandOutput::success(result)
+        .with_profile_ref(ProfileRef::from_profile(&profile))
+        .with_witness_id(witness_id))
+}
+
+/// Whether a drift result should exit ISSUES_FOUND.
+pub fn has_drift_issues(result: &Value) -> bool {
+    result
+        .get("issues")
+        .and_then(Value::as_array)
+        .is_some_and(|issues| !issues.is_empty())
+}
+
+fn measure_column(
+    expected: &BaselineColumn,
+    accumulator: ColumnAccumulator,
+    row_count: usize,
+    explicit: bool,
+) -> ColumnDrift {
+    let mut measures = BTreeMap::new();
+    let null_rate = accumulator.null_rate(row_count);
+    let uniqueness = accumulator.uniqueness(row_count);
+    measures.insert("null_rate_delta", (null_rate - expected.null_rate).abs());
+    measures.insert("uniqueness_delta", (uniqueness - expected.uniqueness).abs());
+
+    let mut detail = json!({
+        "name": expected.name,
+        "null_rate": {
+            "baseline": expected.null_rate,
+            "current": null_rate,
+            "delta": measures["null_rate_delta"]
+        },
+        "uniqueness": {
+            "baseline": expected.uniqueness,
+            "current": uniqueness,
+            "delta": measures["uniqueness_delta"]
+        },
+        "psi": null,
+        "ks": null,
+        "new_values": null
+    });
+
+    if let Some(categories) = &expected.categories
+        && let Some(counts) = accumulator.value_counts()
+    {
+        let non_null = counts.values().sum::<usize>();
+        let mut shares = categories
+            .keys()
+            .map(|value| (value.as_str(), 0.0))
+            .collect::<BTreeMap<_, _>>();
+        let mut new_values = BTreeMap::new();
+        for (value, count) in counts {
+            let value = String::from_utf8_lossy(value);
+            match shares.get_mut(value.as_ref()) {
+                Some(share) => *share = *count as f64 / non_null as f64,
+                None => {
+                    new_values.insert(value.into_owned(), *count);
+                }
+            }
+        }
+        let new_rows = new_values.values().sum::<usize>();
+        let new_share = if non_null == 0 {
+            0.0
+        } else {
+            new_rows as f64 / non_null as f64
+        };
+        let expected_shares = categories.values().copied().chain([0.0]);
+        let current_shares = shares.values().copied().chain([new_share]);
+        measures.insert("psi", psi(expected_shares, current_shares));
+        measures.insert("new_value_rate", new_share);
+
+        let mut new_detail = json!({
+            "count": new_values.len(),
+            "rate": measures["new_value_rate"],
+            "missing": shares.values().filter(|share| **share == 0.0).count()
+        });
+        if explicit {
+            new_detail["values"] = json!(new_values.keys().collect::<Vec<_>>());
+        }
+        detail["new_values"] = new_detail;
+    }
+
+    // A numeric distribution, when the baseline has one, takes precedence for PSI.
+    if let Some(numeric) = &expected.numeric {
+        let current = accumulator
+            .into_numeric()
+            .map(|distribution| distribution.proportions(&numeric.edges))
+            .unwrap_or_else(|| vec![0.0; numeric.proportions.len()]);
+        measures.insert(
+            "psi",
+            psi(numeric.proportions.iter().copied(), current.iter().copied()),
+        );
+        measures.insert("ks", ks(numeric, &current));
+    }
+
+    for measure in ["psi", "ks"] {
+        if let Some(value) = measures.get(measure) {
+            detail[measure] = json!(value);
+        }
+    }
+    ColumnDrift { measures, detail }
+}
+
+/// Population stability index: sum of `(current - expected) * ln(current / expected)` over
+/// matching bins.
+fn psi(expected: impl Iterator<Item = f64>, current: impl Iterator<Item = f64>) -> f64 {
+    expected
+        .zip(current)
+        .map(|(expected, current)| {
+            let (expected, current) = (expected.max(PSI_FLOOR), current.max(PSI_FLOOR));
+            (current - expected) * (current / expected).ln()
+        })
+        .sum()
+}
+
+/// Largest gap between the baseline and current cumulative shares at the baseline's bin edges.
+fn ks(baseline: &NumericBaseline, current: &[f64]) -> f64 {
+    let (mut expected_cumulative, mut current_cumulative) = (0.0, 0.0);
+    baseline
+        .proportions
+        .iter()
+        .zip(current)
+        .map(|(expected, current)| {
+            expected_cumulative += expected;
+            current_cumulative += current;
+            f64::abs(expected_cumulative - current_cumulative)
+        })
+        .fold(0.0, f64::max)
+}
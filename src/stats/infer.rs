@@ -0,0 +1,182 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::schema::ColumnType;
+
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%Y/%m/%d", "%m/%d/%Y"];
+const DATETIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+];
+
+/// Infer the most specific type for a single non-null cell value.
+pub fn infer_value(value: &str) -> ColumnType {
+    let value = value.trim();
+
+    if is_integer(value) {
+        ColumnType::Integer
+    } else if is_decimal(value) {
+        ColumnType::Decimal
+    } else if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+        ColumnType::Boolean
+    } else if is_date(value) {
+        ColumnType::Date
+    } else if is_datetime(value) {
+        ColumnType::Datetime
+    } else {
+        ColumnType::String
+    }
+}
+
+/// Running per-column type observations; blank cells are treated as nulls and skipped.
+#[derive(Debug, Default, Clone)]
+pub struct TypeAccumulator {
+    counts: [usize; 6],
+}
+
+impl TypeAccumulator {
+    pub fn observe(&mut self, value: &str) -> Option<ColumnType> {
+        if value.trim().is_empty() {
+            return None;
+        }
+
+        let inferred = infer_value(value);
+        self.counts[type_slot(inferred)] += 1;
+        Some(inferred)
+    }
+
+    pub fn non_null(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    pub fn count(&self, column_type: ColumnType) -> usize {
+        self.counts[type_slot(column_type)]
+    }
+
+    /// The narrowest type that accepts every observed value, or `None` when all values were null.
+    pub fn observed_type(&self) -> Option<ColumnType> {
+        if self.non_null() == 0 {
+            return None;
+        }
+
+        [
+            ColumnType::Integer,
+            ColumnType::Decimal,
+            ColumnType::Boolean,
+            ColumnType::Date,
+            ColumnType::Datetime,
+        ]
+        .into_iter()
+        .find(|candidate| self.accepted_by(*candidate) == self.non_null())
+        .or(Some(ColumnType::String))
+    }
+
+    /// The observed type with the share of non-null values it accepts. When only a string
+    /// accepts everything, the non-string type accepting the most values is preferred if it
+    /// covers at least `INFERENCE_FLOOR` of them, so a few sentinels such as `N/A` do not turn
+    /// a numeric column into text.
+    pub fn inferred_type(&self) -> Option<(ColumnType, f64)> {
+        let observed = self.observed_type()?;
+        if observed != ColumnType::String {
+            return Some((observed, 1.0));
+        }
+
+        let non_null = self.non_null() as f64;
+        let mut best: Option<(ColumnType, usize)> = None;
+        for candidate in &ALL_TYPES[..5] {
+            let accepted = self.accepted_by(*candidate);
+            if best.is_none_or(|(_, best_accepted)| accepted > best_accepted) {
+                best = Some((*candidate, accepted));
+            }
+        }
+        Some(match best {
+            Some((candidate, accepted)) if accepted as f64 / non_null >= INFERENCE_FLOOR => {
+                (candidate, accepted as f64 / non_null)
+            }
+            _ => (ColumnType::String, 1.0),
+        })
+    }
+
+    /// Number of observed values that a column declared as `declared` accepts.
+    pub fn accepted_by(&self, declared: ColumnType) -> usize {
+        ALL_TYPES
+            .into_iter()
+            .filter(|observed| declared.accepts(*observed))
+            .map(|observed| self.count(observed))
+            .sum()
+    }
+}
+
+/// Share of non-null values a non-string type must accept to be inferred over `string`.
+const INFERENCE_FLOOR: f64 = 0.9;
+
+const ALL_TYPES: [ColumnType; 6] = [
+    ColumnType::Integer,
+    ColumnType::Decimal,
+    ColumnType::Boolean,
+    ColumnType::Date,
+    ColumnType::Datetime,
+    ColumnType::String,
+];
+
+const fn type_slot(column_type: ColumnType) -> usize {
+    match column_type {
+        ColumnType::Integer => 0,
+        ColumnType::Decimal => 1,
+        ColumnType::Boolean => 2,
+        ColumnType::Date => 3,
+        ColumnType::Datetime => 4,
+        ColumnType::String => 5,
+    }
+}
+
+fn is_integer(value: &str) -> bool {
+    let digits = value
+        .strip_prefix('-')
+        .or_else(|| value.strip_prefix('+'))
+        .unwrap_or(value);
+    !digits.is_empty() && digits.chars().all(|ch| ch.is_ascii_digit())
+}
+
+fn is_decimal(value: &str) -> bool {
+    value.chars().any(|ch| ch.is_ascii_digit())
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_digit() || matches!(ch, '+' | '-' | '.' | 'e' | 'E'))
+        && value.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+/// The instant a date or datetime cell names, with offsets normalized to UTC, and whether it
+/// carried a time of day.
+pub fn parse_temporal(value: &str) -> Option<(NaiveDateTime, bool)> {
+    let value = value.trim();
+    parse_date(value)
+        .map(|date| (date.and_time(NaiveTime::MIN), false))
+        .or_else(|| parse_datetime(value).map(|datetime| (datetime, true)))
+}
+
+fn is_date(value: &str) -> bool {
+    parse_date(value).is_some()
+}
+
+fn is_datetime(value: &str) -> bool {
+    parse_datetime(value).is_some()
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+}
+
+fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|datetime| datetime.naive_utc())
+        .ok()
+        .or_else(|| {
+            DATETIME_FORMATS
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        })
+}
@@ -1,6 +1,12 @@
+pub mod baseline;
+pub mod drift;
+pub mod infer;
+mod parallel;
+pub mod sketch;
 #[allow(clippy::module_inception)]
 pub mod stats;
 pub mod suggest_key;
+mod summary;
 
 pub use stats::run as run_stats;
 pub use suggest_key::run as run_suggest_key;
@@ -0,0 +1,79 @@
+use std::num::NonZeroUsize;
+use std::thread;
+
+use csv::StringRecord;
+
+use crate::refusal::RefusalPayload;
+
+/// Records buffered before the accumulators are fanned out across threads.
+const BATCH_ROWS: usize = 8192;
+
+/// Feed every record to every accumulator and return the number of records read.
+///
+/// Accumulators are split into contiguous groups, one thread per group, and each thread walks
+/// the current batch in row order; the next batch is read while the current one is processed.
+/// Every accumulator therefore sees exactly the rows a serial scan would, in the same order, so
+/// results do not depend on the thread count.
+pub(crate) fn accumulate<A, R, F>(
+    rows: R,
+    accumulators: &mut [A],
+    observe: F,
+) -> Result<usize, RefusalPayload>
+where
+    A: Send,
+    R: IntoIterator<Item = Result<StringRecord, RefusalPayload>>,
+    F: Fn(usize, &mut A, &StringRecord) + Sync,
+{
+    let mut rows = rows.into_iter();
+    let workers = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(accumulators.len())
+        .max(1);
+    let group_size = accumulators.len().div_ceil(workers).max(1);
+    let observe = &observe;
+
+    let mut total_rows = 0usize;
+    let mut batch = next_batch(&mut rows)?;
+    while !batch.is_empty() {
+        total_rows += batch.len();
+        let next = if workers == 1 {
+            observe_group(0, accumulators, &batch, observe);
+            next_batch(&mut rows)
+        } else {
+            let batch = &batch;
+            thread::scope(|scope| {
+                for (group, accumulators) in accumulators.chunks_mut(group_size).enumerate() {
+                    scope.spawn(move || {
+                        observe_group(group * group_size, accumulators, batch, observe);
+                    });
+                }
+                next_batch(&mut rows)
+            })
+        };
+        batch = next?;
+    }
+    Ok(total_rows)
+}
+
+fn observe_group<A>(
+    offset: usize,
+    accumulators: &mut [A],
+    batch: &[StringRecord],
+    observe: &(impl Fn(usize, &mut A, &StringRecord) + Sync),
+) {
+    for record in batch {
+        for (index, accumulator) in accumulators.iter_mut().enumerate() {
+            observe(offset + index, accumulator, record);
+        }
+    }
+}
+
+fn next_batch(
+    rows: &mut impl Iterator<Item = Result<StringRecord, RefusalPayload>>,
+) -> Result<Vec<StringRecord>, RefusalPayload> {
+    let mut batch = Vec::with_capacity(BATCH_ROWS);
+    for record in rows.by_ref().take(BATCH_ROWS) {
+        batch.push(record?);
+    }
+    Ok(batch)
+}
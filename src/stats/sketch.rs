@@ -0,0 +1,415 @@
+use std::collections::{BTreeSet, HashMap};
+
+use csv::StringRecord;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::refusal::RefusalPayload;
+
+/// HyperLogLog registers are indexed by this many hash bits: 2^14 one-byte registers (16 KiB)
+/// per column, for a relative standard error of about 0.8%.
+const HLL_PRECISION: u32 = 14;
+/// Reservoir sampling is seeded so the same input always yields the same sample.
+const RESERVOIR_SEED: u64 = 0x5eed;
+/// Two-sided 95% normal quantile used for reported bounds.
+const Z_95: f64 = 1.96;
+/// Numeric values kept per column for quantiles under `--approx`.
+const QUANTILE_RESERVOIR: usize = 65_536;
+/// Distinct values tracked per column for `top_values` under `--approx`.
+const FREQUENT_CAPACITY: usize = 256;
+
+/// How `stats` and `suggest-key` trade exactness for bounded memory: `approx` counts distinct
+/// values with a HyperLogLog sketch, and `sample` profiles a seeded reservoir of that many rows.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Estimation {
+    pub approx: bool,
+    pub sample: Option<usize>,
+}
+
+impl Estimation {
+    pub fn is_exact(self) -> bool {
+        !self.approx && self.sample.is_none()
+    }
+
+    pub fn distinct_counter(self) -> DistinctCounter {
+        if self.approx {
+            DistinctCounter::Approx(Box::default())
+        } else {
+            DistinctCounter::Exact(HashMap::new())
+        }
+    }
+
+    /// Where a column keeps its numeric values for quantiles: all of them, or a seeded
+    /// reservoir under `--approx`.
+    pub fn value_reservoir<T>(self) -> Reservoir<T> {
+        Reservoir::new(if self.approx {
+            QUANTILE_RESERVOIR
+        } else {
+            usize::MAX
+        })
+    }
+
+    /// A heavy-hitter sketch when distinct values are not all held, `None` when exact counts
+    /// are available from the distinct counter.
+    pub fn frequent_values(self) -> Option<SpaceSaving> {
+        self.approx.then(|| SpaceSaving::new(FREQUENT_CAPACITY))
+    }
+
+    /// How `stats` derived quantiles and frequent values, for its `approximation` block.
+    pub fn value_methods(self) -> Value {
+        if self.approx {
+            json!({
+                "quantiles": { "method": "reservoir", "capacity": QUANTILE_RESERVOIR, "seed": RESERVOIR_SEED },
+                "top_values": { "method": "space_saving", "capacity": FREQUENT_CAPACITY }
+            })
+        } else {
+            json!({
+                "quantiles": { "method": "exact" },
+                "top_values": { "method": "exact" }
+            })
+        }
+    }
+
+    /// The `approximation` block of a result: which estimators ran and over how many rows.
+    pub fn summary(self, rows: &ScannedRows) -> Value {
+        json!({
+            "distinct": if self.approx {
+                json!({
+                    "method": "hyperloglog",
+                    "precision": HLL_PRECISION,
+                    "relative_standard_error": HyperLogLog::relative_standard_error()
+                })
+            } else {
+                json!({ "method": "exact" })
+            },
+            "sample": self.sample.map(|capacity| json!({
+                "method": "reservoir",
+                "capacity": capacity,
+                "seed": RESERVOIR_SEED,
+                "rows_sampled": rows.analyzed,
+                "rows_seen": rows.seen
+            }))
+        })
+    }
+
+    /// Bounds on a column's null rate and uniqueness, widened by whichever estimators ran. The
+    /// null rate gets a 95% interval. Sampled uniqueness gets the hard limits on the full data,
+    /// since every row the sample skipped may repeat a sampled value or be new. HyperLogLog
+    /// then widens either end by its 95% margin.
+    pub fn bounds(self, rows: &ScannedRows, null_rate: f64, uniqueness: f64) -> Bounds {
+        let null_rate = if self.sample.is_some() {
+            proportion_bounds(null_rate, rows.analyzed, rows.seen)
+        } else {
+            [null_rate, null_rate]
+        };
+        let [lower, upper] = if self.sample.is_some() && !rows.is_complete() {
+            let distinct = uniqueness * rows.analyzed as f64;
+            let skipped = (rows.seen - rows.analyzed) as f64;
+            [
+                distinct / rows.seen as f64,
+                (distinct + skipped) / rows.seen as f64,
+            ]
+        } else {
+            [uniqueness, uniqueness]
+        };
+        let uniqueness = if self.approx {
+            let margin = Z_95 * HyperLogLog::relative_standard_error();
+            [
+                (lower * (1.0 - margin)).max(0.0),
+                (upper * (1.0 + margin)).min(1.0),
+            ]
+        } else {
+            [lower, upper.min(1.0)]
+        };
+        Bounds {
+            null_rate,
+            uniqueness,
+        }
+    }
+}
+
+/// Lower and upper estimates; see [`Estimation::bounds`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bounds {
+    pub null_rate: [f64; 2],
+    pub uniqueness: [f64; 2],
+}
+
+/// Rows a scan analyzed out of all the rows it read; equal unless sampling.
+#[derive(Debug, Clone, Copy)]
+pub struct ScannedRows {
+    pub analyzed: usize,
+    pub seen: usize,
+}
+
+impl ScannedRows {
+    /// Whether every row read was analyzed. Only then can uniqueness establish a key: rows a
+    /// sample skipped may repeat the values it saw.
+    pub const fn is_complete(&self) -> bool {
+        self.analyzed >= self.seen
+    }
+}
+
+/// Normal-approximation interval for a sampled proportion with the finite-population
+/// correction, so a sample that covered every row has zero width.
+fn proportion_bounds(rate: f64, sampled: usize, population: usize) -> [f64; 2] {
+    if sampled == 0 || sampled >= population {
+        return [rate, rate];
+    }
+    let correction = ((population - sampled) as f64 / (population - 1) as f64).sqrt();
+    let margin = Z_95 * (rate * (1.0 - rate) / sampled as f64).sqrt() * correction;
+    [(rate - margin).max(0.0), (rate + margin).min(1.0)]
+}
+
+/// Distinct non-null values seen in a column or column combination, counted exactly (with
+/// occurrences) or estimated.
+pub enum DistinctCounter {
+    Exact(HashMap<Box<[u8]>, usize>),
+    Approx(Box<HyperLogLog>),
+}
+
+impl DistinctCounter {
+    pub fn insert(&mut self, value: &[u8]) {
+        match self {
+            Self::Exact(values) => match values.get_mut(value) {
+                Some(count) => *count += 1,
+                None => {
+                    values.insert(value.into(), 1);
+                }
+            },
+            Self::Approx(sketch) => sketch.insert(value),
+        }
+    }
+
+    /// The `limit` most frequent values with their counts, most frequent first and ties in
+    /// byte order; `None` for a sketch, which keeps no values.
+    pub fn most_frequent(&self, limit: usize) -> Option<Vec<(&[u8], usize)>> {
+        match self {
+            Self::Exact(values) => Some(top_by_count(
+                values.iter().map(|(value, count)| (&**value, *count)),
+                limit,
+            )),
+            Self::Approx(_) => None,
+        }
+    }
+
+    /// The distinct count, never more than the `observed` values inserted.
+    pub fn count(&self, observed: usize) -> f64 {
+        match self {
+            Self::Exact(values) => values.len() as f64,
+            Self::Approx(sketch) => sketch.estimate().min(observed as f64),
+        }
+    }
+}
+
+/// A HyperLogLog cardinality sketch over BLAKE3 hashes, so estimates are identical across
+/// platforms and builds.
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        Self {
+            registers: vec![0; 1 << HLL_PRECISION],
+        }
+    }
+
+    pub fn relative_standard_error() -> f64 {
+        1.04 / f64::from(1u32 << HLL_PRECISION).sqrt()
+    }
+
+    pub fn insert(&mut self, value: &[u8]) {
+        let digest = blake3::hash(value);
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&digest.as_bytes()[..8]);
+        let hash = u64::from_le_bytes(prefix);
+        let index = (hash >> (64 - HLL_PRECISION)) as usize;
+        // The sentinel bit caps the run length when every remaining bit is zero.
+        let remaining = (hash << HLL_PRECISION) | (1 << (HLL_PRECISION - 1));
+        let rank = remaining.leading_zeros() as u8 + 1;
+        self.registers[index] = self.registers[index].max(rank);
+    }
+
+    /// The bias-corrected raw estimate, switching to linear counting while registers are
+    /// still mostly empty.
+    pub fn estimate(&self) -> f64 {
+        let registers = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / registers);
+        let harmonic = self
+            .registers
+            .iter()
+            .map(|&rank| 2f64.powi(-i32::from(rank)))
+            .sum::<f64>();
+        let raw = alpha * registers * registers / harmonic;
+        let empty = self.registers.iter().filter(|&&rank| rank == 0).count();
+        if raw <= 2.5 * registers && empty > 0 {
+            registers * (registers / empty as f64).ln()
+        } else {
+            raw
+        }
+    }
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Space-Saving heavy hitters: at most `capacity` tracked values, where a value that evicts the
+/// least frequent one inherits its count. Each reported count overstates the true count by at
+/// most its `error`, and eviction ties go to the earliest-filled slot, so the summary depends
+/// only on row order.
+pub struct SpaceSaving {
+    capacity: usize,
+    slots: Vec<FrequentSlot>,
+    index: HashMap<Box<[u8]>, usize>,
+    by_count: BTreeSet<(usize, usize)>,
+}
+
+struct FrequentSlot {
+    value: Box<[u8]>,
+    count: usize,
+    error: usize,
+}
+
+impl SpaceSaving {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            slots: Vec::new(),
+            index: HashMap::new(),
+            by_count: BTreeSet::new(),
+        }
+    }
+
+    pub fn insert(&mut self, value: &[u8]) {
+        if let Some(&slot) = self.index.get(value) {
+            let entry = &mut self.slots[slot];
+            self.by_count.remove(&(entry.count, slot));
+            entry.count += 1;
+            self.by_count.insert((entry.count, slot));
+        } else if self.slots.len() < self.capacity {
+            let slot = self.slots.len();
+            self.slots.push(FrequentSlot {
+                value: value.into(),
+                count: 1,
+                error: 0,
+            });
+            self.index.insert(value.into(), slot);
+            self.by_count.insert((1, slot));
+        } else if let Some((floor, slot)) = self.by_count.pop_first() {
+            let entry = &mut self.slots[slot];
+            self.index.remove(&entry.value);
+            entry.value = value.into();
+            entry.count = floor + 1;
+            entry.error = floor;
+            self.index.insert(value.into(), slot);
+            self.by_count.insert((entry.count, slot));
+        }
+    }
+
+    /// The `limit` highest counts as `(value, count, error)`, ordered like
+    /// [`DistinctCounter::most_frequent`].
+    pub fn most_frequent(&self, limit: usize) -> Vec<(&[u8], usize, usize)> {
+        let errors = self
+            .slots
+            .iter()
+            .map(|slot| (&*slot.value, slot.error))
+            .collect::<HashMap<_, _>>();
+        top_by_count(
+            self.slots.iter().map(|slot| (&*slot.value, slot.count)),
+            limit,
+        )
+        .into_iter()
+        .map(|(value, count)| (value, count, errors[value]))
+        .collect()
+    }
+}
+
+fn top_by_count<'a>(
+    counts: impl Iterator<Item = (&'a [u8], usize)>,
+    limit: usize,
+) -> Vec<(&'a [u8], usize)> {
+    let order = |left: &(&[u8], usize), right: &(&[u8], usize)| {
+        right.1.cmp(&left.1).then_with(|| left.0.cmp(right.0))
+    };
+    let mut counts = counts.collect::<Vec<_>>();
+    if counts.len() > limit && limit > 0 {
+        counts.select_nth_unstable_by(limit - 1, order);
+    }
+    counts.truncate(limit);
+    counts.sort_unstable_by(order);
+    counts
+}
+
+/// A seeded uniform sample (Algorithm R) of at most `capacity` items.
+pub struct Reservoir<T> {
+    capacity: usize,
+    items: Vec<T>,
+    seen: usize,
+    random: SplitMix64,
+}
+
+impl<T> Reservoir<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            items: Vec::new(),
+            seen: 0,
+            random: SplitMix64(RESERVOIR_SEED),
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.seen += 1;
+        if self.items.len() < self.capacity {
+            self.items.push(item);
+        } else {
+            let slot = self.random.below(self.seen);
+            if slot < self.capacity {
+                self.items[slot] = item;
+            }
+        }
+    }
+
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+}
+
+/// A uniform sample of at most `capacity` records, plus the number of records read to draw it.
+pub fn sample_records(
+    rows: impl Iterator<Item = Result<StringRecord, RefusalPayload>>,
+    capacity: usize,
+) -> Result<(Vec<StringRecord>, usize), RefusalPayload> {
+    let mut reservoir = Reservoir::new(capacity);
+    for record in rows {
+        reservoir.push(record?);
+    }
+    let seen = reservoir.seen();
+    Ok((reservoir.into_items(), seen))
+}
+
+/// A small deterministic generator; sampling needs reproducibility, not cryptographic strength.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..bound`.
+    fn below(&mut self, bound: usize) -> usize {
+        ((u128::from(self.next()) * bound as u128) >> 64) as usize
+    }
+}
@@ -1,34 +1,68 @@
-use std::collections::HashSet;
-use std::fs::File;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::Path;
 
 use csv::StringRecord;
-use serde_json::json;
+use serde_json::{Value, json};
 
 use crate::cli::args::StatsArgs;
+use crate::dialect::Dialect;
 use crate::output::json::{CommandOutput, ProfileRef};
 use crate::refusal::RefusalPayload;
+use crate::resolve::resolver::apply_extends;
+use crate::schema::ColumnType;
 use crate::schema::{
-    ValidationMode, build_header_index, load_column_registry_aliases, parse_profile_yaml,
+    Profile, ValidationMode, build_header_index, canonicalize_header_sequence,
+    load_column_registry_aliases, parse_profile_yaml, resolve_profile_columns,
     resolve_registry_path, validate_profile,
 };
+use crate::slice::{DatasetRows, ReadOptions};
+use crate::stats::baseline::{Baseline, BaselineColumn, NumericBaseline};
+use crate::stats::infer::{TypeAccumulator, parse_temporal};
+use crate::stats::parallel::accumulate;
+use crate::stats::sketch::{DistinctCounter, Estimation, ScannedRows, SpaceSaving, sample_records};
+use crate::stats::summary::{DateRange, LengthSummary, NumericDistribution, NumericSummary};
 use crate::witness::append::append_for_command;
 
 const KEY_VIABLE_UNIQUENESS_THRESHOLD: f64 = 0.95;
+/// Most frequent values reported per column.
+const TOP_VALUES: usize = 5;
 
-#[derive(Debug, Default)]
-struct ColumnAccumulator {
+pub(crate) struct ColumnAccumulator {
     null_count: usize,
-    values: HashSet<String>,
+    observed: usize,
+    values: DistinctCounter,
+    frequent: Option<SpaceSaving>,
     example: Option<String>,
+    types: TypeAccumulator,
+    numeric: NumericSummary,
+    lengths: LengthSummary,
+    dates: DateRange,
 }
 
-struct SelectedColumns {
-    columns: Vec<(String, usize)>,
-    profile_ref: Option<ProfileRef>,
+/// The selected columns of a dataset after one pass, in selection order.
+pub(crate) struct ColumnScan {
+    pub columns: Vec<(String, ColumnAccumulator)>,
+    pub scanned: ScannedRows,
+    pub dialect: Option<Dialect>,
 }
 
 impl ColumnAccumulator {
-    fn observe(&mut self, value: &str) {
+    pub fn new(estimation: Estimation) -> Self {
+        Self {
+            null_count: 0,
+            observed: 0,
+            values: estimation.distinct_counter(),
+            frequent: estimation.frequent_values(),
+            example: None,
+            types: TypeAccumulator::default(),
+            numeric: NumericSummary::new(estimation),
+            lengths: LengthSummary::default(),
+            dates: DateRange::default(),
+        }
+    }
+
+    pub fn observe(&mut self, value: &str) {
         if value.trim().is_empty() {
             self.null_count += 1;
             return;
@@ -38,79 +72,203 @@ impl ColumnAccumulator {
             self.example = Some(value.to_string());
         }
 
-        self.values.insert(value.to_string());
+        self.observed += 1;
+        self.values.insert(value.as_bytes());
+        if let Some(frequent) = &mut self.frequent {
+            frequent.insert(value.as_bytes());
+        }
+        self.lengths.observe(value);
+        match self.types.observe(value) {
+            Some(ColumnType::Integer | ColumnType::Decimal) => {
+                if let Ok(number) = value.trim().parse::<f64>() {
+                    self.numeric.observe(number);
+                }
+            }
+            Some(ColumnType::Date | ColumnType::Datetime) => {
+                if let Some((instant, timed)) = parse_temporal(value) {
+                    self.dates.observe(instant, timed);
+                }
+            }
+            _ => {}
+        }
     }
-}
 
-pub fn run(
-    args: &StatsArgs,
-    no_witness: bool,
-    explicit: bool,
-) -> Result<CommandOutput, RefusalPayload> {
-    let file = File::open(&args.dataset).map_err(|error| {
-        RefusalPayload::io(args.dataset.display().to_string(), error.to_string())
-    })?;
+    pub fn null_rate(&self, row_count: usize) -> f64 {
+        self.null_count as f64 / row_count as f64
+    }
 
-    let mut reader = csv::Reader::from_reader(file);
-    let headers = read_headers(&mut reader, &args.dataset.display().to_string())?;
+    pub fn uniqueness(&self, row_count: usize) -> f64 {
+        self.values.count(self.observed) / row_count as f64
+    }
 
-    let selected = resolve_selected_columns(args, &headers)?;
-    let selected_column_names = selected
-        .columns
-        .iter()
-        .map(|(name, _)| name.clone())
-        .collect::<Vec<_>>();
+    pub fn inferred_type(&self) -> Option<(ColumnType, f64)> {
+        self.types.inferred_type()
+    }
 
-    let mut accumulators = selected
-        .columns
-        .iter()
-        .map(|_| ColumnAccumulator::default())
-        .collect::<Vec<_>>();
-    let mut row_count = 0usize;
-
-    for record in reader.records() {
-        let record = record.map_err(|error| {
-            RefusalPayload::csv_parse(args.dataset.display().to_string(), error.to_string())
-        })?;
-        row_count += 1;
-        apply_record(&record, &selected.columns, &mut accumulators);
+    /// Occurrences of every distinct non-null value, when they were counted exactly.
+    pub fn value_counts(&self) -> Option<&HashMap<Box<[u8]>, usize>> {
+        match &self.values {
+            DistinctCounter::Exact(values) => Some(values),
+            DistinctCounter::Approx(_) => None,
+        }
     }
 
-    if row_count == 0 {
-        return Err(RefusalPayload::empty_with_reason(
-            args.dataset.display().to_string(),
-            "no data rows",
-        ));
+    pub fn into_numeric(self) -> Option<NumericDistribution> {
+        self.numeric.finish()
     }
 
-    let columns = selected_column_names
-        .iter()
-        .zip(accumulators.iter())
-        .map(|(name, accumulator)| {
-            let null_rate = accumulator.null_count as f64 / row_count as f64;
-            let uniqueness = accumulator.values.len() as f64 / row_count as f64;
-            let key_viable = null_rate == 0.0 && uniqueness >= KEY_VIABLE_UNIQUENESS_THRESHOLD;
-
-            let mut col = json!({
-                "name": name,
-                "null_rate": null_rate,
-                "uniqueness": uniqueness,
-                "key_viable": key_viable,
-            });
+    /// Most frequent values as `{count, rate[, count_error][, value]}`; values are only shown
+    /// with `--explicit`, and `count_error` bounds a sketched count's overstatement.
+    fn top_values(&self, row_count: usize, explicit: bool) -> Vec<serde_json::Value> {
+        let entries = match &self.frequent {
+            Some(frequent) => frequent
+                .most_frequent(TOP_VALUES)
+                .into_iter()
+                .map(|(value, count, error)| (value, count, Some(error)))
+                .collect::<Vec<_>>(),
+            None => self
+                .values
+                .most_frequent(TOP_VALUES)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(value, count)| (value, count, None))
+                .collect(),
+        };
+        entries
+            .into_iter()
+            .map(|(value, count, error)| {
+                let mut entry = json!({
+                    "count": count,
+                    "rate": count as f64 / row_count as f64
+                });
+                if let Some(error) = error {
+                    entry["count_error"] = json!(error);
+                }
+                if explicit {
+                    entry["value"] = json!(String::from_utf8_lossy(value));
+                }
+                entry
+            })
+            .collect()
+    }
+}
 
-            if explicit {
-                col["example"] = json!(accumulator.example.clone().unwrap_or_default());
-            }
+pub fn run(
+    args: &StatsArgs,
+    no_witness: bool,
+    explicit: bool,
+) -> Result<CommandOutput, RefusalPayload> {
+    let profile = args.profile.as_deref().map(load_profile).transpose()?;
+    let baseline_profile = match (&args.save_baseline, &args.profile, &profile) {
+        (None, ..) => None,
+        (Some(_), Some(path), Some(profile)) if profile.profile_sha256.is_some() => {
+            Some((path.as_path(), profile))
+        }
+        (Some(_), ..) => {
+            return Err(RefusalPayload::invalid_schema_single(
+                "profile_sha256",
+                "--save-baseline requires --profile naming a frozen profile",
+            ));
+        }
+    };
+    let estimation = Estimation {
+        approx: args.approx,
+        sample: args.sample.map(NonZeroUsize::get),
+    };
+    let scan = scan_columns(
+        &args.dataset,
+        args.profile.as_deref().zip(profile.as_ref()),
+        estimation,
+    )?;
+    let scanned = scan.scanned;
+    let row_count = scanned.analyzed;
+    let column_count = scan.columns.len();
+
+    let mut baseline_columns = Vec::new();
+    let mut columns = Vec::with_capacity(column_count);
+    for (name, accumulator) in scan.columns {
+        let null_rate = accumulator.null_rate(row_count);
+        let uniqueness = accumulator.uniqueness(row_count);
+        let key_viable = null_rate == 0.0
+            && uniqueness >= KEY_VIABLE_UNIQUENESS_THRESHOLD
+            && scanned.is_complete();
+
+        let inferred = accumulator.inferred_type();
+
+        let mut col = json!({
+            "name": name,
+            "null_rate": null_rate,
+            "uniqueness": uniqueness,
+            "key_viable": key_viable,
+            "type": inferred.map(|(column_type, confidence)| json!({
+                "inferred": column_type.as_str(),
+                "confidence": confidence
+            })),
+            "top_values": accumulator.top_values(row_count, explicit),
+            "length": accumulator.lengths.summary(),
+            "date_range": accumulator.dates.summary(),
+        });
 
-            col
-        })
-        .collect::<Vec<_>>();
+        if explicit {
+            col["example"] = json!(accumulator.example.clone().unwrap_or_default());
+        }
+        if !estimation.is_exact() {
+            col["bounds"] = json!(estimation.bounds(&scanned, null_rate, uniqueness));
+        }
+        let categories = baseline_profile
+            .is_some()
+            .then(|| BaselineColumn::categories(&accumulator))
+            .flatten();
+        let numeric = accumulator.into_numeric();
+        col["numeric"] = numeric
+            .as_ref()
+            .map_or(Value::Null, NumericDistribution::summary);
+        if baseline_profile.is_some() {
+            baseline_columns.push(BaselineColumn {
+                name,
+                null_rate,
+                uniqueness,
+                numeric: numeric
+                    .as_ref()
+                    .filter(|_| {
+                        inferred.is_some_and(|(column_type, _)| {
+                            matches!(column_type, ColumnType::Integer | ColumnType::Decimal)
+                        })
+                    })
+                    .map(NumericBaseline::from_distribution),
+                categories,
+            });
+        }
+
+        columns.push(col);
+    }
 
-    let result = json!({
-        "row_count": row_count,
-        "column_count": selected_column_names.len(),
+    let mut result = json!({
+        "row_count": scanned.seen,
+        "column_count": column_count,
         "columns": columns
     });
+    if let Some(dialect) = scan.dialect {
+        result["dialect"] = dialect.summary();
+    }
+    if !estimation.is_exact() {
+        let mut approximation = estimation.summary(&scanned);
+        if let (Some(approximation), Some(methods)) = (
+            approximation.as_object_mut(),
+            estimation.value_methods().as_object(),
+        ) {
+            approximation.extend(methods.clone());
+        }
+        result["approximation"] = approximation;
+    }
+    if let (Some(path), Some((profile_path, profile))) = (&args.save_baseline, baseline_profile) {
+        let baseline = Baseline::new(profile_path, profile, scanned.seen, baseline_columns);
+        baseline.write(path)?;
+        result["baseline"] = json!({
+            "path": path.display().to_string(),
+            "profile_sha256": baseline.profile_sha256
+        });
+    }
 
     let mut inputs = vec![args.dataset.clone()];
     if let Some(profile) = &args.profile {
@@ -123,47 +281,92 @@ pub fn run(
         inputs,
         json!({
             "subcommand": "stats",
-            "profile": args.profile.as_ref().map(|path| path.display().to_string())
+            "profile": args.profile.as_ref().map(|path| path.display().to_string()),
+            "approx": args.approx,
+            "sample": args.sample,
+            "save_baseline": args.save_baseline.as_ref().map(|path| path.display().to_string())
         }),
         no_witness,
     );
 
     Ok(CommandOutput::success(result)
-        .with_profile_ref(selected.profile_ref)
+        .with_profile_ref(profile.as_ref().and_then(ProfileRef::from_profile))
         .with_witness_id(witness_id))
 }
 
-fn read_headers(
-    reader: &mut csv::Reader<File>,
-    dataset_path: &str,
-) -> Result<StringRecord, RefusalPayload> {
-    let headers = reader
-        .headers()
-        .map_err(|error| RefusalPayload::csv_parse(dataset_path.to_string(), error.to_string()))?
-        .clone();
+/// Read `dataset` (through `profile`'s `pre_parse`, scoped to its columns when given) and
+/// accumulate every selected column. Refuses `E_EMPTY` when no data rows were analyzed.
+pub(crate) fn scan_columns(
+    dataset: &Path,
+    profile: Option<(&Path, &Profile)>,
+    estimation: Estimation,
+) -> Result<ColumnScan, RefusalPayload> {
+    let rows = DatasetRows::open(
+        dataset,
+        profile
+            .map(|(_, profile)| ReadOptions::from_profile(profile))
+            .unwrap_or_default(),
+    )?;
+
+    let dialect = rows.dialect();
+    let selected = resolve_selected_columns(profile, rows.headers())?;
+    let mut accumulators = selected
+        .iter()
+        .map(|_| ColumnAccumulator::new(estimation))
+        .collect::<Vec<_>>();
+
+    let observe = |position: usize, accumulator: &mut ColumnAccumulator, record: &StringRecord| {
+        let index = selected[position].1;
+        accumulator.observe(record.get(index).unwrap_or_default());
+    };
+    let scanned = match estimation.sample {
+        Some(capacity) => {
+            let (sample, seen) = sample_records(rows, capacity)?;
+            let analyzed = accumulate(sample.into_iter().map(Ok), &mut accumulators, observe)?;
+            ScannedRows { analyzed, seen }
+        }
+        None => {
+            let row_count = accumulate(rows, &mut accumulators, observe)?;
+            ScannedRows {
+                analyzed: row_count,
+                seen: row_count,
+            }
+        }
+    };
 
-    if headers.is_empty() {
+    if scanned.analyzed == 0 {
         return Err(RefusalPayload::empty_with_reason(
-            dataset_path.to_string(),
-            "no header row",
+            dataset.display().to_string(),
+            "no data rows",
         ));
     }
 
-    Ok(headers)
+    Ok(ColumnScan {
+        columns: selected
+            .into_iter()
+            .map(|(name, _)| name)
+            .zip(accumulators)
+            .collect(),
+        scanned,
+        dialect,
+    })
+}
+
+fn load_profile(profile_path: &Path) -> Result<Profile, RefusalPayload> {
+    let profile_content = std::fs::read_to_string(profile_path).map_err(|error| {
+        RefusalPayload::io(profile_path.display().to_string(), error.to_string())
+    })?;
+
+    let profile = apply_extends(parse_profile_yaml(&profile_content)?, profile_path)?;
+    validate_profile(&profile, ValidationMode::Validate)?;
+    Ok(profile)
 }
 
 fn resolve_selected_columns(
-    args: &StatsArgs,
+    profile: Option<(&Path, &Profile)>,
     headers: &StringRecord,
-) -> Result<SelectedColumns, RefusalPayload> {
-    if let Some(profile_path) = &args.profile {
-        let profile_content = std::fs::read_to_string(profile_path).map_err(|error| {
-            RefusalPayload::io(profile_path.display().to_string(), error.to_string())
-        })?;
-
-        let profile = parse_profile_yaml(&profile_content)?;
-        validate_profile(&profile, ValidationMode::Validate)?;
-        let profile_ref = ProfileRef::from_profile(&profile);
+) -> Result<Vec<(String, usize)>, RefusalPayload> {
+    if let Some((profile_path, profile)) = profile {
         let column_aliases = profile
             .column_registry
             .as_deref()
@@ -172,17 +375,25 @@ fn resolve_selected_columns(
             })
             .transpose()?;
         let index_by_name = build_header_index(headers, column_aliases.as_ref());
+        let header_names = canonicalize_header_sequence(headers, column_aliases.as_ref());
+        let selection = resolve_profile_columns(profile, &header_names, &index_by_name);
 
-        let mut selected = Vec::with_capacity(profile.include_columns.len());
-        let mut missing = Vec::new();
-
-        for column in &profile.include_columns {
-            if let Some(index) = index_by_name.column_index(column) {
-                selected.push((column.clone(), index));
-            } else {
-                missing.push(column.clone());
-            }
-        }
+        let selected = selection
+            .columns
+            .iter()
+            .filter_map(|column| Some((column.clone(), index_by_name.column_index(column)?)))
+            .collect::<Vec<_>>();
+        let missing = selection
+            .missing
+            .iter()
+            .cloned()
+            .chain(
+                selection
+                    .unmatched_selectors()
+                    .filter(|selector| selector.list == "include_columns")
+                    .map(|selector| selector.selector.clone()),
+            )
+            .collect::<Vec<_>>();
 
         if !missing.is_empty() {
             return Err(RefusalPayload::column_not_found(
@@ -191,29 +402,12 @@ fn resolve_selected_columns(
             ));
         }
 
-        return Ok(SelectedColumns {
-            columns: selected,
-            profile_ref,
-        });
+        return Ok(selected);
     }
 
-    Ok(SelectedColumns {
-        columns: headers
-            .iter()
-            .enumerate()
-            .map(|(index, name)| (name.to_string(), index))
-            .collect::<Vec<_>>(),
-        profile_ref: None,
-    })
-}
-
-fn apply_record(
-    record: &StringRecord,
-    selected_columns: &[(String, usize)],
-    accumulators: &mut [ColumnAccumulator],
-) {
-    for (position, (_, index)) in selected_columns.iter().enumerate() {
-        let value = record.get(*index).unwrap_or_default();
-        accumulators[position].observe(value);
-    }
+    Ok(headers
+        .iter()
+        .enumerate()
+        .map(|(index, name)| (name.to_string(), index))
+        .collect())
 }
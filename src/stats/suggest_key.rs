@@ -1,18 +1,31 @@
 use std::cmp::Ordering;
-use std::collections::HashSet;
-use std::fs::File;
+use std::fs;
+use std::num::NonZeroUsize;
 use std::path::Path;
 
-use csv::ReaderBuilder;
+use csv::StringRecord;
+
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::cli::args::SuggestKeyArgs;
 use crate::output::json::CommandOutput;
 use crate::refusal::RefusalPayload;
+use crate::resolve::resolver::apply_extends;
+use crate::schema::{ValidationMode, parse_profile_yaml, validate_profile};
+use crate::slice::{DatasetRows, ReadOptions};
+use crate::stats::parallel::accumulate;
+use crate::stats::sketch::{Bounds, DistinctCounter, Estimation, ScannedRows, sample_records};
 use crate::witness::append::append_for_command;
 
 const AUTO_KEY_UNIQUENESS_THRESHOLD: f64 = 0.95;
+/// Columns combined into composite keys; combinations grow as C(n, 3), so the pool is capped.
+const COMPOSITE_POOL_SIZE: usize = 10;
+/// Widest composite key searched.
+const MAX_COMPOSITE_WIDTH: usize = 3;
+/// Combinations scored per pass over the rows when distinct tuples are counted exactly, so at
+/// most this many row-count-sized digest sets are held at once.
+const EXACT_COMPOSITE_BATCH: usize = 16;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyCandidate {
@@ -23,75 +36,141 @@ pub struct KeyCandidate {
     pub stability_score: u8,
     pub viable: bool,
     pub rank: usize,
+    /// 95% bounds when `--approx` or `--sample` made the scores estimates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<Bounds>,
+}
+
+/// A combination of columns scored as a key when no single column is viable. A row counts as
+/// null when any of its columns is empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositeKeyCandidate {
+    pub columns: Vec<String>,
+    pub positions: Vec<usize>,
+    pub uniqueness: f64,
+    pub null_rate: f64,
+    pub stability_score: u8,
+    pub viable: bool,
+    pub rank: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<Bounds>,
 }
 
-#[derive(Debug, Default)]
 struct ColumnStats {
-    unique_values: HashSet<String>,
+    unique_values: DistinctCounter,
     null_count: usize,
+    observed: usize,
 }
 
 impl ColumnStats {
+    fn new(estimation: Estimation) -> Self {
+        Self {
+            unique_values: estimation.distinct_counter(),
+            null_count: 0,
+            observed: 0,
+        }
+    }
+
     fn observe(&mut self, value: &str) {
         if value.trim().is_empty() {
             self.null_count += 1;
         } else {
-            self.unique_values.insert(value.to_string());
+            self.observed += 1;
+            self.unique_values.insert(value.as_bytes());
         }
     }
-}
 
-pub fn run(args: &SuggestKeyArgs, no_witness: bool) -> Result<CommandOutput, RefusalPayload> {
-    let file = File::open(&args.dataset).map_err(|error| {
-        RefusalPayload::io(args.dataset.display().to_string(), error.to_string())
-    })?;
-
-    let mut reader = ReaderBuilder::new().has_headers(true).from_reader(file);
-    let headers = reader
-        .headers()
-        .map_err(|error| {
-            RefusalPayload::csv_parse(args.dataset.display().to_string(), error.to_string())
-        })?
-        .clone();
-
-    if headers.is_empty() {
-        return Err(RefusalPayload::empty_with_reason(
-            args.dataset.display().to_string(),
-            "no header row",
-        ));
+    fn scores(&self, total_rows: usize) -> (f64, f64) {
+        (
+            self.unique_values.count(self.observed) / total_rows as f64,
+            self.null_count as f64 / total_rows as f64,
+        )
     }
+}
 
-    let header_names = headers
-        .iter()
-        .map(std::string::ToString::to_string)
-        .collect::<Vec<_>>();
-    let mut candidates = analyze_columns(&mut reader, &header_names, &args.dataset)?;
-
-    candidates.sort_by(|left, right| {
-        right
-            .uniqueness
-            .partial_cmp(&left.uniqueness)
-            .unwrap_or(Ordering::Equal)
-            .then(
-                left.null_rate
-                    .partial_cmp(&right.null_rate)
-                    .unwrap_or(Ordering::Equal),
-            )
-            .then(right.stability_score.cmp(&left.stability_score))
-            .then(left.position.cmp(&right.position))
-    });
+pub fn run(args: &SuggestKeyArgs, no_witness: bool) -> Result<CommandOutput, RefusalPayload> {
+    let profile = args
+        .profile
+        .as_deref()
+        .map(|profile_path| {
+            let profile_content = fs::read_to_string(profile_path).map_err(|error| {
+                RefusalPayload::io(profile_path.display().to_string(), error.to_string())
+            })?;
+            let profile = apply_extends(parse_profile_yaml(&profile_content)?, profile_path)?;
+            validate_profile(&profile, ValidationMode::Validate)?;
+            Ok::<_, RefusalPayload>(profile)
+        })
+        .transpose()?;
+    let options = profile
+        .as_ref()
+        .map(ReadOptions::from_profile)
+        .unwrap_or_default();
+    let estimation = Estimation {
+        approx: args.approx,
+        sample: args.sample.map(NonZeroUsize::get),
+    };
+    let rows = DatasetRows::open(&args.dataset, options)?;
+    let dialect = rows.dialect();
+    let headers = header_names(&rows);
 
-    for (index, candidate) in candidates.iter_mut().enumerate() {
-        candidate.rank = index + 1;
+    // A sample is drawn once and shared by the single-column and composite passes.
+    let (mut candidates, mut composites, scanned) = match estimation.sample {
+        Some(capacity) => {
+            let (sample, seen) = sample_records(rows, capacity)?;
+            let sampled = || Ok(sample.iter().cloned().map(Ok));
+            let (mut candidates, analyzed) =
+                rank_rows(&args.dataset, &headers, sampled()?, estimation)?;
+            let scanned = ScannedRows { analyzed, seen };
+            // A sample that skipped rows cannot show a key is unique, only that it may be.
+            if !scanned.is_complete() {
+                for candidate in &mut candidates {
+                    candidate.viable = false;
+                }
+            }
+            let composites = rank_combinations(&args.dataset, &candidates, estimation, sampled)?
+                .into_iter()
+                .map(|candidate| CompositeKeyCandidate {
+                    viable: candidate.viable && scanned.is_complete(),
+                    ..candidate
+                })
+                .collect();
+            (candidates, composites, scanned)
+        }
+        None => {
+            let (candidates, analyzed) = rank_rows(&args.dataset, &headers, rows, estimation)?;
+            let composites = rank_combinations(&args.dataset, &candidates, estimation, || {
+                DatasetRows::open(&args.dataset, options)
+            })?;
+            let scanned = ScannedRows {
+                analyzed,
+                seen: analyzed,
+            };
+            (candidates, composites, scanned)
+        }
+    };
+    if !estimation.is_exact() {
+        for candidate in &mut candidates {
+            candidate.bounds =
+                Some(estimation.bounds(&scanned, candidate.null_rate, candidate.uniqueness));
+        }
+        for candidate in &mut composites {
+            candidate.bounds =
+                Some(estimation.bounds(&scanned, candidate.null_rate, candidate.uniqueness));
+        }
     }
 
     let top_candidates = candidates
         .into_iter()
         .take(args.top)
         .collect::<Vec<KeyCandidate>>();
+    let top_composites = composites
+        .into_iter()
+        .take(args.top)
+        .collect::<Vec<CompositeKeyCandidate>>();
 
-    let result = json!({
+    let mut result = json!({
         "candidates": top_candidates,
+        "composite_candidates": top_composites,
         "top": args.top,
         "ranking": {
             "order": [
@@ -103,14 +182,28 @@ pub fn run(args: &SuggestKeyArgs, no_witness: bool) -> Result<CommandOutput, Ref
             "stability_signals": ["*_id", "*_key", "*_number"]
         }
     });
+    if let Some(dialect) = dialect {
+        result["dialect"] = dialect.summary();
+    }
+    if !estimation.is_exact() {
+        result["approximation"] = estimation.summary(&scanned);
+    }
+
+    let mut inputs = vec![args.dataset.clone()];
+    if let Some(profile) = &args.profile {
+        inputs.push(profile.clone());
+    }
 
     let witness_id = append_for_command(
         "suggest-key",
         &result,
-        vec![args.dataset.clone()],
+        inputs,
         json!({
             "subcommand": "suggest-key",
-            "top": args.top
+            "top": args.top,
+            "profile": args.profile.as_ref().map(|path| path.display().to_string()),
+            "approx": args.approx,
+            "sample": args.sample
         }),
         no_witness,
     );
@@ -118,28 +211,250 @@ pub fn run(args: &SuggestKeyArgs, no_witness: bool) -> Result<CommandOutput, Ref
     Ok(CommandOutput::success(result).with_witness_id(witness_id))
 }
 
-fn analyze_columns(
-    reader: &mut csv::Reader<File>,
-    headers: &[String],
-    dataset_path: &Path,
+/// Score every dataset column as a key candidate, best first, with `rank` assigned.
+pub fn rank_candidates(
+    dataset: &Path,
+    options: ReadOptions<'_>,
 ) -> Result<Vec<KeyCandidate>, RefusalPayload> {
-    let mut stats = headers
+    let rows = DatasetRows::open(dataset, options)?;
+    let headers = header_names(&rows);
+    Ok(rank_rows(dataset, &headers, rows, Estimation::default())?.0)
+}
+
+/// Exact composite candidates for `singles`, reading the dataset once per batch of combinations.
+pub fn rank_composites(
+    dataset: &Path,
+    options: ReadOptions<'_>,
+    singles: &[KeyCandidate],
+) -> Result<Vec<CompositeKeyCandidate>, RefusalPayload> {
+    rank_combinations(dataset, singles, Estimation::default(), || {
+        DatasetRows::open(dataset, options)
+    })
+}
+
+fn header_names(rows: &DatasetRows) -> Vec<String> {
+    rows.headers()
+        .iter()
+        .map(std::string::ToString::to_string)
+        .collect()
+}
+
+/// Ranked single-column candidates and the number of rows scored.
+fn rank_rows(
+    dataset: &Path,
+    headers: &[String],
+    rows: impl IntoIterator<Item = Result<StringRecord, RefusalPayload>>,
+    estimation: Estimation,
+) -> Result<(Vec<KeyCandidate>, usize), RefusalPayload> {
+    let (mut candidates, total_rows) = analyze_columns(rows, headers, dataset, estimation)?;
+
+    candidates.sort_by(|left, right| {
+        right
+            .uniqueness
+            .partial_cmp(&left.uniqueness)
+            .unwrap_or(Ordering::Equal)
+            .then(
+                left.null_rate
+                    .partial_cmp(&right.null_rate)
+                    .unwrap_or(Ordering::Equal),
+            )
+            .then(right.stability_score.cmp(&left.stability_score))
+            .then(left.position.cmp(&right.position))
+    });
+
+    for (index, candidate) in candidates.iter_mut().enumerate() {
+        candidate.rank = index + 1;
+    }
+
+    Ok((candidates, total_rows))
+}
+
+/// When no single column is viable, score pairs of the most promising columns, then triples
+/// if no pair is viable either; best first, with `rank` assigned. Empty when a single column
+/// already makes a key. `open_rows` supplies a fresh pass over the rows for each width, or
+/// for each batch of `EXACT_COMPOSITE_BATCH` combinations when counting exactly.
+fn rank_combinations<I>(
+    dataset: &Path,
+    singles: &[KeyCandidate],
+    estimation: Estimation,
+    mut open_rows: impl FnMut() -> Result<I, RefusalPayload>,
+) -> Result<Vec<CompositeKeyCandidate>, RefusalPayload>
+where
+    I: IntoIterator<Item = Result<StringRecord, RefusalPayload>>,
+{
+    if singles.iter().any(|candidate| candidate.viable) {
+        return Ok(Vec::new());
+    }
+
+    // Null-free, key-like, high-cardinality columns first; ties keep dataset order.
+    let mut pool = singles
         .iter()
-        .map(|_| ColumnStats::default())
+        .filter(|candidate| candidate.null_rate < 1.0)
         .collect::<Vec<_>>();
-    let mut total_rows = 0usize;
+    pool.sort_by(|left, right| {
+        left.null_rate
+            .partial_cmp(&right.null_rate)
+            .unwrap_or(Ordering::Equal)
+            .then(right.stability_score.cmp(&left.stability_score))
+            .then(
+                right
+                    .uniqueness
+                    .partial_cmp(&left.uniqueness)
+                    .unwrap_or(Ordering::Equal),
+            )
+            .then(left.position.cmp(&right.position))
+    });
+    pool.truncate(COMPOSITE_POOL_SIZE);
+    pool.sort_by_key(|candidate| candidate.position);
+
+    let mut composites = Vec::new();
+    for width in 2..=MAX_COMPOSITE_WIDTH {
+        let combinations = combinations(&pool, width);
+        if combinations.is_empty() {
+            break;
+        }
+        // HyperLogLog sketches are small enough to score a whole width in one pass.
+        let batch = if estimation.approx {
+            combinations.len()
+        } else {
+            EXACT_COMPOSITE_BATCH
+        };
+        let mut found_viable = false;
+        for combinations in combinations.chunks(batch) {
+            let scored = analyze_combinations(open_rows()?, combinations, dataset, estimation)?;
+            found_viable |= scored.iter().any(|candidate| candidate.viable);
+            composites.extend(scored);
+        }
+        if found_viable {
+            break;
+        }
+    }
+
+    composites.sort_by(|left, right| {
+        right
+            .uniqueness
+            .partial_cmp(&left.uniqueness)
+            .unwrap_or(Ordering::Equal)
+            .then(
+                left.null_rate
+                    .partial_cmp(&right.null_rate)
+                    .unwrap_or(Ordering::Equal),
+            )
+            .then(left.columns.len().cmp(&right.columns.len()))
+            .then(right.stability_score.cmp(&left.stability_score))
+            .then(left.positions.cmp(&right.positions))
+    });
+    for (index, candidate) in composites.iter_mut().enumerate() {
+        candidate.rank = index + 1;
+    }
 
-    for record in reader.records() {
-        let record = record.map_err(|error| {
-            RefusalPayload::csv_parse(dataset_path.display().to_string(), error.to_string())
-        })?;
-        total_rows += 1;
+    Ok(composites)
+}
 
-        for (index, _) in headers.iter().enumerate() {
-            let value = record.get(index).unwrap_or_default();
-            stats[index].observe(value);
+/// Every `width`-sized combination of `pool`, in lexicographic order of pool index.
+fn combinations<'a>(pool: &[&'a KeyCandidate], width: usize) -> Vec<Vec<&'a KeyCandidate>> {
+    if width == 0 {
+        return vec![Vec::new()];
+    }
+    let mut combinations = Vec::new();
+    for (index, first) in pool.iter().enumerate() {
+        for mut rest in self::combinations(&pool[index + 1..], width - 1) {
+            rest.insert(0, *first);
+            combinations.push(rest);
         }
     }
+    combinations
+}
+
+fn analyze_combinations(
+    rows: impl IntoIterator<Item = Result<StringRecord, RefusalPayload>>,
+    combinations: &[Vec<&KeyCandidate>],
+    dataset_path: &Path,
+    estimation: Estimation,
+) -> Result<Vec<CompositeKeyCandidate>, RefusalPayload> {
+    // Each row's values are reduced to a 128-bit digest, so an exact count holds 16 bytes per
+    // distinct tuple however wide the columns are; callers bound how many combinations share
+    // a pass.
+    let mut stats = combinations
+        .iter()
+        .map(|_| ColumnStats::new(estimation))
+        .collect::<Vec<_>>();
+    let total_rows = accumulate(
+        rows,
+        &mut stats,
+        |index, stats: &mut ColumnStats, record: &StringRecord| {
+            let values = combinations[index]
+                .iter()
+                .map(|candidate| record.get(candidate.position).unwrap_or_default())
+                .collect::<Vec<_>>();
+            if values.iter().any(|value| value.trim().is_empty()) {
+                stats.null_count += 1;
+                return;
+            }
+            let mut hasher = blake3::Hasher::new();
+            for value in &values {
+                hasher.update(&(value.len() as u64).to_le_bytes());
+                hasher.update(value.as_bytes());
+            }
+            let mut digest = [0u8; 16];
+            digest.copy_from_slice(&hasher.finalize().as_bytes()[..16]);
+            stats.observed += 1;
+            stats.unique_values.insert(&digest);
+        },
+    )?;
+
+    if total_rows == 0 {
+        return Err(RefusalPayload::empty_with_reason(
+            dataset_path.display().to_string(),
+            "no data rows",
+        ));
+    }
+
+    Ok(combinations
+        .iter()
+        .zip(&stats)
+        .map(|(combination, stats)| {
+            let (uniqueness, null_rate) = stats.scores(total_rows);
+            CompositeKeyCandidate {
+                columns: combination
+                    .iter()
+                    .map(|candidate| candidate.column.clone())
+                    .collect(),
+                positions: combination
+                    .iter()
+                    .map(|candidate| candidate.position)
+                    .collect(),
+                uniqueness,
+                null_rate,
+                stability_score: combination
+                    .iter()
+                    .map(|candidate| candidate.stability_score)
+                    .sum(),
+                viable: uniqueness >= AUTO_KEY_UNIQUENESS_THRESHOLD && null_rate == 0.0,
+                rank: 0,
+                bounds: None,
+            }
+        })
+        .collect())
+}
+
+fn analyze_columns(
+    rows: impl IntoIterator<Item = Result<StringRecord, RefusalPayload>>,
+    headers: &[String],
+    dataset_path: &Path,
+    estimation: Estimation,
+) -> Result<(Vec<KeyCandidate>, usize), RefusalPayload> {
+    let mut stats = headers
+        .iter()
+        .map(|_| ColumnStats::new(estimation))
+        .collect::<Vec<_>>();
+    let total_rows = accumulate(
+        rows,
+        &mut stats,
+        |index, stats: &mut ColumnStats, record: &StringRecord| {
+            stats.observe(record.get(index).unwrap_or_default());
+        },
+    )?;
 
     if total_rows == 0 {
         return Err(RefusalPayload::empty_with_reason(
@@ -148,13 +463,11 @@ fn analyze_columns(
         ));
     }
 
-    Ok(headers
+    let candidates = headers
         .iter()
         .enumerate()
         .map(|(position, column_name)| {
-            let column_stats = &stats[position];
-            let uniqueness = column_stats.unique_values.len() as f64 / total_rows as f64;
-            let null_rate = column_stats.null_count as f64 / total_rows as f64;
+            let (uniqueness, null_rate) = stats[position].scores(total_rows);
             let stability_score = stability_score(column_name);
             let viable = uniqueness >= AUTO_KEY_UNIQUENESS_THRESHOLD && null_rate == 0.0;
 
@@ -166,9 +479,11 @@ fn analyze_columns(
                 stability_score,
                 viable,
                 rank: 0,
+                bounds: None,
             }
         })
-        .collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    Ok((candidates, total_rows))
 }
 
 fn stability_score(column_name: &str) -> u8 {
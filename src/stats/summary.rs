@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDateTime;
+use serde_json::{Map, Value, json};
+
+use crate::stats::sketch::{Estimation, Reservoir};
+
+/// Quantiles reported for numeric values and string lengths, by nearest rank.
+const QUANTILES: [(&str, f64); 5] = [
+    ("p05", 0.05),
+    ("p25", 0.25),
+    ("p50", 0.5),
+    ("p75", 0.75),
+    ("p95", 0.95),
+];
+
+/// Running moments of a column's numeric cells, with their values kept for quantiles.
+pub(crate) struct NumericSummary {
+    count: usize,
+    min: f64,
+    max: f64,
+    mean: f64,
+    /// Sum of squared deviations from the running mean (Welford).
+    m2: f64,
+    values: Reservoir<f64>,
+}
+
+impl NumericSummary {
+    pub fn new(estimation: Estimation) -> Self {
+        Self {
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            mean: 0.0,
+            m2: 0.0,
+            values: estimation.value_reservoir(),
+        }
+    }
+
+    pub fn observe(&mut self, value: f64) {
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.values.push(value);
+    }
+
+    /// The finished distribution, or `None` when the column held no numeric cells.
+    pub fn finish(self) -> Option<NumericDistribution> {
+        if self.count == 0 {
+            return None;
+        }
+        let stddev = if self.count > 1 {
+            (self.m2 / (self.count - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        let mut sorted = self.values.into_items();
+        sorted.sort_by(f64::total_cmp);
+        Some(NumericDistribution {
+            count: self.count,
+            min: self.min,
+            max: self.max,
+            mean: self.mean,
+            stddev,
+            sorted,
+        })
+    }
+}
+
+/// Moments of a column's numeric cells with its (possibly sampled) values in ascending order.
+pub(crate) struct NumericDistribution {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Sample standard deviation.
+    pub stddev: f64,
+    sorted: Vec<f64>,
+}
+
+impl NumericDistribution {
+    /// `{count, min, max, mean, stddev, quantiles}`.
+    pub fn summary(&self) -> Value {
+        let quantiles = QUANTILES
+            .iter()
+            .map(|(name, quantile)| ((*name).to_string(), json!(self.quantile(*quantile))))
+            .collect::<Map<_, _>>();
+        json!({
+            "count": self.count,
+            "min": self.min,
+            "max": self.max,
+            "mean": self.mean,
+            "stddev": self.stddev,
+            "quantiles": quantiles
+        })
+    }
+
+    /// Nearest-rank value at `quantile`.
+    pub fn quantile(&self, quantile: f64) -> f64 {
+        self.sorted[nearest_rank(quantile, self.sorted.len()) - 1]
+    }
+
+    /// Share of values in each bin cut at `edges`: bin `i` holds values above `edges[i - 1]`
+    /// and at most `edges[i]`, and the last bin holds everything above the final edge.
+    pub fn proportions(&self, edges: &[f64]) -> Vec<f64> {
+        let mut counts = vec![0usize; edges.len() + 1];
+        for value in &self.sorted {
+            counts[edges.partition_point(|edge| edge < value)] += 1;
+        }
+        counts
+            .into_iter()
+            .map(|count| count as f64 / self.sorted.len() as f64)
+            .collect()
+    }
+}
+
+/// Character lengths of a column's non-null cells, kept as a histogram so the distribution is
+/// exact in memory bounded by the number of distinct lengths.
+#[derive(Default)]
+pub(crate) struct LengthSummary {
+    histogram: BTreeMap<usize, usize>,
+}
+
+impl LengthSummary {
+    pub fn observe(&mut self, value: &str) {
+        *self.histogram.entry(value.chars().count()).or_default() += 1;
+    }
+
+    pub fn summary(&self) -> Value {
+        let (Some((&min, _)), Some((&max, _))) = (
+            self.histogram.first_key_value(),
+            self.histogram.last_key_value(),
+        ) else {
+            return Value::Null;
+        };
+        let count = self.histogram.values().sum::<usize>();
+        let total = self
+            .histogram
+            .iter()
+            .map(|(length, occurrences)| length * occurrences)
+            .sum::<usize>();
+        let quantiles = QUANTILES
+            .iter()
+            .map(|(name, quantile)| {
+                let rank = nearest_rank(*quantile, count);
+                let mut cumulative = 0usize;
+                let length = self
+                    .histogram
+                    .iter()
+                    .find(|(_, occurrences)| {
+                        cumulative += **occurrences;
+                        cumulative >= rank
+                    })
+                    .map_or(max, |(length, _)| *length);
+                ((*name).to_string(), json!(length))
+            })
+            .collect::<Map<_, _>>();
+        json!({
+            "min": min,
+            "max": max,
+            "mean": total as f64 / count as f64,
+            "quantiles": quantiles
+        })
+    }
+}
+
+/// Earliest and latest date or datetime cell of a column.
+#[derive(Default)]
+pub(crate) struct DateRange {
+    min: Option<NaiveDateTime>,
+    max: Option<NaiveDateTime>,
+    timed: bool,
+}
+
+impl DateRange {
+    pub fn observe(&mut self, instant: NaiveDateTime, timed: bool) {
+        self.min = Some(self.min.map_or(instant, |min| min.min(instant)));
+        self.max = Some(self.max.map_or(instant, |max| max.max(instant)));
+        self.timed |= timed;
+    }
+
+    /// ISO 8601 bounds, as dates unless some cell carried a time of day.
+    pub fn summary(&self) -> Value {
+        let (Some(min), Some(max)) = (self.min, self.max) else {
+            return Value::Null;
+        };
+        let format = if self.timed {
+            "%Y-%m-%dT%H:%M:%S%.f"
+        } else {
+            "%Y-%m-%d"
+        };
+        json!({
+            "min": min.format(format).to_string(),
+            "max": max.format(format).to_string()
+        })
+    }
+}
+
+/// 1-based nearest rank of `quantile` among `count` sorted values.
+fn nearest_rank(quantile: f64, count: usize) -> usize {
+    ((quantile * count as f64).ceil() as usize).clamp(1, count)
+}
@@ -1,8 +1,10 @@
 use std::fs;
+use std::io;
 use std::path::PathBuf;
 
 use serde_json::{Value, json};
 
+use crate::compression::{Compression, is_stdin, stdin_bytes};
 use crate::witness::{ledger, record};
 
 pub fn append_for_command(
@@ -16,11 +18,10 @@ pub fn append_for_command(
         return None;
     }
 
-    let outcome = if subcommand == "lint"
-        && result
-            .get("issues")
-            .and_then(Value::as_array)
-            .is_some_and(|issues| !issues.is_empty())
+    let outcome = if (matches!(subcommand, "lint" | "check")
+        && crate::lint::lint::has_actionable_issues(result))
+        || (subcommand == "normalize" && crate::normalize::normalize::has_unparsed_values(result))
+        || (subcommand == "drift" && crate::stats::drift::has_drift_issues(result))
     {
         "ISSUES_FOUND"
     } else {
@@ -74,19 +75,50 @@ pub fn append_for_command(
 fn witness_enabled_subcommand(subcommand: &str) -> bool {
     matches!(
         subcommand,
-        "freeze" | "validate" | "lint" | "slice" | "stats" | "suggest-key"
+        "freeze"
+            | "validate"
+            | "lint"
+            | "check"
+            | "slice"
+            | "normalize"
+            | "apply"
+            | "stats"
+            | "suggest-key"
+            | "drift"
     )
 }
 
 fn build_inputs(input_paths: &[PathBuf]) -> Result<Value, String> {
     let mut inputs = Vec::with_capacity(input_paths.len());
     for path in input_paths {
+        // Standard input cannot be re-read, so hash the bytes the command already consumed.
+        if is_stdin(path) {
+            let bytes = stdin_bytes().map_err(|refusal| format!("stdin: {}", refusal.message))?;
+            inputs.push(json!({
+                "path": "stdin",
+                "hash": format!("blake3:{}", blake3::hash(bytes).to_hex()),
+                "bytes": bytes.len()
+            }));
+            continue;
+        }
         let bytes = fs::read(path).map_err(|error| format!("{}: {error}", path.display()))?;
-        inputs.push(json!({
+        let mut input = json!({
             "path": path.display().to_string(),
             "hash": format!("blake3:{}", blake3::hash(&bytes).to_hex()),
             "bytes": bytes.len()
-        }));
+        });
+        // `hash` pins the file as stored; the decompressed hash pins the content that was read.
+        if let Some(compression) = Compression::for_path(path) {
+            let mut hasher = blake3::Hasher::new();
+            let decompressed_bytes = compression
+                .decoder(bytes.as_slice())
+                .and_then(|mut decoder| io::copy(&mut decoder, &mut hasher))
+                .map_err(|error| format!("{}: {error}", path.display()))?;
+            input["compression"] = json!(compression.as_str());
+            input["decompressed_hash"] = json!(format!("blake3:{}", hasher.finalize().to_hex()));
+            input["decompressed_bytes"] = json!(decompressed_bytes);
+        }
+        inputs.push(input);
     }
     Ok(Value::Array(inputs))
 }
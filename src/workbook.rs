@@ -0,0 +1,104 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use calamine::{Data, Reader, Xlsx, XlsxError};
+
+use crate::compression::read_dataset;
+use crate::refusal::RefusalPayload;
+
+/// One worksheet rendered to text cells. Row `n` of the sheet is `rows[n - 1]`, so directive
+/// row numbers match what a spreadsheet shows; blank rows are empty and trailing empty cells
+/// are dropped the way a delimited export would omit them.
+#[derive(Debug)]
+pub(crate) struct Worksheet {
+    pub name: String,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Worksheet {
+    /// The first row with any non-empty cell, 1-based.
+    pub fn first_filled_row(&self) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|row| !row.is_empty())
+            .map(|index| index + 1)
+    }
+}
+
+/// Whether `path` names an Excel workbook by its extension.
+pub(crate) fn is_workbook_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("xlsx") || extension.eq_ignore_ascii_case("xlsm")
+        })
+}
+
+/// Read `sheet` (or the first sheet) of an xlsx workbook from disk. The archive is loaded into
+/// memory first, which also lets a `.xlsx.gz` be read without unpacking it to disk.
+pub(crate) fn read_worksheet(
+    path: &Path,
+    sheet: Option<&str>,
+) -> Result<Worksheet, RefusalPayload> {
+    let mut workbook =
+        Xlsx::new(Cursor::new(read_dataset(path)?)).map_err(|error: XlsxError| {
+            RefusalPayload::io(path.display().to_string(), error.to_string())
+        })?;
+    let names = workbook.sheet_names();
+    let name = match sheet {
+        Some(sheet) => names
+            .iter()
+            .find(|name| name.as_str() == sheet)
+            .cloned()
+            .ok_or_else(|| {
+                RefusalPayload::invalid_schema_single(
+                    "sheet",
+                    format!(
+                        "workbook has no sheet named '{sheet}' (sheets: {})",
+                        names.join(", ")
+                    ),
+                )
+            })?,
+        None => names.first().cloned().ok_or_else(|| {
+            RefusalPayload::empty_with_reason(path.display().to_string(), "workbook has no sheets")
+        })?,
+    };
+    let range = workbook
+        .worksheet_range(&name)
+        .map_err(|error| RefusalPayload::io(path.display().to_string(), error.to_string()))?;
+
+    let leading_rows = range.start().map_or(0, |(row, _)| row as usize);
+    let mut rows = vec![Vec::new(); leading_rows];
+    for cells in range.rows() {
+        let mut row = cells.iter().map(cell_text).collect::<Vec<_>>();
+        while row.last().is_some_and(String::is_empty) {
+            row.pop();
+        }
+        rows.push(row);
+    }
+
+    Ok(Worksheet { name, rows })
+}
+
+/// Render a cell the way it reads in a CSV export: whole numbers without a trailing `.0`,
+/// dates as ISO 8601, durations as their day count, and error cells as their `#` code.
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::Empty => String::new(),
+        Data::String(value) | Data::DateTimeIso(value) | Data::DurationIso(value) => value.clone(),
+        Data::Int(value) => value.to_string(),
+        Data::Float(value) => value.to_string(),
+        Data::Bool(value) => value.to_string(),
+        Data::DateTime(value) if value.is_duration() => value.as_f64().to_string(),
+        Data::DateTime(value) => match value.to_ymd_hms_milli() {
+            (year, month, day, 0, 0, 0, 0) => format!("{year:04}-{month:02}-{day:02}"),
+            (year, month, day, hour, minute, second, 0) => {
+                format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}")
+            }
+            (year, month, day, hour, minute, second, milli) => format!(
+                "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}.{milli:03}"
+            ),
+        },
+        Data::Error(error) => error.to_string(),
+    }
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use common::{copy_fixture, fixture_path, parse_stdout_json, profile_cmd, temp_workspace};
+use serde_json::{Value, json};
+use std::fs;
+
+#[test]
+fn apply_projects_registry_headers_to_profile_columns_and_writes_manifest() {
+    let workspace = temp_workspace();
+    let registry_dir = workspace.path().join("registries").join("annex_columns_v0");
+    copy_fixture(
+        "registries/annex_columns_v0/registry.json",
+        registry_dir.join("registry.json"),
+    );
+    copy_fixture(
+        "registries/annex_columns_v0/aliases.json",
+        registry_dir.join("aliases.json"),
+    );
+
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+profile_id: csv.loan_tape.annex.v1
+profile_version: 1
+profile_family: csv.loan_tape.annex
+profile_sha256: sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef
+status: frozen
+format: csv
+column_registry: registries/annex_columns_v0
+equivalence:
+  order: order-invariant
+  float_decimals: 2
+  trim_strings: true
+key:
+  - loan_id_number
+include_columns:
+  - note_rate
+  - loan_id_number
+  - current_balance
+column_equivalence:
+  note_rate:
+    float_decimals: 4
+  loan_id_number:
+    case_insensitive: true
+",
+    )
+    .expect("profile fixture write should succeed");
+
+    let out = workspace.path().join("scoped.csv");
+    let manifest = workspace.path().join("apply.manifest.json");
+    let assert = profile_cmd()
+        .arg("apply")
+        .arg(fixture_path("datasets/valid/loan_tape_alt_headers.csv"))
+        .arg("--profile")
+        .arg(&profile_path)
+        .arg("--out")
+        .arg(&out)
+        .arg("--emit-manifest")
+        .arg(&manifest)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        fs::read_to_string(&out).expect("read scoped csv"),
+        "note_rate,loan_id_number,current_balance\n5.0000,a,100\n6.1000,b,200\n5.5000,c,150\n"
+    );
+    assert_eq!(envelope["subcommand"], "apply");
+    assert_eq!(envelope["result"]["profile_id"], "csv.loan_tape.annex.v1");
+    assert_eq!(
+        envelope["profile_ref"]["profile_id"],
+        "csv.loan_tape.annex.v1"
+    );
+    assert!(envelope["result"].get("scoped_csv").is_none());
+
+    let manifest_json: Value =
+        serde_json::from_str(&fs::read_to_string(&manifest).expect("manifest")).expect("json");
+    assert_eq!(manifest_json["schema"], "profile.apply_manifest.v1");
+    assert_eq!(
+        manifest_json["columns"][0],
+        json!({
+            "column": "note_rate",
+            "source_header": "Note Rate",
+            "source_index": 2,
+            "normalized": false,
+            "equivalence": { "float_decimals": 4, "trim_strings": true }
+        })
+    );
+    assert_eq!(
+        manifest_json["output_hash"],
+        envelope["result"]["output_hash"]
+    );
+}
+
+#[test]
+fn apply_streams_scoped_csv_to_stdout_in_human_mode() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.csv");
+    fs::write(
+        &dataset,
+        "loan_id,property_type,balance,rate\n LN-1 ,SFR,100.004,0.0525\nLN-2,CONDO,-0.0000001,1\n",
+    )
+    .expect("dataset write should succeed");
+
+    let assert = profile_cmd()
+        .arg("apply")
+        .arg(&dataset)
+        .arg("--profile")
+        .arg(fixture_path("profiles/valid/frozen_complete.yaml"))
+        .arg("--no-witness")
+        .assert();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        stdout,
+        "loan_id,balance,rate\nLN-1,100.004000,0.052500\nLN-2,0.000000,1\n"
+    );
+}
+
+#[test]
+fn apply_refuses_draft_profiles() {
+    let assert = profile_cmd()
+        .arg("apply")
+        .arg(fixture_path("datasets/valid/loan_tape_basic.csv"))
+        .arg("--profile")
+        .arg(fixture_path("profiles/valid/draft_minimal.yaml"))
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+
+    assert_eq!(envelope["result"]["code"], "E_INVALID_SCHEMA");
+}
+
+#[test]
+fn apply_expands_selectors_in_profile_order_and_drops_exclusions() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("tape.csv");
+    fs::write(
+        &dataset,
+        "balance_original,loan_id,rate,balance_current,balance_memo\n12,L1,0.05,10,x\n",
+    )
+    .expect("dataset write should succeed");
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+profile_id: csv.servicer_tape.core.v1
+profile_version: 1
+profile_family: csv.servicer_tape.core
+profile_sha256: sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef
+status: frozen
+format: csv
+include_columns:
+  - loan_id
+  - glob:balance_*
+exclude_columns:
+  - \"re:.*_memo\"
+",
+    )
+    .expect("profile fixture write should succeed");
+
+    let assert = profile_cmd()
+        .arg("apply")
+        .arg(&dataset)
+        .arg("--profile")
+        .arg(&profile_path)
+        .arg("--no-witness")
+        .assert();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        stdout,
+        "loan_id,balance_original,balance_current\nL1,12,10\n"
+    );
+}
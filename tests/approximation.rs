@@ -0,0 +1,214 @@
+mod common;
+
+use common::{parse_stdout_json, profile_cmd, temp_workspace};
+use serde_json::{Value, json};
+use std::fmt::Write as _;
+use std::fs;
+
+fn write_loans(path: &std::path::Path, rows: usize) {
+    let mut content = String::from("loan_id,status\n");
+    for index in 0..rows {
+        let status = if index % 4 == 0 { "" } else { "open" };
+        writeln!(content, "LN-{index},{status}").expect("format row");
+    }
+    fs::write(path, content).expect("dataset write");
+}
+
+#[test]
+fn stats_approx_estimates_uniqueness_with_bounds() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.csv");
+    write_loans(&dataset, 2000);
+
+    let assert = profile_cmd()
+        .arg("stats")
+        .arg(&dataset)
+        .arg("--approx")
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    let result = &envelope["result"];
+    assert_eq!(result["row_count"], 2000);
+    assert_eq!(result["approximation"]["distinct"]["method"], "hyperloglog");
+    assert_eq!(result["approximation"]["distinct"]["precision"], 14);
+    assert_eq!(result["approximation"]["sample"], Value::Null);
+
+    let loan_id = &result["columns"][0];
+    let uniqueness = loan_id["uniqueness"].as_f64().expect("uniqueness");
+    assert!((uniqueness - 1.0).abs() < 0.03, "uniqueness {uniqueness}");
+    let bounds = &loan_id["bounds"]["uniqueness"];
+    assert!(bounds[0].as_f64().expect("lower") <= uniqueness);
+    assert!(bounds[1].as_f64().expect("upper") >= uniqueness);
+    // Null counts stay exact without sampling.
+    assert_eq!(
+        result["columns"][1]["bounds"]["null_rate"],
+        json!([0.25, 0.25])
+    );
+    assert_eq!(
+        result["approximation"]["top_values"]["method"],
+        "space_saving"
+    );
+    assert_eq!(
+        result["columns"][1]["top_values"],
+        json!([{ "count": 1500, "count_error": 0, "rate": 0.75 }])
+    );
+}
+
+#[test]
+fn stats_sample_is_seeded_and_bounds_the_null_rate() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.csv");
+    write_loans(&dataset, 400);
+
+    let run = || {
+        let assert = profile_cmd()
+            .arg("stats")
+            .arg(&dataset)
+            .arg("--sample")
+            .arg("100")
+            .arg("--json")
+            .arg("--no-witness")
+            .assert();
+        let envelope = parse_stdout_json(&assert);
+        common::assert_success_exit!(assert);
+        envelope["result"].clone()
+    };
+    let first = run();
+    assert_eq!(first, run());
+
+    assert_eq!(first["row_count"], 400);
+    assert_eq!(first["approximation"]["distinct"]["method"], "exact");
+    assert_eq!(first["approximation"]["sample"]["method"], "reservoir");
+    assert_eq!(first["approximation"]["sample"]["rows_sampled"], 100);
+    assert_eq!(first["approximation"]["sample"]["rows_seen"], 400);
+
+    let status = &first["columns"][1];
+    let null_rate = status["null_rate"].as_f64().expect("null rate");
+    let bounds = &status["bounds"]["null_rate"];
+    let (lower, upper) = (
+        bounds[0].as_f64().expect("lower"),
+        bounds[1].as_f64().expect("upper"),
+    );
+    assert!(lower < null_rate && null_rate < upper);
+    assert!(lower <= 0.25 && 0.25 <= upper, "[{lower}, {upper}]");
+}
+
+#[test]
+fn suggest_key_samples_once_for_singles_and_composites() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("payments.csv");
+    let mut content = String::from("loan_id,period,amount\n");
+    for loan in 0..50 {
+        for period in 1..=4 {
+            writeln!(content, "LN-{loan},{period},{}", 100 + period).expect("format row");
+        }
+    }
+    fs::write(&dataset, content).expect("dataset write");
+
+    let assert = profile_cmd()
+        .arg("suggest-key")
+        .arg(&dataset)
+        .arg("--sample")
+        .arg("60")
+        .arg("--approx")
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    let result = &envelope["result"];
+    assert_eq!(result["approximation"]["distinct"]["method"], "hyperloglog");
+    assert_eq!(result["approximation"]["sample"]["rows_sampled"], 60);
+    assert_eq!(result["approximation"]["sample"]["rows_seen"], 200);
+    assert!(result["candidates"][0]["bounds"]["uniqueness"].is_array());
+    assert_eq!(
+        result["composite_candidates"][0]["columns"],
+        json!(["loan_id", "period"])
+    );
+    assert!(result["composite_candidates"][0]["bounds"]["null_rate"].is_array());
+}
+
+#[test]
+fn witness_params_record_the_estimation_mode() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.csv");
+    write_loans(&dataset, 10);
+    let ledger_path = workspace.path().join("witness.jsonl");
+
+    for command in ["stats", "suggest-key"] {
+        let assert = profile_cmd()
+            .env("HOME", workspace.path())
+            .env("EPISTEMIC_WITNESS", &ledger_path)
+            .arg(command)
+            .arg(&dataset)
+            .arg("--approx")
+            .arg("--sample")
+            .arg("5")
+            .arg("--json")
+            .assert();
+        common::assert_success_exit!(assert);
+    }
+
+    let ledger = fs::read_to_string(&ledger_path).expect("read witness ledger");
+    for line in ledger.lines() {
+        let record: Value = serde_json::from_str(line).expect("json");
+        assert_eq!(record["params"]["approx"], true);
+        assert_eq!(record["params"]["sample"], 5);
+    }
+    assert_eq!(ledger.lines().count(), 2);
+}
+
+#[test]
+fn sampled_uniqueness_never_establishes_a_key() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.csv");
+    // Every loan appears twice, so the full data is only 50% unique.
+    let mut content = String::from("loan_id,status\n");
+    for index in 0..2000 {
+        writeln!(content, "LN-{},open", index % 1000).expect("format row");
+    }
+    fs::write(&dataset, content).expect("dataset write");
+
+    let assert = profile_cmd()
+        .arg("stats")
+        .arg(&dataset)
+        .arg("--sample")
+        .arg("10")
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+    let loan_id = &envelope["result"]["columns"][0];
+    assert_eq!(loan_id["uniqueness"], 1.0);
+    assert_eq!(loan_id["key_viable"], false);
+    let bounds = &loan_id["bounds"]["uniqueness"];
+    assert_eq!(bounds[0], 0.005);
+    assert_eq!(bounds[1], 1.0);
+
+    let assert = profile_cmd()
+        .arg("suggest-key")
+        .arg(&dataset)
+        .arg("--sample")
+        .arg("10")
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+    let result = &envelope["result"];
+    assert_eq!(result["candidates"][0]["column"], "loan_id");
+    assert_eq!(result["candidates"][0]["uniqueness"], 1.0);
+    assert_eq!(result["candidates"][0]["viable"], false);
+    assert!(
+        result["composite_candidates"]
+            .as_array()
+            .expect("composites")
+            .iter()
+            .all(|candidate| candidate["viable"] == false)
+    );
+}
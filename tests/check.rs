@@ -0,0 +1,364 @@
+mod common;
+
+use common::{parse_stdout_json, profile_cmd, temp_workspace};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+
+const PROFILE: &str = "\
+schema_version: 1
+status: draft
+format: csv
+key:
+  - loan_id
+include_columns:
+  - loan_id
+  - balance
+  - state
+  - origination_date
+  - maturity_date
+constraints:
+  columns:
+    loan_id:
+      not_null: true
+      unique: true
+      pattern: 'LN-\\d{3}'
+    balance:
+      min: 0
+      max: 1000000
+    state:
+      allowed_values: [CA, NY, TX]
+  rules:
+    - maturity_date > origination_date
+";
+
+const CLEAN: &str = "\
+loan_id,balance,state,origination_date,maturity_date
+LN-001,100.50,CA,2020-01-15,2050-01-15
+LN-002,2250.00,NY,2021-06-01,2036-06-01
+LN-003,75.25,,2022-03-10,
+";
+
+const DIRTY: &str = "\
+loan_id,balance,state,origination_date,maturity_date
+LN-001,100.50,CA,2020-01-15,2050-01-15
+LN-001,-5,NY,2021-06-01,2036-06-01
+,n/a,WA,2022-03-10,2022-03-10
+LN-4,75.25,TX,2023-01-01,2022-12-31
+";
+
+fn write(path: &Path, content: &str) {
+    fs::write(path, content).expect("fixture write should succeed");
+}
+
+fn check(dataset: &Path, profile: &Path, explicit: bool) -> (assert_cmd::assert::Assert, Value) {
+    let mut cmd = profile_cmd();
+    cmd.arg("check")
+        .arg(dataset)
+        .arg("--profile")
+        .arg(profile)
+        .arg("--json")
+        .arg("--no-witness");
+    if explicit {
+        cmd.arg("--explicit");
+    }
+    let assert = cmd.assert();
+    let envelope = parse_stdout_json(&assert);
+    (assert, envelope)
+}
+
+#[test]
+fn check_passes_when_every_constraint_holds() {
+    let workspace = temp_workspace();
+    let profile = workspace.path().join("profile.yaml");
+    write(&profile, PROFILE);
+    let dataset = workspace.path().join("tape.csv");
+    write(&dataset, CLEAN);
+
+    let (assert, envelope) = check(&dataset, &profile, false);
+    common::assert_success_exit!(assert);
+    assert_eq!(envelope["subcommand"], "check");
+    assert_eq!(
+        envelope["result"],
+        json!({
+            "rows": 3,
+            "checked": { "columns": 3, "rules": 1 },
+            "issues": []
+        })
+    );
+}
+
+#[test]
+fn check_reports_each_broken_constraint() {
+    let workspace = temp_workspace();
+    let profile = workspace.path().join("profile.yaml");
+    write(&profile, PROFILE);
+    let dataset = workspace.path().join("tape.csv");
+    write(&dataset, DIRTY);
+
+    let (assert, envelope) = check(&dataset, &profile, false);
+    common::assert_issues_exit!(assert);
+    let issues = envelope["result"]["issues"].as_array().expect("issues");
+    let summary = issues
+        .iter()
+        .map(|issue| {
+            (
+                issue["kind"].as_str().unwrap_or_default(),
+                issue["column"]
+                    .as_str()
+                    .or_else(|| issue["rule"].as_str())
+                    .unwrap_or_default(),
+                issue["constraint"].as_str().unwrap_or_default(),
+                issue["sample_rows"].clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("constraint_violation", "balance", "range", json!([2, 3])),
+            ("constraint_violation", "loan_id", "not_null", json!([3])),
+            ("constraint_violation", "loan_id", "unique", json!([2])),
+            ("constraint_violation", "loan_id", "pattern", json!([4])),
+            (
+                "constraint_violation",
+                "state",
+                "allowed_values",
+                json!([3])
+            ),
+            (
+                "rule_violation",
+                "maturity_date > origination_date",
+                "",
+                json!([3, 4])
+            ),
+        ]
+    );
+    assert_eq!(issues[0]["violating_rows"], 2);
+    assert_eq!(issues[0]["violating_percent"], 50.0);
+    assert!(issues.iter().all(|issue| issue.get("samples").is_none()));
+
+    let (_, envelope) = check(&dataset, &profile, true);
+    let issues = envelope["result"]["issues"].as_array().expect("issues");
+    assert_eq!(issues[0]["samples"], json!(["-5", "n/a"]));
+    assert_eq!(
+        issues[5]["samples"][1],
+        json!({ "left": "2022-12-31", "right": "2023-01-01" })
+    );
+}
+
+#[test]
+fn check_reports_missing_constrained_columns() {
+    let workspace = temp_workspace();
+    let profile = workspace.path().join("profile.yaml");
+    write(&profile, PROFILE);
+    let dataset = workspace.path().join("tape.csv");
+    write(&dataset, "loan_id,balance,state\nLN-001,1,CA\n");
+
+    let (assert, envelope) = check(&dataset, &profile, false);
+    common::assert_issues_exit!(assert);
+    assert_eq!(
+        envelope["result"]["issues"],
+        json!([
+            { "kind": "missing_column", "column": "maturity_date", "severity": "error" },
+            { "kind": "missing_column", "column": "origination_date", "severity": "error" }
+        ])
+    );
+    assert_eq!(envelope["result"]["checked"]["rules"], 0);
+}
+
+#[test]
+fn check_refuses_profile_without_constraints() {
+    let workspace = temp_workspace();
+    let profile = workspace.path().join("profile.yaml");
+    write(
+        &profile,
+        "schema_version: 1\nstatus: draft\nformat: csv\ninclude_columns: [loan_id]\n",
+    );
+    let dataset = workspace.path().join("tape.csv");
+    write(&dataset, CLEAN);
+
+    let (assert, envelope) = check(&dataset, &profile, false);
+    common::assert_refusal_exit!(assert);
+    assert_eq!(envelope["result"]["code"], "E_MISSING_FIELD");
+    assert_eq!(envelope["result"]["detail"]["field"], "constraints");
+}
+
+#[test]
+fn validate_rejects_malformed_constraints() {
+    let workspace = temp_workspace();
+    for (replace, with, field) in [
+        (
+            "'LN-\\d{3}'",
+            "'LN-(\\d'",
+            "constraints.columns.loan_id.pattern",
+        ),
+        ("min: 0", "min: 2000000", "constraints.columns.balance.min"),
+        (
+            "maturity_date > origination_date",
+            "maturity_date > closing_date",
+            "constraints.rules",
+        ),
+        (
+            "maturity_date > origination_date",
+            "maturity_date => origination_date",
+            "constraints.rules",
+        ),
+        (
+            "      allowed_values: [CA, NY, TX]",
+            "      allowed_values: []",
+            "constraints.columns.state",
+        ),
+    ] {
+        let profile = workspace.path().join("profile.yaml");
+        assert!(PROFILE.contains(replace));
+        write(&profile, &PROFILE.replace(replace, with));
+        let assert = profile_cmd()
+            .arg("validate")
+            .arg(&profile)
+            .arg("--json")
+            .arg("--no-witness")
+            .assert();
+        let envelope = parse_stdout_json(&assert);
+        common::assert_refusal_exit!(assert);
+        assert_eq!(envelope["result"]["code"], "E_INVALID_SCHEMA", "{with}");
+        assert_eq!(
+            envelope["result"]["detail"]["errors"][0]["field"], field,
+            "{with}"
+        );
+    }
+}
+
+#[test]
+fn freeze_hashes_constraints() {
+    let workspace = temp_workspace();
+    let mut hashes = Vec::new();
+    for (name, draft) in [
+        ("base", PROFILE.to_string()),
+        ("wide", PROFILE.replace("max: 1000000", "max: 5000000")),
+    ] {
+        let draft_path = workspace.path().join(format!("{name}.yaml"));
+        write(&draft_path, &draft);
+        let out = workspace.path().join(format!("{name}.frozen.yaml"));
+        let assert = profile_cmd()
+            .arg("freeze")
+            .arg(&draft_path)
+            .arg("--family")
+            .arg("csv.checked_tape")
+            .arg("--version")
+            .arg("0")
+            .arg("--out")
+            .arg(&out)
+            .arg("--no-witness")
+            .assert();
+        common::assert_success_exit!(assert);
+        let frozen = fs::read_to_string(&out).expect("frozen profile should be readable");
+        assert!(frozen.contains("constraints:\n  columns:\n"));
+        hashes.push(
+            frozen
+                .lines()
+                .find(|line| line.starts_with("profile_sha256:"))
+                .expect("profile_sha256 line")
+                .to_owned(),
+        );
+    }
+    assert_ne!(hashes[0], hashes[1]);
+}
+
+#[test]
+fn check_witness_records_issues_found_outcome() {
+    let workspace = temp_workspace();
+    let profile = workspace.path().join("profile.yaml");
+    write(&profile, PROFILE);
+    let ledger_path = workspace.path().join("witness.jsonl");
+
+    for (name, content) in [("clean", CLEAN), ("dirty", DIRTY)] {
+        let dataset = workspace.path().join(format!("{name}.csv"));
+        write(&dataset, content);
+        let assert = profile_cmd()
+            .env("HOME", workspace.path())
+            .env("EPISTEMIC_WITNESS", &ledger_path)
+            .arg("check")
+            .arg(&dataset)
+            .arg("--profile")
+            .arg(&profile)
+            .arg("--json")
+            .assert();
+        if name == "clean" {
+            common::assert_success_exit!(assert);
+        } else {
+            common::assert_issues_exit!(assert);
+        }
+    }
+
+    let ledger = fs::read_to_string(&ledger_path).expect("read witness ledger");
+    let outcomes = ledger
+        .lines()
+        .map(|line| {
+            let record: Value = serde_json::from_str(line).expect("json");
+            assert_eq!(record["params"]["subcommand"], "check");
+            record["outcome"].as_str().unwrap_or_default().to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(outcomes, vec!["SUCCESS", "ISSUES_FOUND"]);
+}
+
+#[test]
+fn check_emit_violations_writes_jsonl_rows() {
+    let workspace = temp_workspace();
+    let profile = workspace.path().join("profile.yaml");
+    write(&profile, PROFILE);
+    let dataset = workspace.path().join("tape.csv");
+    write(&dataset, DIRTY);
+    let report = workspace.path().join("violations.jsonl");
+
+    let assert = profile_cmd()
+        .arg("check")
+        .arg(&dataset)
+        .arg("--profile")
+        .arg(&profile)
+        .arg("--emit-violations")
+        .arg(&report)
+        .arg("--json")
+        .arg("--explicit")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    let written = fs::read_to_string(&report).expect("violation report should be written");
+    let records = written
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).expect("json line"))
+        .collect::<Vec<_>>();
+    assert_eq!(records.len(), 8);
+    assert_eq!(
+        records[0],
+        json!({
+            "row": 2,
+            "key": { "loan_id": "LN-001" },
+            "kind": "constraint_violation",
+            "column": "balance",
+            "rule": "range",
+            "value": "-5"
+        })
+    );
+    assert_eq!(
+        records[7],
+        json!({
+            "row": 4,
+            "key": { "loan_id": "LN-4" },
+            "kind": "rule_violation",
+            "column": null,
+            "rule": "maturity_date > origination_date",
+            "value": "2022-12-31 > 2023-01-01"
+        })
+    );
+    let violations = &envelope["result"]["violations"];
+    assert_eq!(violations["format"], "jsonl");
+    assert_eq!(violations["rows"], 8);
+    assert_eq!(
+        violations["hash"],
+        format!("blake3:{}", blake3::hash(written.as_bytes()).to_hex())
+    );
+}
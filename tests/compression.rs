@@ -0,0 +1,158 @@
+mod common;
+
+use common::{fixture_path, parse_stdout_json, profile_cmd, temp_workspace};
+use flate2::Compression as GzipLevel;
+use flate2::write::GzEncoder;
+use serde_json::{Value, json};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// A servicer tape with a one-line title above the header.
+const TAPE_CSV: &str = "Servicer Tape\nloan_id,balance\nLN-1,100\nLN-2,250.5\n";
+const LOANS_CSV: &str = "loan_id,balance\nLN-1,100\nLN-2,250.5\nLN-3,\n";
+
+fn write_gzip(path: &Path, content: &[u8]) {
+    let mut encoder = GzEncoder::new(Vec::new(), GzipLevel::default());
+    encoder.write_all(content).expect("gzip write");
+    fs::write(path, encoder.finish().expect("gzip finish")).expect("dataset write");
+}
+
+fn write_zstd(path: &Path, content: &[u8]) {
+    fs::write(path, zstd::encode_all(content, 0).expect("zstd encode")).expect("dataset write");
+}
+
+#[test]
+fn slice_and_stats_read_gzip_and_zstd_inputs() {
+    let workspace = temp_workspace();
+    let tapes = [
+        workspace.path().join("tape.csv.gz"),
+        workspace.path().join("tape.csv.zst"),
+    ];
+    write_gzip(&tapes[0], TAPE_CSV.as_bytes());
+    write_zstd(&tapes[1], TAPE_CSV.as_bytes());
+    let loans = [
+        workspace.path().join("loans.csv.gz"),
+        workspace.path().join("loans.csv.zst"),
+    ];
+    write_gzip(&loans[0], LOANS_CSV.as_bytes());
+    write_zstd(&loans[1], LOANS_CSV.as_bytes());
+
+    for (tape, dataset) in tapes.iter().zip(&loans) {
+        let slice = profile_cmd()
+            .arg("slice")
+            .arg(tape)
+            .arg("--header-at-row")
+            .arg("2")
+            .arg("--json")
+            .arg("--explicit")
+            .arg("--no-witness")
+            .assert();
+        let envelope = parse_stdout_json(&slice);
+        common::assert_success_exit!(slice);
+        assert_eq!(
+            envelope["result"]["slice_csv"],
+            "loan_id,balance\nLN-1,100\nLN-2,250.5\n"
+        );
+
+        let stats = profile_cmd()
+            .arg("stats")
+            .arg(dataset)
+            .arg("--json")
+            .arg("--no-witness")
+            .assert();
+        let envelope = parse_stdout_json(&stats);
+        common::assert_success_exit!(stats);
+        assert_eq!(envelope["result"]["row_count"], 3);
+        assert_eq!(
+            envelope["result"]["columns"][1]["null_rate"],
+            json!(1.0 / 3.0)
+        );
+    }
+}
+
+#[test]
+fn draft_init_infers_format_beneath_the_compression_suffix() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("events.jsonl.gz");
+    write_gzip(
+        &dataset,
+        b"{\"event_id\":\"E1\",\"amount\":10}\n{\"event_id\":\"E2\",\"amount\":12}\n",
+    );
+    let out = workspace.path().join("draft.yaml");
+
+    let assert = profile_cmd()
+        .arg("draft")
+        .arg("init")
+        .arg(&dataset)
+        .arg("--key")
+        .arg("auto")
+        .arg("--out")
+        .arg(&out)
+        .arg("--json")
+        .assert();
+    common::assert_success_exit!(assert);
+
+    let draft = fs::read_to_string(&out).expect("read draft");
+    assert!(draft.contains("format: jsonl\n"));
+    assert!(draft.contains("key:\n- event_id\ninclude_columns:\n- amount\n- event_id\n"));
+}
+
+#[test]
+fn witness_records_stored_and_decompressed_hashes() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.csv.gz");
+    let content = b"loan_id,balance\nLN-1,100\n";
+    write_gzip(&dataset, content);
+    let ledger_path = workspace.path().join("witness.jsonl");
+
+    let assert = profile_cmd()
+        .env("HOME", workspace.path())
+        .env("EPISTEMIC_WITNESS", &ledger_path)
+        .arg("suggest-key")
+        .arg(&dataset)
+        .arg("--json")
+        .assert();
+    common::assert_success_exit!(assert);
+
+    let ledger = fs::read_to_string(&ledger_path).expect("read witness ledger");
+    let record: Value = serde_json::from_str(ledger.lines().next().expect("record")).expect("json");
+    let stored = fs::read(&dataset).expect("read dataset");
+    assert_eq!(
+        record["inputs"][0],
+        json!({
+            "path": dataset.display().to_string(),
+            "hash": format!("blake3:{}", blake3::hash(&stored).to_hex()),
+            "bytes": stored.len(),
+            "compression": "gzip",
+            "decompressed_hash": format!("blake3:{}", blake3::hash(content).to_hex()),
+            "decompressed_bytes": content.len()
+        })
+    );
+}
+
+#[test]
+fn emit_discovery_reads_a_compressed_source_file() {
+    let workspace = temp_workspace();
+    let source = workspace.path().join("linkedin_source.csv.zst");
+    let source_bytes =
+        fs::read(fixture_path("discovery/linkedin_source.csv")).expect("read source fixture");
+    write_zstd(&source, &source_bytes);
+
+    let assert = profile_cmd()
+        .arg("emit-discovery")
+        .arg(fixture_path("discovery/linkedin_sliced.csv"))
+        .arg("--source-file")
+        .arg(&source)
+        .arg("--skip-rows")
+        .arg("3")
+        .arg("--source-kind")
+        .arg("linkedin_export")
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(envelope["result"]["outcome"], "DISCOVERED");
+}
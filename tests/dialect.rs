@@ -0,0 +1,162 @@
+mod common;
+
+use common::{parse_stdout_json, profile_cmd, temp_workspace};
+use serde_json::json;
+use std::fs;
+
+#[test]
+fn stats_sniffs_semicolons_and_reports_the_dialect() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.csv");
+    fs::write(
+        &dataset,
+        "loan_id;balance;note\nLN-1;100,50;\"late; cured\"\nLN-2;250,00;\n",
+    )
+    .expect("dataset write");
+
+    let assert = profile_cmd()
+        .arg("stats")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--explicit")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        envelope["result"]["dialect"],
+        json!({
+            "delimiter": ";",
+            "quote": "\"",
+            "has_header": true,
+            "delimiter_source": "sniffed"
+        })
+    );
+    assert_eq!(envelope["result"]["column_count"], 3);
+    assert_eq!(envelope["result"]["columns"][2]["example"], "late; cured");
+}
+
+#[test]
+fn draft_init_and_suggest_key_read_tab_separated_single_quoted_files() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.txt");
+    fs::write(
+        &dataset,
+        "loan_id\tborrower\n'LN-1'\t'Smith\tJ'\n'LN-2'\t'Jones\tK'\n",
+    )
+    .expect("dataset write");
+    let out = workspace.path().join("draft.yaml");
+
+    let draft = profile_cmd()
+        .arg("draft")
+        .arg("init")
+        .arg(&dataset)
+        .arg("--out")
+        .arg(&out)
+        .arg("--json")
+        .assert();
+    let envelope = parse_stdout_json(&draft);
+    common::assert_success_exit!(draft);
+    assert_eq!(envelope["result"]["dialect"]["delimiter"], "\t");
+    assert_eq!(envelope["result"]["dialect"]["quote"], "'");
+    let yaml = fs::read_to_string(&out).expect("read draft");
+    assert!(yaml.contains("include_columns:\n- loan_id\n- borrower\n"));
+
+    let suggest = profile_cmd()
+        .arg("suggest-key")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&suggest);
+    common::assert_success_exit!(suggest);
+    assert_eq!(envelope["result"]["candidates"][0]["column"], "loan_id");
+    assert_eq!(envelope["result"]["dialect"]["quote"], "'");
+}
+
+#[test]
+fn headerless_files_number_their_columns() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("rates.csv");
+    fs::write(&dataset, "1,0.0525\n2,0.0475\n3,\n").expect("dataset write");
+
+    let assert = profile_cmd()
+        .arg("stats")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(envelope["result"]["dialect"]["has_header"], false);
+    assert_eq!(envelope["result"]["row_count"], 3);
+    assert_eq!(envelope["result"]["columns"][0]["name"], "column_1");
+    assert_eq!(envelope["result"]["columns"][1]["name"], "column_2");
+}
+
+#[test]
+fn profile_delimiter_overrides_the_sniffed_one() {
+    let workspace = temp_workspace();
+    // The draws hold commas; the declared pipe keeps each list in one column.
+    let dataset = workspace.path().join("draws.csv");
+    fs::write(&dataset, "loan_id|draws\nLN-1|100,250\nLN-2|75,80\n").expect("dataset write");
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: csv
+pre_parse:
+  slice:
+    mode: preamble_skip
+    header_at_row: 1
+    delimiter: '|'
+key:
+  - loan_id
+include_columns:
+  - loan_id
+  - draws
+",
+    )
+    .expect("profile write");
+
+    let lint = profile_cmd()
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&lint);
+    common::assert_success_exit!(lint);
+    assert_eq!(envelope["result"]["issues"], json!([]));
+    assert_eq!(
+        envelope["result"]["dialect"],
+        json!({
+            "delimiter": "|",
+            "quote": "\"",
+            "has_header": true,
+            "delimiter_source": "declared"
+        })
+    );
+
+    let slice = profile_cmd()
+        .arg("slice")
+        .arg(&dataset)
+        .arg("--profile-path")
+        .arg(&profile_path)
+        .arg("--json")
+        .arg("--explicit")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&slice);
+    common::assert_success_exit!(slice);
+    assert_eq!(
+        envelope["result"]["slice_csv"],
+        "loan_id,draws\nLN-1,\"100,250\"\nLN-2,\"75,80\"\n"
+    );
+}
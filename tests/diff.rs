@@ -154,3 +154,43 @@ include_columns:
         "identity fields must be excluded from semantic diff"
     );
 }
+
+#[test]
+fn diff_json_reports_column_equivalence_overrides() {
+    let workspace = temp_workspace();
+    let profile_a = copy_fixture(
+        "profiles/valid/frozen_complete.yaml",
+        workspace.path().join("a.yaml"),
+    );
+    let profile_b = workspace.path().join("b-column-equivalence.yaml");
+    let mut content = fs::read_to_string(&profile_a).expect("fixture profile should be readable");
+    content.push_str(
+        "\
+column_equivalence:
+  balance:
+    float_decimals: 2
+    tolerance:
+      absolute: 0.01
+",
+    );
+    fs::write(&profile_b, content).expect("comparison profile should be written");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("diff")
+        .arg(&profile_a)
+        .arg(&profile_b)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    assert_eq!(
+        envelope.pointer("/result/differences/0/field"),
+        Some(&serde_json::json!("column_equivalence"))
+    );
+    assert_eq!(
+        envelope.pointer("/result/differences/0/b_value/balance/tolerance/absolute"),
+        Some(&serde_json::json!(0.01))
+    );
+}
@@ -114,6 +114,38 @@ fn draft_init_auto_key_falls_back_to_empty_when_no_viable_candidate() {
     assert!(keys.is_empty(), "expected key to be empty fallback");
 }
 
+#[test]
+fn draft_init_auto_key_writes_the_best_composite_key() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("deals.csv");
+    fs::write(
+        &dataset,
+        "deal_id,loan_number,balance\nD1,1,100\nD1,2,100\nD2,1,250\nD2,2,300\n",
+    )
+    .expect("dataset write should succeed");
+    let out_path = workspace.path().join("auto_composite.yaml");
+
+    let assert = profile_cmd()
+        .arg("draft")
+        .arg("init")
+        .arg(&dataset)
+        .arg("--out")
+        .arg(&out_path)
+        .arg("--key")
+        .arg("auto")
+        .assert();
+    common::assert_success_exit!(assert);
+
+    let yaml = load_yaml(&out_path);
+    let keys = yaml["key"]
+        .as_sequence()
+        .expect("key should be a sequence")
+        .iter()
+        .map(|item| item.as_str().unwrap_or_default().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(keys, vec!["deal_id", "loan_number"]);
+}
+
 #[test]
 fn draft_init_auto_key_refuses_when_dataset_has_no_data_rows() {
     let workspace = temp_workspace();
@@ -239,6 +271,40 @@ fn draft_init_with_column_registry_canonicalizes_headers_and_records_registry_pa
     assert_eq!(keys, vec!["loan_id_number"]);
 }
 
+#[test]
+fn draft_init_infer_types_seeds_column_types() {
+    let workspace = temp_workspace();
+    let out_path = workspace.path().join("typed.yaml");
+
+    let assert = profile_cmd()
+        .arg("draft")
+        .arg("init")
+        .arg(fixture_path("datasets/valid/loan_tape_basic.csv"))
+        .arg("--out")
+        .arg(&out_path)
+        .arg("--infer-types")
+        .assert();
+    common::assert_success_exit!(assert);
+
+    let yaml = load_yaml(&out_path);
+    assert_eq!(yaml["column_types"]["loan_id"].as_str(), Some("string"));
+    assert_eq!(yaml["column_types"]["balance"].as_str(), Some("decimal"));
+    assert_eq!(yaml["column_types"]["rate"].as_str(), Some("decimal"));
+    assert_eq!(
+        yaml["column_types"]["property_type"].as_str(),
+        Some("string")
+    );
+
+    let lint = profile_cmd()
+        .arg("--no-witness")
+        .arg("lint")
+        .arg(&out_path)
+        .arg("--against")
+        .arg(fixture_path("datasets/valid/loan_tape_basic.csv"))
+        .assert();
+    common::assert_success_exit!(lint);
+}
+
 fn load_yaml(path: &std::path::Path) -> YamlValue {
     let content = fs::read_to_string(path).expect("generated YAML should be readable");
     serde_yaml::from_str(&content).expect("generated YAML should parse")
@@ -0,0 +1,251 @@
+mod common;
+
+use common::{parse_stdout_json, profile_cmd, temp_workspace};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DRAFT: &str = "\
+schema_version: 1
+status: draft
+format: csv
+key:
+  - loan_id
+include_columns:
+  - loan_id
+  - balance
+  - state
+column_types:
+  balance: decimal
+drift:
+  null_rate_delta: 0.1
+  psi: 0.25
+  new_value_rate: 0.05
+";
+
+/// `rows` loans with balances starting at `offset` and states cycling through `states`.
+fn write_tape(path: &Path, rows: usize, offset: usize, states: &[&str]) {
+    let mut content = String::from("loan_id,balance,state\n");
+    for row in 0..rows {
+        content.push_str(&format!(
+            "LN-{row:04},{}.00,{}\n",
+            offset + row * 10,
+            states[row % states.len()]
+        ));
+    }
+    fs::write(path, content).expect("dataset write should succeed");
+}
+
+fn freeze(workspace: &Path, name: &str, draft: &str) -> PathBuf {
+    let draft_path = workspace.join(format!("{name}.yaml"));
+    fs::write(&draft_path, draft).expect("draft write should succeed");
+    let frozen = workspace.join(format!("{name}.frozen.yaml"));
+    let assert = profile_cmd()
+        .arg("freeze")
+        .arg(&draft_path)
+        .arg("--family")
+        .arg("csv.drift_tape")
+        .arg("--version")
+        .arg("0")
+        .arg("--out")
+        .arg(&frozen)
+        .arg("--no-witness")
+        .assert();
+    common::assert_success_exit!(assert);
+    frozen
+}
+
+fn save_baseline(dataset: &Path, profile: &Path, baseline: &Path) -> Value {
+    let assert = profile_cmd()
+        .arg("stats")
+        .arg(dataset)
+        .arg("--profile")
+        .arg(profile)
+        .arg("--save-baseline")
+        .arg(baseline)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+    envelope
+}
+
+fn drift(dataset: &Path, baseline: &Path) -> assert_cmd::assert::Assert {
+    profile_cmd()
+        .arg("drift")
+        .arg(dataset)
+        .arg("--baseline")
+        .arg(baseline)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert()
+}
+
+#[test]
+fn drift_is_clean_against_the_baselined_dataset() {
+    let workspace = temp_workspace();
+    let frozen = freeze(workspace.path(), "tape", DRAFT);
+    let dataset = workspace.path().join("march.csv");
+    write_tape(&dataset, 200, 100, &["CA", "NY", "TX"]);
+    let baseline = workspace.path().join("march.baseline.json");
+
+    let envelope = save_baseline(&dataset, &frozen, &baseline);
+    let saved: Value =
+        serde_json::from_str(&fs::read_to_string(&baseline).expect("baseline should be written"))
+            .expect("baseline should be JSON");
+    assert_eq!(saved["version"], "profile.baseline.v0");
+    assert_eq!(saved["row_count"], 200);
+    assert_eq!(
+        envelope["result"]["baseline"]["profile_sha256"],
+        saved["profile_sha256"]
+    );
+    let columns = saved["columns"].as_array().expect("baseline columns");
+    assert_eq!(columns.len(), 3);
+    assert!(columns[1]["numeric"]["edges"].is_array());
+    assert_eq!(columns[2]["categories"]["CA"], json!(67.0 / 200.0));
+
+    let assert = drift(&dataset, &baseline);
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+    assert_eq!(envelope["subcommand"], "drift");
+    assert_eq!(envelope["result"]["issues"], json!([]));
+    assert_eq!(envelope["result"]["row_count"], 200);
+    let state = &envelope["result"]["columns"][2];
+    assert_eq!(state["psi"], 0.0);
+    assert_eq!(state["new_values"]["count"], 0);
+    assert_eq!(envelope["result"]["columns"][1]["ks"], 0.0);
+}
+
+#[test]
+fn drift_reports_shifted_distributions_and_new_values() {
+    let workspace = temp_workspace();
+    let frozen = freeze(workspace.path(), "tape", DRAFT);
+    let march = workspace.path().join("march.csv");
+    write_tape(&march, 200, 100, &["CA", "NY", "TX"]);
+    let baseline = workspace.path().join("march.baseline.json");
+    save_baseline(&march, &frozen, &baseline);
+
+    let april = workspace.path().join("april.csv");
+    write_tape(&april, 200, 1500, &["CA", "NY", "TX", "WA"]);
+
+    let assert = profile_cmd()
+        .arg("drift")
+        .arg(&april)
+        .arg("--baseline")
+        .arg(&baseline)
+        .arg("--json")
+        .arg("--explicit")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    let flagged = envelope["result"]["issues"]
+        .as_array()
+        .expect("issues array")
+        .iter()
+        .map(|issue| {
+            assert_eq!(issue["kind"], "drift_exceeded");
+            (
+                issue["column"].as_str().unwrap_or_default(),
+                issue["measure"].as_str().unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        flagged,
+        vec![
+            ("balance", "psi"),
+            ("state", "psi"),
+            ("state", "new_value_rate"),
+        ]
+    );
+    let state = &envelope["result"]["columns"][2];
+    assert_eq!(state["new_values"]["count"], 1);
+    assert_eq!(state["new_values"]["rate"], 0.25);
+    assert_eq!(state["new_values"]["values"], json!(["WA"]));
+    // 140 of the 200 April balances lie above the March maximum.
+    let ks = envelope["result"]["columns"][1]["ks"]
+        .as_f64()
+        .expect("balance ks");
+    assert!((ks - 0.7).abs() < 1e-9);
+}
+
+#[test]
+fn drift_refuses_when_profile_hash_differs_from_baseline() {
+    let workspace = temp_workspace();
+    let frozen = freeze(workspace.path(), "tape", DRAFT);
+    let dataset = workspace.path().join("march.csv");
+    write_tape(&dataset, 20, 100, &["CA", "NY"]);
+    let baseline = workspace.path().join("march.baseline.json");
+    save_baseline(&dataset, &frozen, &baseline);
+
+    let other = freeze(
+        workspace.path(),
+        "strict",
+        &DRAFT.replace("psi: 0.25", "psi: 0.1"),
+    );
+    let assert = profile_cmd()
+        .arg("drift")
+        .arg(&dataset)
+        .arg("--baseline")
+        .arg(&baseline)
+        .arg("--profile")
+        .arg(&other)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+    assert_eq!(envelope["result"]["code"], "E_INVALID_SCHEMA");
+    assert_eq!(
+        envelope["result"]["detail"]["errors"][0]["field"],
+        "profile_sha256"
+    );
+}
+
+#[test]
+fn stats_refuses_to_save_baseline_without_frozen_profile() {
+    let workspace = temp_workspace();
+    let draft = workspace.path().join("draft.yaml");
+    fs::write(&draft, DRAFT).expect("draft write should succeed");
+    let dataset = workspace.path().join("march.csv");
+    write_tape(&dataset, 20, 100, &["CA", "NY"]);
+    let baseline = workspace.path().join("march.baseline.json");
+
+    let assert = profile_cmd()
+        .arg("stats")
+        .arg(&dataset)
+        .arg("--profile")
+        .arg(&draft)
+        .arg("--save-baseline")
+        .arg(&baseline)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+    assert_eq!(envelope["result"]["code"], "E_INVALID_SCHEMA");
+    assert!(!baseline.exists());
+}
+
+#[test]
+fn drift_new_value_rate_counts_only_non_null_values() {
+    let workspace = temp_workspace();
+    let frozen = freeze(workspace.path(), "tape", DRAFT);
+    let march = workspace.path().join("march.csv");
+    write_tape(&march, 200, 100, &["CA", "NY", "TX"]);
+    let baseline = workspace.path().join("march.baseline.json");
+    save_baseline(&march, &frozen, &baseline);
+
+    let april = workspace.path().join("april.csv");
+    write_tape(&april, 200, 100, &["CA", "WA", "", ""]);
+
+    let assert = drift(&april, &baseline);
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+    let state = &envelope["result"]["columns"][2];
+    assert_eq!(state["new_values"]["count"], 1);
+    assert_eq!(state["new_values"]["rate"], 0.5);
+}
@@ -0,0 +1,420 @@
+mod common;
+
+use common::{copy_fixture, fixture_path, parse_stdout_json, profile_cmd, temp_workspace};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CORE: &str = "\
+schema_version: 1
+status: draft
+format: csv
+key:
+  - loan_id
+include_columns:
+  - loan_id
+  - balance
+  - state
+equivalence:
+  float_decimals: 2
+  trim_strings: true
+column_types:
+  balance: decimal
+";
+
+const VENDOR: &str = "\
+schema_version: 1
+status: draft
+format: csv
+extends: csv.core_tape.v0
+key:
+  - loan_id
+  - vendor_ref
+include_columns:
+  - state
+  - vendor_ref
+equivalence:
+  trim_strings: false
+column_types:
+  balance: string
+";
+
+fn profiles_dir(home: &Path) -> PathBuf {
+    home.join(".cmdrvl")
+        .join("config")
+        .join("profile")
+        .join("profiles")
+}
+
+fn freeze(
+    home: &Path,
+    draft: &Path,
+    family: &str,
+    out: &Path,
+) -> (assert_cmd::assert::Assert, Value) {
+    let assert = profile_cmd()
+        .env("HOME", home)
+        .arg("freeze")
+        .arg(draft)
+        .arg("--family")
+        .arg(family)
+        .arg("--version")
+        .arg("0")
+        .arg("--out")
+        .arg(out)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    (assert, envelope)
+}
+
+/// Freeze `core` as `csv.core_tape.v0` into the profile directory under `home`.
+fn freeze_parent(home: &Path, core: &str) -> String {
+    let directory = profiles_dir(home);
+    fs::create_dir_all(&directory).expect("profiles directory should be created");
+    let draft = home.join("core.yaml");
+    fs::write(&draft, core).expect("draft write should succeed");
+    let out = directory.join("csv.core_tape.v0.yaml");
+    if out.exists() {
+        fs::remove_file(&out).expect("previous parent should be removed");
+    }
+    let (assert, envelope) = freeze(home, &draft, "csv.core_tape", &out);
+    common::assert_success_exit!(assert);
+    envelope["result"]["profile_sha256"]
+        .as_str()
+        .expect("parent hash")
+        .to_string()
+}
+
+#[test]
+fn freeze_merges_parent_and_records_its_hash() {
+    let workspace = temp_workspace();
+    let home = workspace.path();
+    let parent_sha256 = freeze_parent(home, CORE);
+    let draft = home.join("vendor.yaml");
+    fs::write(&draft, VENDOR).expect("draft write should succeed");
+    let out = home.join("vendor.frozen.yaml");
+
+    let (assert, envelope) = freeze(home, &draft, "csv.vendor_tape", &out);
+    common::assert_success_exit!(assert);
+    assert_eq!(
+        envelope["result"]["extends"],
+        json!({ "profile_id": "csv.core_tape.v0", "profile_sha256": parent_sha256 })
+    );
+
+    let frozen: Value = serde_yaml::from_str(&fs::read_to_string(&out).expect("frozen profile"))
+        .expect("frozen profile should be YAML");
+    assert_eq!(frozen["extends"], "csv.core_tape.v0");
+    assert_eq!(frozen["extends_sha256"], json!(parent_sha256));
+    assert_eq!(
+        frozen["include_columns"],
+        json!(["loan_id", "balance", "state", "vendor_ref"])
+    );
+    assert_eq!(frozen["key"], json!(["loan_id", "vendor_ref"]));
+    assert_eq!(
+        frozen["equivalence"],
+        json!({ "order": "order-invariant", "float_decimals": 2, "trim_strings": false })
+    );
+    assert_eq!(frozen["column_types"], json!({ "balance": "string" }));
+}
+
+#[test]
+fn child_hash_pins_the_exact_parent() {
+    let workspace = temp_workspace();
+    let home = workspace.path();
+    let draft = home.join("vendor.yaml");
+    fs::write(&draft, VENDOR).expect("draft write should succeed");
+
+    let mut hashes = Vec::new();
+    for (name, core) in [
+        ("before", CORE.to_string()),
+        (
+            "after",
+            CORE.replace("float_decimals: 2", "float_decimals: 4"),
+        ),
+    ] {
+        let parent_sha256 = freeze_parent(home, &core);
+        let out = home.join(format!("vendor.{name}.yaml"));
+        let (assert, envelope) = freeze(home, &draft, "csv.vendor_tape", &out);
+        common::assert_success_exit!(assert);
+        assert_eq!(
+            envelope["result"]["extends"]["profile_sha256"],
+            json!(parent_sha256)
+        );
+        hashes.push(envelope["result"]["profile_sha256"].clone());
+    }
+    assert_ne!(hashes[0], hashes[1]);
+}
+
+#[test]
+fn freeze_refuses_unfrozen_or_mismatched_parent() {
+    let workspace = temp_workspace();
+    let home = workspace.path();
+
+    let draft_parent = home.join("core.yaml");
+    fs::write(&draft_parent, CORE).expect("draft write should succeed");
+    let draft = home.join("vendor.yaml");
+    fs::write(
+        &draft,
+        VENDOR.replace(
+            "extends: csv.core_tape.v0",
+            &format!("extends: {}", draft_parent.display()),
+        ),
+    )
+    .expect("draft write should succeed");
+    let (assert, envelope) = freeze(home, &draft, "csv.vendor_tape", &home.join("a.yaml"));
+    common::assert_refusal_exit!(assert);
+    assert_eq!(envelope["result"]["code"], "E_INVALID_SCHEMA");
+    assert_eq!(
+        envelope["result"]["detail"]["errors"][0]["field"],
+        "extends"
+    );
+
+    freeze_parent(home, CORE);
+    let pinned = format!(
+        "extends: csv.core_tape.v0\nextends_sha256: sha256:{}",
+        "0".repeat(64)
+    );
+    fs::write(&draft, VENDOR.replace("extends: csv.core_tape.v0", &pinned))
+        .expect("draft write should succeed");
+    let out = home.join("b.yaml");
+    let (assert, envelope) = freeze(home, &draft, "csv.vendor_tape", &out);
+    common::assert_refusal_exit!(assert);
+    assert_eq!(
+        envelope["result"]["detail"]["errors"][0]["field"],
+        "extends_sha256"
+    );
+    assert!(!out.exists());
+}
+
+#[test]
+fn lint_checks_inherited_columns_of_a_child_draft() {
+    let workspace = temp_workspace();
+    let home = workspace.path();
+    freeze_parent(home, CORE);
+    let draft = home.join("vendor.yaml");
+    fs::write(&draft, VENDOR).expect("draft write should succeed");
+    let dataset = home.join("tape.csv");
+    fs::write(&dataset, "loan_id,state,vendor_ref\nLN-1,CA,V-1\n")
+        .expect("dataset write should succeed");
+
+    let assert = profile_cmd()
+        .env("HOME", home)
+        .arg("lint")
+        .arg(&draft)
+        .arg("--against")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+    let missing = envelope["result"]["issues"]
+        .as_array()
+        .expect("issues")
+        .iter()
+        .filter(|issue| issue["kind"] == "missing_column")
+        .map(|issue| issue["column"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(missing, vec![json!("balance")]);
+}
+
+#[test]
+fn validate_rejects_extends_sha256_without_extends() {
+    let workspace = temp_workspace();
+    let draft = workspace.path().join("core.yaml");
+    fs::write(
+        &draft,
+        format!("{CORE}extends_sha256: sha256:{}\n", "0".repeat(64)),
+    )
+    .expect("draft write should succeed");
+
+    let assert = profile_cmd()
+        .env("HOME", workspace.path())
+        .arg("validate")
+        .arg(&draft)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+    assert_eq!(
+        envelope["result"]["detail"]["errors"][0]["field"],
+        "extends_sha256"
+    );
+}
+
+#[test]
+fn extends_cycle_between_drafts_is_refused() {
+    let workspace = temp_workspace();
+    let home = workspace.path();
+    let first = home.join("a.yaml");
+    let second = home.join("b.yaml");
+    for (path, parent) in [(&first, &second), (&second, &first)] {
+        fs::write(
+            path,
+            VENDOR.replace(
+                "extends: csv.core_tape.v0",
+                &format!("extends: {}", parent.display()),
+            ),
+        )
+        .expect("draft write should succeed");
+    }
+    let dataset = home.join("tape.csv");
+    fs::write(&dataset, "loan_id,state,vendor_ref\nLN-1,CA,V-1\n")
+        .expect("dataset write should succeed");
+
+    for draft in [&first, &home.join("self.yaml")] {
+        if !draft.exists() {
+            fs::write(
+                draft,
+                VENDOR.replace(
+                    "extends: csv.core_tape.v0",
+                    &format!("extends: {}", draft.display()),
+                ),
+            )
+            .expect("draft write should succeed");
+        }
+        let assert = profile_cmd()
+            .env("HOME", home)
+            .arg("lint")
+            .arg(draft)
+            .arg("--against")
+            .arg(&dataset)
+            .arg("--json")
+            .arg("--no-witness")
+            .assert();
+        let envelope = parse_stdout_json(&assert);
+        common::assert_refusal_exit!(assert);
+        assert_eq!(envelope["result"]["code"], "E_INVALID_SCHEMA");
+        assert_eq!(
+            envelope["result"]["detail"]["errors"][0]["field"],
+            "extends"
+        );
+    }
+}
+
+#[test]
+fn check_enforces_constraints_inherited_from_parent() {
+    let workspace = temp_workspace();
+    let home = workspace.path();
+    freeze_parent(
+        home,
+        &format!(
+            "{CORE}constraints:\n  columns:\n    balance:\n      min: 0\n  rules:\n    - loan_id != state\n"
+        ),
+    );
+    let draft = home.join("vendor.yaml");
+    fs::write(
+        &draft,
+        format!("{VENDOR}constraints:\n  columns:\n    vendor_ref:\n      not_null: true\n"),
+    )
+    .expect("draft write should succeed");
+    let dataset = home.join("tape.csv");
+    fs::write(
+        &dataset,
+        "loan_id,balance,state,vendor_ref\nLN-1,-5,CA,\nLN-2,10,NY,V-2\n",
+    )
+    .expect("dataset write should succeed");
+
+    let assert = profile_cmd()
+        .env("HOME", home)
+        .arg("check")
+        .arg(&dataset)
+        .arg("--profile")
+        .arg(&draft)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+    assert_eq!(
+        envelope["result"]["checked"],
+        json!({ "columns": 2, "rules": 1 })
+    );
+    let broken = envelope["result"]["issues"]
+        .as_array()
+        .expect("issues")
+        .iter()
+        .map(|issue| {
+            (
+                issue["column"].as_str().unwrap_or_default().to_string(),
+                issue["constraint"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        broken,
+        vec![
+            ("balance".to_string(), "range".to_string()),
+            ("vendor_ref".to_string(), "not_null".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn relative_parent_path_and_its_registry_resolve_from_the_child_directory() {
+    let workspace = temp_workspace();
+    let root = workspace.path();
+    for file in ["registry.json", "aliases.json"] {
+        copy_fixture(
+            &format!("registries/annex_columns_v0/{file}"),
+            root.join("p/registries/annex_columns_v0").join(file),
+        );
+    }
+    let draft_parent = root.join("p/core.yaml");
+    fs::write(
+        &draft_parent,
+        "\
+schema_version: 1
+status: draft
+format: csv
+column_registry: registries/annex_columns_v0
+key:
+  - loan_id_number
+include_columns:
+  - loan_id_number
+  - current_balance
+",
+    )
+    .expect("draft write should succeed");
+    let (assert, _) = freeze(
+        root,
+        &draft_parent,
+        "csv.annex_core",
+        &root.join("p/parent.yaml"),
+    );
+    common::assert_success_exit!(assert);
+
+    let child = root.join("c/child.yaml");
+    fs::create_dir_all(root.join("c")).expect("child directory should be created");
+    fs::write(
+        &child,
+        "schema_version: 1\nstatus: draft\nformat: csv\nextends: ../p/parent.yaml\ninclude_columns:\n  - note_rate\n",
+    )
+    .expect("draft write should succeed");
+
+    let assert = profile_cmd()
+        .env("HOME", root)
+        .current_dir(root)
+        .arg("lint")
+        .arg("c/child.yaml")
+        .arg("--against")
+        .arg(fixture_path("datasets/valid/loan_tape_alt_headers.csv"))
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+    // Registry-canonical columns all resolve; only the unscoped header is reported.
+    assert_eq!(
+        envelope["result"]["issues"],
+        json!([{
+            "kind": "unexpected_column",
+            "column": "General Property Type",
+            "position": 4,
+            "severity": "info"
+        }])
+    );
+}
@@ -0,0 +1,182 @@
+mod common;
+
+use common::{parse_stdout_json, profile_cmd, temp_workspace};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+const SPANS: &str = "\
+fixed_width:
+  columns:
+    - { name: loan_id, start: 1, length: 6 }
+    - { name: balance, start: 7, length: 10 }
+    - { name: state, start: 17, length: 2 }
+";
+
+/// A report title, then right-aligned balances; the last record is cut short after the
+/// balance.
+fn write_tape(path: &Path) {
+    fs::write(
+        path,
+        "SERVICER TAPE 2026-03\nLN-001    100.50CA\nLN-002   2250.00NY\n\nLN-003     75.25\n",
+    )
+    .expect("dataset write should succeed");
+}
+
+fn write_profile(path: &Path, spans: &str) {
+    fs::write(
+        path,
+        format!(
+            "\
+schema_version: 1
+status: draft
+format: fixed_width
+{spans}pre_parse:
+  slice:
+    mode: preamble_skip
+    skip_rows: 1
+key:
+  - loan_id
+include_columns:
+  - loan_id
+  - balance
+  - state
+column_types:
+  balance: decimal
+"
+        ),
+    )
+    .expect("profile write should succeed");
+}
+
+#[test]
+fn slice_cuts_spans_into_clean_csv() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("tape.txt");
+    write_tape(&dataset);
+    let profile_path = workspace.path().join("profile.yaml");
+    write_profile(&profile_path, SPANS);
+
+    let assert = profile_cmd()
+        .arg("slice")
+        .arg(&dataset)
+        .arg("--profile-path")
+        .arg(&profile_path)
+        .arg("--json")
+        .arg("--explicit")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        envelope["result"]["slice_csv"],
+        "loan_id,balance,state\nLN-001,100.50,CA\nLN-002,2250.00,NY\nLN-003,75.25,\n"
+    );
+    assert_eq!(envelope["result"]["rows"]["header_rows"], json!([]));
+    assert_eq!(envelope["result"]["rows"]["data_starts_at"], 2);
+}
+
+#[test]
+fn lint_and_stats_read_the_raw_fixed_width_file() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("tape.txt");
+    write_tape(&dataset);
+    let profile_path = workspace.path().join("profile.yaml");
+    write_profile(&profile_path, SPANS);
+
+    let lint = profile_cmd()
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let lint_envelope = parse_stdout_json(&lint);
+    common::assert_success_exit!(lint);
+    assert_eq!(lint_envelope["result"]["issues"], json!([]));
+
+    let stats = profile_cmd()
+        .arg("stats")
+        .arg(&dataset)
+        .arg("--profile")
+        .arg(&profile_path)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let stats_envelope = parse_stdout_json(&stats);
+    common::assert_success_exit!(stats);
+    assert_eq!(stats_envelope["result"]["row_count"], 3);
+    assert_eq!(stats_envelope["result"]["columns"][2]["name"], "state");
+}
+
+#[test]
+fn validate_refuses_overlapping_empty_and_overflowing_spans() {
+    let workspace = temp_workspace();
+    let profile_path = workspace.path().join("profile.yaml");
+
+    for spans in [
+        "fixed_width:\n  columns:\n    - { name: loan_id, start: 1, length: 6 }\n    - { name: balance, start: 6, length: 10 }\n",
+        "fixed_width:\n  columns:\n    - { name: loan_id, start: 1, length: 0 }\n",
+        "fixed_width:\n  columns:\n    - { name: loan_id, start: 18446744073709551615, length: 2 }\n",
+        "",
+    ] {
+        write_profile(&profile_path, spans);
+        let assert = profile_cmd()
+            .arg("validate")
+            .arg(&profile_path)
+            .arg("--json")
+            .assert();
+        let envelope = parse_stdout_json(&assert);
+        common::assert_refusal_exit!(assert);
+
+        assert_eq!(envelope["result"]["code"], "E_INVALID_SCHEMA");
+        assert!(
+            envelope["result"]["detail"]["errors"][0]["field"]
+                .as_str()
+                .is_some_and(|field| field.starts_with("fixed_width"))
+        );
+    }
+}
+
+#[test]
+fn freeze_hashes_column_spans() {
+    let workspace = temp_workspace();
+    let mut hashes = Vec::new();
+
+    for (name, spans) in [
+        ("narrow", SPANS.to_owned()),
+        (
+            "wide",
+            SPANS.replace("start: 17, length: 2", "start: 17, length: 3"),
+        ),
+    ] {
+        let draft = workspace.path().join(format!("{name}.yaml"));
+        write_profile(&draft, &spans);
+        let out = workspace.path().join(format!("{name}.frozen.yaml"));
+        let assert = profile_cmd()
+            .arg("freeze")
+            .arg(&draft)
+            .arg("--family")
+            .arg("fwf.servicer_tape")
+            .arg("--version")
+            .arg("0")
+            .arg("--out")
+            .arg(&out)
+            .assert();
+        common::assert_success_exit!(assert);
+
+        let frozen = fs::read_to_string(&out).expect("frozen profile should be readable");
+        assert!(frozen.contains("fixed_width:\n  columns:\n  - name: loan_id\n"));
+        hashes.push(
+            frozen
+                .lines()
+                .find(|line| line.starts_with("profile_sha256:"))
+                .expect("profile_sha256 line")
+                .to_owned(),
+        );
+    }
+
+    assert_ne!(hashes[0], hashes[1]);
+}
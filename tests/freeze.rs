@@ -282,3 +282,78 @@ include_columns:
         "expected frozen profile to retain column_registry field"
     );
 }
+
+#[test]
+fn freeze_checks_column_selectors_against_reference_dataset() {
+    let workspace = temp_workspace();
+    let draft = workspace.path().join("selectors.yaml");
+    let dataset = workspace.path().join("tape.csv");
+    fs::write(
+        &dataset,
+        "loan_id,balance_current,balance_original,rate,servicer_note\nL1,10,12,0.05,x\n",
+    )
+    .expect("dataset should be written");
+
+    let freeze = |draft_yaml: &str, out: &str, reference: bool| {
+        fs::write(&draft, draft_yaml).expect("draft should be written");
+        let mut command = profile_cmd();
+        command
+            .arg("--json")
+            .arg("--no-witness")
+            .arg("freeze")
+            .arg(&draft)
+            .arg("--family")
+            .arg("csv.servicer_tape.core")
+            .arg("--version")
+            .arg("1")
+            .arg("--out")
+            .arg(workspace.path().join(out));
+        if reference {
+            command.arg("--reference").arg(&dataset);
+        }
+        command.assert()
+    };
+    let draft_yaml = |exclude: &str| {
+        format!(
+            "schema_version: 1\nstatus: draft\nformat: csv\ninclude_columns:\n  - loan_id\n  - \"glob:balance_*\"\n  - \"re:rate|coupon\"\nexclude_columns:\n  - {exclude}\n"
+        )
+    };
+
+    let assert = freeze(&draft_yaml("balance_original"), "no-reference.yaml", false);
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+    assert_eq!(envelope["result"]["code"], "E_INVALID_SCHEMA");
+
+    let assert = freeze(&draft_yaml("\"glob:*_memo\""), "unmatched.yaml", true);
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+    assert_eq!(envelope["result"]["code"], "E_COLUMN_NOT_FOUND");
+    assert_eq!(
+        envelope["result"]["detail"]["columns"],
+        serde_json::json!(["glob:*_memo"])
+    );
+
+    let assert = freeze(&draft_yaml("balance_original"), "frozen.yaml", true);
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+    assert_eq!(
+        envelope["result"]["selectors"],
+        serde_json::json!([
+            {
+                "selector": "glob:balance_*",
+                "kind": "glob",
+                "list": "include_columns",
+                "columns": ["balance_current", "balance_original"]
+            },
+            {
+                "selector": "re:rate|coupon",
+                "kind": "regex",
+                "list": "include_columns",
+                "columns": ["rate"]
+            }
+        ])
+    );
+    let frozen = fs::read_to_string(workspace.path().join("frozen.yaml")).expect("frozen profile");
+    assert!(frozen.contains("- glob:balance_*"));
+    assert!(frozen.contains("exclude_columns:\n- balance_original"));
+}
@@ -0,0 +1,204 @@
+mod common;
+
+use common::{parse_stdout_json, profile_cmd, temp_workspace};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+/// Payment events with a nested borrower; the third event adds a servicer object and carries
+/// its amount as a string.
+fn write_events(path: &Path) {
+    fs::write(
+        path,
+        "\
+{\"event_id\":\"E1\",\"amount\":10,\"borrower\":{\"id\":\"B1\",\"state\":\"CA\"}}
+{\"event_id\":\"E2\",\"amount\":12.5,\"borrower\":{\"id\":\"B2\",\"state\":null}}
+
+{\"event_id\":\"E3\",\"amount\":\"7.25\",\"borrower\":{\"id\":\"B3\",\"state\":\"NY\"},\"servicer\":{\"region\":\"west\"}}
+",
+    )
+    .expect("dataset write should succeed");
+}
+
+#[test]
+fn draft_init_discovers_nested_paths_over_a_sample() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("events.jsonl");
+    write_events(&dataset);
+    let out = workspace.path().join("draft.yaml");
+
+    let assert = profile_cmd()
+        .arg("draft")
+        .arg("init")
+        .arg(&dataset)
+        .arg("--path-sample")
+        .arg("2")
+        .arg("--key")
+        .arg("auto")
+        .arg("--infer-types")
+        .arg("--out")
+        .arg(&out)
+        .arg("--json")
+        .assert();
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        fs::read_to_string(&out).expect("read draft"),
+        "\
+schema_version: 1
+status: draft
+format: jsonl
+equivalence:
+  float_decimals: 6
+  trim_strings: true
+key:
+- event_id
+include_columns:
+- amount
+- borrower.id
+- borrower.state
+- event_id
+column_types:
+  amount: decimal
+  borrower.id: string
+  borrower.state: string
+  event_id: string
+"
+    );
+
+    let validate = profile_cmd()
+        .arg("validate")
+        .arg(&out)
+        .arg("--json")
+        .assert();
+    common::assert_success_exit!(validate);
+}
+
+#[test]
+fn lint_resolves_pointer_paths_and_reports_missing_and_type_changed_paths() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("events.jsonl");
+    write_events(&dataset);
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: jsonl
+key:
+  - event_id
+include_columns:
+  - event_id
+  - amount
+  - /borrower/id
+  - borrower.state
+  - borrower.zip
+exclude_columns:
+  - glob:servicer.*
+",
+    )
+    .expect("profile write should succeed");
+
+    let assert = profile_cmd()
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    assert_eq!(
+        envelope["result"]["issues"],
+        json!([
+            { "kind": "missing_column", "column": "borrower.zip", "severity": "error" },
+            {
+                "kind": "path_type_changed",
+                "column": "amount",
+                "severity": "warning",
+                "observed_kinds": { "number": 2, "string": 1 }
+            }
+        ])
+    );
+}
+
+#[test]
+fn stats_reads_nested_values_with_absent_paths_as_nulls() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("events.ndjson");
+    write_events(&dataset);
+
+    let assert = profile_cmd()
+        .arg("stats")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--explicit")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(envelope["result"]["row_count"], 3);
+    let columns = &envelope["result"]["columns"];
+    assert_eq!(columns[2]["name"], "borrower.state");
+    assert_eq!(columns[2]["example"], "CA");
+    assert_eq!(columns[4]["name"], "servicer.region");
+    assert_eq!(columns[4]["null_rate"], json!(2.0 / 3.0));
+}
+
+#[test]
+fn lint_refuses_malformed_lines_with_their_line_number() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("events.jsonl");
+    fs::write(&dataset, "{\"event_id\":\"E1\"}\n\n{\"event_id\":\n").expect("dataset write");
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "schema_version: 1\nstatus: draft\nformat: jsonl\ninclude_columns:\n  - event_id\n",
+    )
+    .expect("profile write should succeed");
+
+    let assert = profile_cmd()
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+
+    assert_eq!(envelope["result"]["code"], "E_CSV_PARSE");
+    assert!(
+        envelope["result"]["detail"]["error"]
+            .as_str()
+            .is_some_and(|error| error.starts_with("line 3:"))
+    );
+}
+
+#[test]
+fn stats_refuses_dotted_keys_that_collide_with_nested_paths() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("events.jsonl");
+    fs::write(&dataset, "{\"a.b\":1}\n{\"a\":{\"b\":2}}\n").expect("dataset write");
+
+    let assert = profile_cmd()
+        .arg("stats")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+
+    assert_eq!(envelope["result"]["code"], "E_CSV_PARSE");
+    assert!(
+        envelope["result"]["detail"]["error"]
+            .as_str()
+            .is_some_and(|error| error.contains("'a.b'"))
+    );
+}
@@ -6,7 +6,7 @@ use common::{
     assert_json_envelope_shape, copy_fixture, fixture_path, parse_stdout_json, profile_cmd,
     temp_workspace,
 };
-use predicates::prelude::predicate;
+use predicates::prelude::{PredicateBooleanExt, predicate};
 
 #[test]
 fn lint_human_output_contract_for_clean_profile() {
@@ -17,7 +17,9 @@ fn lint_human_output_contract_for_clean_profile() {
         .arg(fixture_path("datasets/valid/loan_tape_basic.csv"))
         .assert()
         .code(common::EXIT_SUCCESS)
-        .stdout(predicate::str::contains("\"issues\": []"));
+        .stdout(predicate::str::contains("\"kind\": \"unexpected_column\""))
+        .stdout(predicate::str::contains("\"severity\": \"info\""))
+        .stdout(predicate::str::contains("\"severity\": \"error\"").not());
 }
 
 #[test]
@@ -48,7 +50,7 @@ fn lint_json_reports_missing_columns_as_issues_found() {
         .and_then(|r| r.get("issues"))
         .and_then(|v| v.as_array())
         .expect("result.issues should be array");
-    assert_eq!(issues.len(), 1);
+    assert_eq!(issues.len(), 2);
     assert_eq!(
         issues[0].get("kind").and_then(|v| v.as_str()),
         Some("missing_column")
@@ -61,6 +63,15 @@ fn lint_json_reports_missing_columns_as_issues_found() {
         issues[0].get("severity").and_then(|v| v.as_str()),
         Some("error")
     );
+    assert_eq!(
+        issues[1],
+        serde_json::json!({
+            "kind": "unexpected_column",
+            "column": "property_type",
+            "severity": "info",
+            "position": 3
+        })
+    );
 }
 
 #[test]
@@ -100,7 +111,7 @@ include_columns:
         .and_then(|r| r.get("issues"))
         .and_then(|v| v.as_array())
         .expect("result.issues should be array");
-    assert_eq!(issues.len(), 2);
+    assert_eq!(issues.len(), 5);
 
     assert_eq!(
         issues[0].get("kind").and_then(|v| v.as_str()),
@@ -118,6 +129,12 @@ include_columns:
         issues[1].get("column").and_then(|v| v.as_str()),
         Some("missing_key")
     );
+    assert!(
+        issues[2..].iter().all(|issue| {
+            issue.get("kind").and_then(|v| v.as_str()) == Some("unexpected_column")
+        }),
+        "header drift issues follow include and key issues"
+    );
 }
 
 #[test]
@@ -278,3 +295,508 @@ include_columns:
         .expect("result.issues should be array");
     assert!(issues.is_empty(), "expected registry-backed lint success");
 }
+
+#[test]
+fn lint_json_reports_duplicate_key_counts_without_samples_by_default() {
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("lint")
+        .arg(fixture_path("profiles/valid/draft_with_key.yaml"))
+        .arg("--against")
+        .arg(fixture_path("datasets/valid/loan_tape_duplicates.csv"))
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    let issues = envelope
+        .get("result")
+        .and_then(|r| r.get("issues"))
+        .and_then(|v| v.as_array())
+        .expect("result.issues should be array");
+    assert_eq!(issues.len(), 2);
+    assert_eq!(
+        issues[0].get("kind").and_then(|v| v.as_str()),
+        Some("unexpected_column")
+    );
+    let issues = &issues[1..];
+    assert_eq!(
+        issues[0].get("kind").and_then(|v| v.as_str()),
+        Some("duplicate_key")
+    );
+    assert_eq!(
+        issues[0].get("columns"),
+        Some(&serde_json::json!(["loan_id"]))
+    );
+    assert_eq!(
+        issues[0].get("duplicate_rows").and_then(|v| v.as_u64()),
+        Some(1)
+    );
+    assert_eq!(
+        issues[0].get("duplicated_keys").and_then(|v| v.as_u64()),
+        Some(1)
+    );
+    assert!(
+        issues[0].get("samples").is_none(),
+        "duplicate key samples should be redacted unless --explicit is set"
+    );
+}
+
+#[test]
+fn lint_json_includes_duplicate_key_samples_when_explicit() {
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("--explicit")
+        .arg("lint")
+        .arg(fixture_path("profiles/valid/draft_with_key.yaml"))
+        .arg("--against")
+        .arg(fixture_path("datasets/valid/loan_tape_duplicates.csv"))
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    assert_eq!(
+        envelope.pointer("/result/issues/1/samples"),
+        Some(&serde_json::json!([["LN-0001"]]))
+    );
+}
+
+#[test]
+fn lint_json_reports_null_composite_key_components() {
+    let workspace = temp_workspace();
+    let dataset_path = workspace.path().join("composite.csv");
+    fs::write(
+        &dataset_path,
+        "\
+deal_id,loan_number,balance
+D1,1,100
+D1,2,200
+D2,,300
+,1,400
+D2,1,500
+",
+    )
+    .expect("dataset fixture write should succeed");
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: csv
+key:
+  - deal_id
+  - loan_number
+include_columns:
+  - deal_id
+  - loan_number
+  - balance
+",
+    )
+    .expect("profile fixture write should succeed");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&dataset_path)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    let issues = envelope
+        .get("result")
+        .and_then(|r| r.get("issues"))
+        .and_then(|v| v.as_array())
+        .expect("result.issues should be array");
+    assert_eq!(issues.len(), 1, "composite tuples are otherwise unique");
+    assert_eq!(
+        issues[0].get("kind").and_then(|v| v.as_str()),
+        Some("null_key")
+    );
+    assert_eq!(issues[0].get("null_rows").and_then(|v| v.as_u64()), Some(2));
+    assert_eq!(
+        issues[0].get("null_columns"),
+        Some(&serde_json::json!([
+            { "column": "deal_id", "null_rows": 1 },
+            { "column": "loan_number", "null_rows": 1 }
+        ]))
+    );
+}
+
+#[test]
+fn lint_json_reports_type_mismatch_with_offending_percentage() {
+    let workspace = temp_workspace();
+    let dataset_path = workspace.path().join("typed.csv");
+    fs::write(
+        &dataset_path,
+        "\
+loan_id,balance,rate,origination_date
+LN-1,100.50,5,2024-01-31
+LN-2,200,6,2024-02-29
+LN-3,300.25,n/a,2024-03-31
+LN-4,,7.5,2024-04-30
+",
+    )
+    .expect("dataset fixture write should succeed");
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: csv
+include_columns:
+  - loan_id
+  - balance
+  - rate
+  - origination_date
+column_types:
+  loan_id: string
+  balance: decimal
+  rate: integer
+  origination_date: date
+",
+    )
+    .expect("profile fixture write should succeed");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&dataset_path)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    let issues = envelope
+        .get("result")
+        .and_then(|r| r.get("issues"))
+        .and_then(|v| v.as_array())
+        .expect("result.issues should be array");
+    assert_eq!(issues.len(), 1, "only rate drifted from its declared type");
+    assert_eq!(
+        issues[0],
+        serde_json::json!({
+            "kind": "type_mismatch",
+            "column": "rate",
+            "severity": "error",
+            "declared_type": "integer",
+            "observed_type": "string",
+            "offending_rows": 2,
+            "offending_percent": 50.0
+        })
+    );
+}
+
+#[test]
+fn lint_json_reports_wedged_columns_and_order_drift_when_order_sensitive() {
+    let workspace = temp_workspace();
+    let dataset_path = workspace.path().join("reordered.csv");
+    fs::write(
+        &dataset_path,
+        "\
+loan_id,rate,servicer,balance,notes
+LN-1,0.05,ACME,100.00,
+LN-2,0.06,ACME,200.00,
+",
+    )
+    .expect("dataset fixture write should succeed");
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: csv
+include_columns:
+  - loan_id
+  - balance
+  - rate
+equivalence:
+  order: order-sensitive
+",
+    )
+    .expect("profile fixture write should succeed");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&dataset_path)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    assert_eq!(
+        envelope.pointer("/result/issues"),
+        Some(&serde_json::json!([
+            {
+                "kind": "unexpected_column",
+                "column": "servicer",
+                "severity": "warning",
+                "position": 3
+            },
+            {
+                "kind": "unexpected_column",
+                "column": "notes",
+                "severity": "info",
+                "position": 5
+            },
+            {
+                "kind": "column_order_changed",
+                "severity": "warning",
+                "expected": ["loan_id", "balance", "rate"],
+                "observed": ["loan_id", "rate", "balance"]
+            }
+        ]))
+    );
+}
+
+#[test]
+fn lint_json_trailing_new_columns_alone_exit_success() {
+    let workspace = temp_workspace();
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: csv
+include_columns:
+  - loan_id
+  - balance
+  - rate
+",
+    )
+    .expect("profile fixture write should succeed");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(fixture_path("datasets/valid/loan_tape_basic.csv"))
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        envelope.get("outcome").and_then(|v| v.as_str()),
+        Some("SUCCESS")
+    );
+    assert_eq!(
+        envelope.pointer("/result/issues/0/severity"),
+        Some(&serde_json::json!("info"))
+    );
+}
+
+#[test]
+fn lint_json_applies_profile_pre_parse_to_raw_export() {
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("lint")
+        .arg(fixture_path("slice/multi_header_profile.yaml"))
+        .arg("--against")
+        .arg(fixture_path("slice/multi_header.csv"))
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        envelope.pointer("/result/issues"),
+        Some(&serde_json::json!([]))
+    );
+}
+
+#[test]
+fn lint_json_reports_columns_matched_by_selectors() {
+    let workspace = temp_workspace();
+    let profile_path = workspace.path().join("selectors.yaml");
+    let dataset = workspace.path().join("tape.csv");
+    fs::write(
+        &dataset,
+        "loan_id,balance_current,servicer_note,balance_original,rate\nL1,10,x,12,0.05\n",
+    )
+    .expect("dataset fixture write should succeed");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: csv
+key:
+  - loan_id
+include_columns:
+  - loan_id
+  - glob:balance_*
+  - \"re:coupon_.*\"
+exclude_columns:
+  - glob:servicer_*
+column_types:
+  balance_current: decimal
+",
+    )
+    .expect("profile fixture write should succeed");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&dataset)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    assert_eq!(
+        envelope["result"]["selectors"],
+        serde_json::json!([
+            {
+                "selector": "glob:balance_*",
+                "kind": "glob",
+                "list": "include_columns",
+                "columns": ["balance_current", "balance_original"]
+            },
+            {
+                "selector": "re:coupon_.*",
+                "kind": "regex",
+                "list": "include_columns",
+                "columns": []
+            },
+            {
+                "selector": "glob:servicer_*",
+                "kind": "glob",
+                "list": "exclude_columns",
+                "columns": ["servicer_note"]
+            }
+        ])
+    );
+    assert_eq!(
+        envelope["result"]["issues"],
+        serde_json::json!([
+            {
+                "kind": "unmatched_selector",
+                "selector": "re:coupon_.*",
+                "list": "include_columns",
+                "severity": "warning"
+            },
+            {
+                "kind": "unexpected_column",
+                "column": "rate",
+                "severity": "info",
+                "position": 5
+            }
+        ])
+    );
+}
+
+#[test]
+fn lint_emit_violations_writes_redacted_row_report() {
+    let workspace = temp_workspace();
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: csv
+key:
+  - loan_id
+include_columns:
+  - loan_id
+  - balance
+column_types:
+  balance: decimal
+",
+    )
+    .expect("profile write should succeed");
+    let dataset = workspace.path().join("tape.csv");
+    fs::write(
+        &dataset,
+        "loan_id,balance\nLN-1,10.5\nLN-1,n/a\n,7\nLN-2,8\n",
+    )
+    .expect("dataset write should succeed");
+    let report = workspace.path().join("violations.csv");
+
+    let assert = profile_cmd()
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&dataset)
+        .arg("--emit-violations")
+        .arg(&report)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    let written = fs::read_to_string(&report).expect("violation report should be written");
+    assert_eq!(
+        written,
+        "\
+row,key.loan_id,kind,column,rule,value
+2,,duplicate_key,,unique,
+2,,type_mismatch,balance,type:decimal,
+3,,null_key,loan_id,not_null,
+"
+    );
+    let violations = &envelope["result"]["violations"];
+    assert_eq!(violations["format"], "csv");
+    assert_eq!(violations["rows"], 3);
+    assert_eq!(
+        violations["hash"],
+        format!("blake3:{}", blake3::hash(written.as_bytes()).to_hex())
+    );
+}
+
+#[test]
+fn lint_treats_unprefixed_bracketed_headers_as_literal_names() {
+    let workspace = temp_workspace();
+    let profile_path = workspace.path().join("profile.yaml");
+    let dataset = workspace.path().join("tape.csv");
+    fs::write(&dataset, "loan_id,Balance [USD],Balance U\nLN-1,10,x\n")
+        .expect("dataset fixture write should succeed");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: csv
+include_columns:
+  - loan_id
+  - Balance [USD]
+exclude_columns:
+  - Balance U
+",
+    )
+    .expect("profile fixture write should succeed");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&dataset)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+    assert_eq!(envelope["result"]["issues"], serde_json::json!([]));
+    assert!(
+        envelope["result"]["selectors"]
+            .as_array()
+            .is_none_or(Vec::is_empty)
+    );
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::{parse_stdout_json, profile_cmd, temp_workspace};
+use serde_json::json;
+use std::fs;
+
+const NORMALIZE_PROFILE: &str = "\
+schema_version: 1
+status: draft
+format: csv
+hashing:
+  algorithm: sha256
+equivalence:
+  order: order-invariant
+  float_decimals: 6
+  trim_strings: true
+include_columns:
+  - loan_id
+  - balance
+  - status
+  - closed_on
+normalize:
+  balance:
+    null_tokens: [\"N/A\", \"-\"]
+    strip_currency: true
+    thousands_separator: \",\"
+  status:
+    case: lower
+  closed_on:
+    date_format: \"%m/%d/%Y\"
+";
+
+#[test]
+fn normalize_writes_canonical_values_and_reports_column_counts() {
+    let workspace = temp_workspace();
+    let profile = workspace.path().join("profile.yaml");
+    let dataset = workspace.path().join("loans.csv");
+    let out = workspace.path().join("normalized.csv");
+    fs::write(&profile, NORMALIZE_PROFILE).expect("write profile");
+    fs::write(
+        &dataset,
+        "loan_id,balance,status,closed_on,note\n\
+         L1,\"$1,250.00\",OPEN,01/31/2026, keep \n\
+         L2,N/A,Closed,2026-02-01,x\n\
+         L3, - ,open,,y\n",
+    )
+    .expect("write dataset");
+
+    let assert = profile_cmd()
+        .arg("normalize")
+        .arg(&dataset)
+        .arg("--profile")
+        .arg(&profile)
+        .arg("--out")
+        .arg(&out)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        fs::read_to_string(&out).expect("read normalized csv"),
+        "loan_id,balance,status,closed_on,note\n\
+         L1,1250.00,open,2026-01-31, keep \n\
+         L2,,closed,2026-02-01,x\n\
+         L3,,open,,y\n"
+    );
+    assert_eq!(envelope["subcommand"], "normalize");
+    assert_eq!(envelope["result"]["rows"], 3);
+    assert!(envelope["result"].get("normalized_csv").is_none());
+    assert_eq!(
+        envelope["result"]["columns"],
+        json!([
+            { "column": "balance", "changed_values": 1, "nulled_values": 2, "unparsed_dates": 0 },
+            { "column": "status", "changed_values": 2, "nulled_values": 0, "unparsed_dates": 0 },
+            { "column": "closed_on", "changed_values": 1, "nulled_values": 0, "unparsed_dates": 0 }
+        ])
+    );
+    assert!(
+        envelope["result"]["output_hash"]
+            .as_str()
+            .is_some_and(|hash| hash.starts_with("blake3:"))
+    );
+}
+
+#[test]
+fn normalize_reports_issues_found_for_unparseable_dates() {
+    let workspace = temp_workspace();
+    let profile = workspace.path().join("profile.yaml");
+    let dataset = workspace.path().join("loans.csv");
+    fs::write(&profile, NORMALIZE_PROFILE).expect("write profile");
+    fs::write(
+        &dataset,
+        "loan_id,balance,status,closed_on\nL1,10,open,31.01.2026\n",
+    )
+    .expect("write dataset");
+
+    let assert = profile_cmd()
+        .arg("normalize")
+        .arg(&dataset)
+        .arg("--profile")
+        .arg(&profile)
+        .arg("--json")
+        .arg("--explicit")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_issues_exit!(assert);
+
+    assert_eq!(envelope["outcome"], "ISSUES_FOUND");
+    assert_eq!(envelope["result"]["columns"][2]["unparsed_dates"], 1);
+    assert_eq!(
+        envelope["result"]["normalized_csv"],
+        "loan_id,balance,status,closed_on\nL1,10,open,31.01.2026\n"
+    );
+}
+
+#[test]
+fn normalize_refuses_when_rule_column_is_missing_from_dataset() {
+    let workspace = temp_workspace();
+    let profile = workspace.path().join("profile.yaml");
+    let dataset = workspace.path().join("loans.csv");
+    fs::write(&profile, NORMALIZE_PROFILE).expect("write profile");
+    fs::write(&dataset, "loan_id,balance,status\nL1,10,open\n").expect("write dataset");
+
+    let assert = profile_cmd()
+        .arg("normalize")
+        .arg(&dataset)
+        .arg("--profile")
+        .arg(&profile)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+
+    assert_eq!(envelope["result"]["code"], "E_COLUMN_NOT_FOUND");
+}
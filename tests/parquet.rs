@@ -0,0 +1,266 @@
+mod common;
+
+use common::{parse_stdout_json, profile_cmd, temp_workspace};
+use parquet::data_type::{BoolType, ByteArray, ByteArrayType, DoubleType, Int32Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use serde_json::json;
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::Arc;
+
+const LOANS_SCHEMA: &str = "
+message loans {
+  REQUIRED BYTE_ARRAY loan_id (UTF8);
+  OPTIONAL DOUBLE balance;
+  REQUIRED INT32 rate (DECIMAL(5,4));
+  REQUIRED INT32 as_of (DATE);
+  REQUIRED BOOLEAN active;
+}
+";
+
+/// Days from 1970-01-01 to 2026-03-31.
+const AS_OF_DAYS: i32 = 20_543;
+
+/// Two row groups; the second loan has a null balance.
+fn write_loans_parquet(path: &Path) {
+    let schema = Arc::new(parse_message_type(LOANS_SCHEMA).expect("schema"));
+    let properties = Arc::new(WriterProperties::builder().build());
+    let file = File::create(path).expect("create parquet file");
+    let mut writer = SerializedFileWriter::new(file, schema, properties).expect("writer");
+
+    for (loan_id, balance, rate, active) in
+        [("LN-1", Some(100.5), 525, true), ("LN-2", None, 610, false)]
+    {
+        let mut row_group = writer.next_row_group().expect("row group");
+
+        let mut column = row_group.next_column().expect("column").expect("loan_id");
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(&[ByteArray::from(loan_id)], None, None)
+            .expect("write loan_id");
+        column.close().expect("close loan_id");
+
+        let mut column = row_group.next_column().expect("column").expect("balance");
+        let values = balance.into_iter().collect::<Vec<f64>>();
+        let definition_levels = [i16::from(balance.is_some())];
+        column
+            .typed::<DoubleType>()
+            .write_batch(&values, Some(&definition_levels), None)
+            .expect("write balance");
+        column.close().expect("close balance");
+
+        for value in [rate, AS_OF_DAYS] {
+            let mut column = row_group.next_column().expect("column").expect("int32");
+            column
+                .typed::<Int32Type>()
+                .write_batch(&[value], None, None)
+                .expect("write int32");
+            column.close().expect("close int32");
+        }
+
+        let mut column = row_group.next_column().expect("column").expect("active");
+        column
+            .typed::<BoolType>()
+            .write_batch(&[active], None, None)
+            .expect("write active");
+        column.close().expect("close active");
+
+        row_group.close().expect("close row group");
+    }
+    writer.close().expect("close parquet file");
+}
+
+#[test]
+fn draft_init_reads_parquet_schema_and_logical_types() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.parquet");
+    write_loans_parquet(&dataset);
+    let out = workspace.path().join("draft.yaml");
+
+    let assert = profile_cmd()
+        .arg("draft")
+        .arg("init")
+        .arg(&dataset)
+        .arg("--key")
+        .arg("auto")
+        .arg("--out")
+        .arg(&out)
+        .arg("--json")
+        .assert();
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        fs::read_to_string(&out).expect("read draft"),
+        "\
+schema_version: 1
+status: draft
+format: parquet
+equivalence:
+  float_decimals: 6
+  trim_strings: true
+key:
+- loan_id
+include_columns:
+- loan_id
+- balance
+- rate
+- as_of
+- active
+column_types:
+  active: boolean
+  as_of: date
+  balance: decimal
+  loan_id: string
+  rate: decimal
+"
+    );
+}
+
+#[test]
+fn lint_and_stats_read_parquet_rows_without_csv_export() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.parquet");
+    write_loans_parquet(&dataset);
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: parquet
+key:
+  - loan_id
+include_columns:
+  - loan_id
+  - balance
+  - rate
+  - as_of
+  - active
+column_types:
+  as_of: date
+  rate: decimal
+  active: boolean
+",
+    )
+    .expect("profile write should succeed");
+
+    let lint = profile_cmd()
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let lint_envelope = parse_stdout_json(&lint);
+    common::assert_success_exit!(lint);
+    assert_eq!(lint_envelope["result"]["issues"], json!([]));
+
+    let stats = profile_cmd()
+        .arg("stats")
+        .arg(&dataset)
+        .arg("--profile")
+        .arg(&profile_path)
+        .arg("--json")
+        .arg("--explicit")
+        .arg("--no-witness")
+        .assert();
+    let stats_envelope = parse_stdout_json(&stats);
+    common::assert_success_exit!(stats);
+    assert_eq!(stats_envelope["result"]["row_count"], 2);
+    let columns = &stats_envelope["result"]["columns"];
+    assert_eq!(columns[1]["name"], "balance");
+    assert_eq!(columns[1]["null_rate"], 0.5);
+    assert_eq!(columns[2]["example"], "0.0525");
+    assert_eq!(columns[3]["example"], "2026-03-31");
+}
+
+#[test]
+fn suggest_key_ranks_parquet_columns() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.parquet");
+    write_loans_parquet(&dataset);
+
+    let assert = profile_cmd()
+        .arg("suggest-key")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(envelope["result"]["candidates"][0]["column"], "loan_id");
+}
+
+#[test]
+fn validate_refuses_pre_parse_on_parquet_profiles() {
+    let workspace = temp_workspace();
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: parquet
+pre_parse:
+  slice:
+    mode: preamble_skip
+    header_at_row: 2
+include_columns:
+  - loan_id
+",
+    )
+    .expect("profile write should succeed");
+
+    let assert = profile_cmd()
+        .arg("validate")
+        .arg(&profile_path)
+        .arg("--json")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+
+    assert_eq!(envelope["result"]["code"], "E_INVALID_SCHEMA");
+    assert_eq!(
+        envelope["result"]["detail"]["errors"][0]["field"],
+        "pre_parse"
+    );
+}
+
+#[test]
+fn out_of_range_parquet_dates_fall_back_to_the_day_count() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("dates.parquet");
+    let schema = Arc::new(
+        parse_message_type("message dates { REQUIRED INT32 as_of (DATE); }").expect("schema"),
+    );
+    let properties = Arc::new(WriterProperties::builder().build());
+    let file = File::create(&dataset).expect("create parquet file");
+    let mut writer = SerializedFileWriter::new(file, schema, properties).expect("writer");
+    let mut row_group = writer.next_row_group().expect("row group");
+    let mut column = row_group.next_column().expect("column").expect("as_of");
+    column
+        .typed::<Int32Type>()
+        .write_batch(&[i32::MAX], None, None)
+        .expect("write as_of");
+    column.close().expect("close as_of");
+    row_group.close().expect("close row group");
+    writer.close().expect("close parquet file");
+
+    let assert = profile_cmd()
+        .arg("stats")
+        .arg(&dataset)
+        .arg("--json")
+        .arg("--explicit")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+    assert_eq!(
+        envelope["result"]["columns"][0]["example"],
+        i32::MAX.to_string()
+    );
+}
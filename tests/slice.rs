@@ -315,7 +315,199 @@ fn slice_output_hash_is_reproducible_across_runs() {
 }
 
 #[test]
-fn slice_fixture_profiles_round_trip_with_lint() {
+fn slice_keeps_quoted_multi_line_fields_and_physical_row_numbers() {
+    let workspace = temp_workspace();
+    let input = workspace.path().join("multi_line.csv");
+    fs::write(
+        &input,
+        "\
+\"Vendor note spanning
+two lines\"
+
+id,comment,amount
+1,\"first line
+second line\",10.00
+2,plain,20.00
+",
+    )
+    .expect("write multi-line fixture");
+    let out = workspace.path().join("clean.csv");
+    let manifest = workspace.path().join("slice.manifest.json");
+
+    let assert = profile_cmd()
+        .arg("slice")
+        .arg(&input)
+        .arg("--mode")
+        .arg("preamble_skip")
+        .arg("--header-at-row")
+        .arg("4")
+        .arg("--out")
+        .arg(&out)
+        .arg("--emit-manifest")
+        .arg(&manifest)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        fs::read_to_string(&out).expect("read clean csv"),
+        "id,comment,amount\n1,\"first line\nsecond line\",10.00\n2,plain,20.00\n"
+    );
+    assert_eq!(
+        envelope["result"]["rows"],
+        json!({
+            "input_physical_rows": 7,
+            "header_rows": [4],
+            "unit_rows": [],
+            "data_starts_at": 5,
+            "output_data_rows": 2
+        })
+    );
+    let manifest: Value =
+        serde_json::from_str(&fs::read_to_string(&manifest).expect("read manifest"))
+            .expect("manifest json");
+    assert_eq!(
+        manifest["preamble_rows"],
+        json!([["Vendor note spanning\ntwo lines"], []])
+    );
+}
+
+#[test]
+fn slice_refuses_header_row_inside_a_quoted_field() {
+    let workspace = temp_workspace();
+    let input = workspace.path().join("multi_line.csv");
+    fs::write(&input, "\"note\nstill note\"\nid,amount\n1,2\n").expect("write multi-line fixture");
+
+    let assert = profile_cmd()
+        .arg("slice")
+        .arg(&input)
+        .arg("--mode")
+        .arg("preamble_skip")
+        .arg("--header-at-row")
+        .arg("2")
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+
+    assert_eq!(envelope["result"]["code"], json!("E_INVALID_SCHEMA"));
+    assert!(
+        envelope.to_string().contains("continues a quoted field"),
+        "refusal should explain the header row is inside a quoted field: {envelope}"
+    );
+}
+
+#[test]
+fn slice_decodes_declared_windows_1252_and_records_source_encoding() {
+    let workspace = temp_workspace();
+    let input = workspace.path().join("cp1252.csv");
+    let mut bytes = b"Export \x93quarterly\x94\nid,name,amount\n".to_vec();
+    bytes.extend_from_slice(b"1,Jos\xe9,\x8010.00\n");
+    fs::write(&input, bytes).expect("write windows-1252 fixture");
+    let out = workspace.path().join("clean.csv");
+    let manifest = workspace.path().join("slice.manifest.json");
+
+    let assert = profile_cmd()
+        .arg("slice")
+        .arg(&input)
+        .arg("--mode")
+        .arg("preamble_skip")
+        .arg("--header-at-row")
+        .arg("2")
+        .arg("--encoding")
+        .arg("windows-1252")
+        .arg("--out")
+        .arg(&out)
+        .arg("--emit-manifest")
+        .arg(&manifest)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        fs::read_to_string(&out).expect("clean csv should be utf-8"),
+        "id,name,amount\n1,Jos\u{e9},\u{20ac}10.00\n"
+    );
+    let manifest: Value =
+        serde_json::from_str(&fs::read_to_string(&manifest).expect("read manifest"))
+            .expect("manifest json");
+    assert_eq!(
+        manifest["source_encoding"],
+        json!({ "name": "windows-1252", "bom": false })
+    );
+    assert_eq!(
+        manifest["preamble_rows"],
+        json!([["Export \u{201c}quarterly\u{201d}"]])
+    );
+}
+
+#[test]
+fn slice_sniffs_utf16le_bom_over_declared_encoding() {
+    let workspace = temp_workspace();
+    let input = workspace.path().join("utf16.csv");
+    let mut bytes = vec![0xFF, 0xFE];
+    for unit in "id,city\n1,K\u{f6}ln\n".encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    fs::write(&input, bytes).expect("write utf-16 fixture");
+    let manifest = workspace.path().join("slice.manifest.json");
+
+    let assert = profile_cmd()
+        .arg("slice")
+        .arg(&input)
+        .arg("--mode")
+        .arg("preamble_skip")
+        .arg("--header-at-row")
+        .arg("1")
+        .arg("--encoding")
+        .arg("latin1")
+        .arg("--emit-manifest")
+        .arg(&manifest)
+        .arg("--no-witness")
+        .assert();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    common::assert_success_exit!(assert);
+
+    assert_eq!(stdout, "id,city\n1,K\u{f6}ln\n");
+    let manifest: Value =
+        serde_json::from_str(&fs::read_to_string(&manifest).expect("read manifest"))
+            .expect("manifest json");
+    assert_eq!(
+        manifest["source_encoding"],
+        json!({ "name": "utf-16le", "bom": true })
+    );
+}
+
+#[test]
+fn slice_refuses_unknown_encoding_label() {
+    let assert = profile_cmd()
+        .arg("slice")
+        .arg(fixture_path("slice/preamble.csv"))
+        .arg("--mode")
+        .arg("preamble_skip")
+        .arg("--header-at-row")
+        .arg("4")
+        .arg("--encoding")
+        .arg("klingon-8")
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+
+    assert_eq!(envelope["result"]["code"], json!("E_INVALID_SCHEMA"));
+    assert_eq!(
+        envelope["result"]["detail"]["errors"][0]["field"],
+        json!("pre_parse.slice.encoding")
+    );
+}
+
+#[test]
+fn slice_fixture_profiles_lint_raw_exports_through_pre_parse() {
     let workspace = temp_workspace();
     let cases = [
         ("slice/preamble.csv", "slice/preparse_profile.yaml"),
@@ -340,11 +532,12 @@ fn slice_fixture_profiles_round_trip_with_lint() {
             .assert();
         common::assert_success_exit!(slice_assert);
 
+        // lint applies the profile's pre_parse itself, so it runs against the raw export.
         let lint_assert = profile_cmd()
             .arg("lint")
             .arg(fixture_path(profile))
             .arg("--against")
-            .arg(&sliced_output)
+            .arg(fixture_path(input))
             .arg("--json")
             .arg("--no-witness")
             .assert();
@@ -382,8 +575,6 @@ fn slice_100mb_dataset_perf_smoke() {
         .arg(&large_csv)
         .arg("--mode")
         .arg("preamble_skip")
-        .arg("--skip-rows")
-        .arg("0")
         .arg("--header-at-row")
         .arg("1")
         .arg("--data-starts-at")
@@ -295,3 +295,197 @@ include_columns:
         vec!["loan_id_number", "current_balance", "note_rate"]
     );
 }
+
+#[test]
+fn stats_json_applies_profile_pre_parse_before_reading_header() {
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("stats")
+        .arg(fixture_path("slice/preamble.csv"))
+        .arg("--profile")
+        .arg(fixture_path("slice/preparse_profile.yaml"))
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        envelope.pointer("/result/row_count"),
+        Some(&serde_json::json!(2))
+    );
+    let names = envelope
+        .pointer("/result/columns")
+        .and_then(|v| v.as_array())
+        .expect("stats result should contain columns array")
+        .iter()
+        .map(|column| column["name"].as_str().unwrap_or_default())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec!["Account ID", "Account Name", "Amount", "Closed Date"]
+    );
+}
+
+#[test]
+fn stats_json_reads_utf16_bom_dataset_without_profile() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("utf16.csv");
+    let mut bytes = vec![0xFF, 0xFE];
+    for unit in "loan_id,balance\nLN-1,10\nLN-2,20\n".encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    fs::write(&dataset, bytes).expect("write utf-16 fixture");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("stats")
+        .arg(&dataset)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        envelope.pointer("/result/columns/0/name"),
+        Some(&serde_json::json!("loan_id"))
+    );
+    assert_eq!(
+        envelope.pointer("/result/row_count"),
+        Some(&serde_json::json!(2))
+    );
+}
+
+#[test]
+fn stats_json_matches_serial_counts_across_many_batches_and_columns() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("wide.csv");
+    let columns = 24;
+    let rows = 20_000;
+    let mut content = (0..columns)
+        .map(|column| format!("col_{column}"))
+        .collect::<Vec<_>>()
+        .join(",");
+    content.push('\n');
+    for row in 0..rows {
+        let cells = (0..columns)
+            .map(|column| {
+                // Column c is empty for the first c rows, then cycles through c + 1 values.
+                if row < column {
+                    String::new()
+                } else {
+                    format!("v{}", row % (column + 1))
+                }
+            })
+            .collect::<Vec<_>>();
+        content.push_str(&cells.join(","));
+        content.push('\n');
+    }
+    fs::write(&dataset, content).expect("write wide dataset");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("--explicit")
+        .arg("stats")
+        .arg(&dataset)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(envelope["result"]["row_count"], rows);
+    for column in 0..columns {
+        let stats = &envelope["result"]["columns"][column];
+        assert_eq!(
+            stats["null_rate"],
+            serde_json::json!(column as f64 / rows as f64)
+        );
+        assert_eq!(
+            stats["uniqueness"],
+            serde_json::json!((column + 1) as f64 / rows as f64)
+        );
+        assert_eq!(stats["example"], format!("v{}", column % (column + 1)));
+    }
+}
+
+#[test]
+fn stats_json_reports_rich_column_statistics() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("loans.csv");
+    let mut content = String::from("loan_id,balance,opened,status\n");
+    for index in 1..=20 {
+        // One sentinel in twenty balances still leaves the column decimal-typed.
+        let balance = if index == 20 {
+            "N/A".to_string()
+        } else {
+            format!("{}.5", index * 10)
+        };
+        let status = if index % 4 == 0 { "closed" } else { "open" };
+        content.push_str(&format!(
+            "LN-{index:02},{balance},2024-01-{index:02},{status}\n"
+        ));
+    }
+    fs::write(&dataset, content).expect("write dataset");
+
+    let run = |explicit: bool| {
+        let mut command = profile_cmd();
+        command.arg("--json").arg("--no-witness");
+        if explicit {
+            command.arg("--explicit");
+        }
+        let assert = command.arg("stats").arg(&dataset).assert();
+        let envelope = parse_stdout_json(&assert);
+        common::assert_success_exit!(assert);
+        envelope["result"]["columns"].clone()
+    };
+    let columns = run(true);
+
+    let balance = &columns[1];
+    assert_eq!(
+        balance["type"],
+        serde_json::json!({ "inferred": "decimal", "confidence": 0.95 })
+    );
+    assert_eq!(balance["numeric"]["count"], 19);
+    assert_eq!(balance["numeric"]["min"], 10.5);
+    assert_eq!(balance["numeric"]["max"], 190.5);
+    assert_eq!(balance["numeric"]["mean"], 100.5);
+    assert_eq!(balance["numeric"]["quantiles"]["p50"], 100.5);
+    let stddev = balance["numeric"]["stddev"].as_f64().expect("stddev");
+    assert!((stddev - 56.273_143_387).abs() < 1e-6, "stddev {stddev}");
+
+    let opened = &columns[2];
+    assert_eq!(opened["type"]["inferred"], "date");
+    assert_eq!(
+        opened["date_range"],
+        serde_json::json!({ "min": "2024-01-01", "max": "2024-01-20" })
+    );
+    assert_eq!(opened["numeric"], serde_json::Value::Null);
+
+    let status = &columns[3];
+    assert_eq!(
+        status["top_values"],
+        serde_json::json!([
+            { "value": "open", "count": 15, "rate": 0.75 },
+            { "value": "closed", "count": 5, "rate": 0.25 }
+        ])
+    );
+    assert_eq!(
+        status["length"],
+        serde_json::json!({
+            "min": 4,
+            "max": 6,
+            "mean": 4.5,
+            "quantiles": { "p05": 4, "p25": 4, "p50": 4, "p75": 4, "p95": 6 }
+        })
+    );
+
+    // Frequent values are redacted to their counts without --explicit.
+    let redacted = run(false);
+    assert_eq!(
+        redacted[3]["top_values"],
+        serde_json::json!([
+            { "count": 15, "rate": 0.75 },
+            { "count": 5, "rate": 0.25 }
+        ])
+    );
+    assert_eq!(redacted[1]["numeric"], balance["numeric"]);
+}
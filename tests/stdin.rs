@@ -0,0 +1,104 @@
+mod common;
+
+use common::{parse_stdout_json, profile_cmd, temp_workspace};
+use serde_json::{Value, json};
+use std::fs;
+
+const LOANS_CSV: &str = "loan_id,balance\nLN-1,100\nLN-2,250.5\nLN-3,\n";
+
+#[test]
+fn stats_and_suggest_key_read_a_piped_dataset() {
+    let stats = profile_cmd()
+        .arg("stats")
+        .arg("-")
+        .arg("--json")
+        .arg("--no-witness")
+        .write_stdin(LOANS_CSV)
+        .assert();
+    let envelope = parse_stdout_json(&stats);
+    common::assert_success_exit!(stats);
+    assert_eq!(envelope["result"]["row_count"], 3);
+    assert_eq!(envelope["result"]["columns"][0]["name"], "loan_id");
+
+    let suggest = profile_cmd()
+        .arg("suggest-key")
+        .arg("-")
+        .arg("--json")
+        .arg("--no-witness")
+        .write_stdin(LOANS_CSV)
+        .assert();
+    let envelope = parse_stdout_json(&suggest);
+    common::assert_success_exit!(suggest);
+    assert_eq!(envelope["result"]["candidates"][0]["column"], "loan_id");
+}
+
+#[test]
+fn slice_reads_stdin_and_witness_hashes_the_bytes_read() {
+    let workspace = temp_workspace();
+    let ledger_path = workspace.path().join("witness.jsonl");
+    let tape = format!("Servicer Tape\n{LOANS_CSV}");
+
+    let assert = profile_cmd()
+        .env("HOME", workspace.path())
+        .env("EPISTEMIC_WITNESS", &ledger_path)
+        .arg("slice")
+        .arg("-")
+        .arg("--header-at-row")
+        .arg("2")
+        .arg("--json")
+        .arg("--explicit")
+        .write_stdin(tape.clone())
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+    assert_eq!(envelope["result"]["slice_csv"], LOANS_CSV);
+
+    let ledger = fs::read_to_string(&ledger_path).expect("read witness ledger");
+    let record: Value = serde_json::from_str(ledger.lines().next().expect("record")).expect("json");
+    assert_eq!(
+        record["inputs"],
+        json!([{
+            "path": "stdin",
+            "hash": format!("blake3:{}", blake3::hash(tape.as_bytes()).to_hex()),
+            "bytes": tape.len()
+        }])
+    );
+}
+
+#[test]
+fn lint_and_draft_init_read_stdin() {
+    let workspace = temp_workspace();
+    let out = workspace.path().join("draft.yaml");
+    let events = "{\"event_id\":\"E1\",\"amount\":10}\n{\"event_id\":\"E2\",\"amount\":12}\n";
+
+    let draft = profile_cmd()
+        .arg("draft")
+        .arg("init")
+        .arg("-")
+        .arg("--format")
+        .arg("jsonl")
+        .arg("--key")
+        .arg("auto")
+        .arg("--out")
+        .arg(&out)
+        .arg("--json")
+        .write_stdin(events)
+        .assert();
+    common::assert_success_exit!(draft);
+    let yaml = fs::read_to_string(&out).expect("read draft");
+    assert!(yaml.contains("format: jsonl\n"));
+    assert!(yaml.contains("key:\n- event_id\n"));
+
+    let lint = profile_cmd()
+        .arg("lint")
+        .arg(&out)
+        .arg("--against")
+        .arg("-")
+        .arg("--json")
+        .arg("--no-witness")
+        .write_stdin(events)
+        .assert();
+    let envelope = parse_stdout_json(&lint);
+    common::assert_success_exit!(lint);
+    assert_eq!(envelope["result"]["issues"], json!([]));
+}
@@ -1,6 +1,10 @@
 mod common;
 
-use common::{assert_json_envelope_shape, fixture_path, parse_stdout_json, profile_cmd};
+use common::{
+    assert_json_envelope_shape, fixture_path, parse_stdout_json, profile_cmd, temp_workspace,
+};
+use serde_json::json;
+use std::fs;
 
 #[test]
 fn suggest_key_json_is_deterministic_and_applies_position_tiebreak() {
@@ -123,3 +127,173 @@ fn suggest_key_json_refuses_header_only_dataset_with_e_empty() {
         Some("no data rows")
     );
 }
+
+#[test]
+fn suggest_key_json_applies_profile_pre_parse() {
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("suggest-key")
+        .arg(fixture_path("slice/preamble.csv"))
+        .arg("--profile")
+        .arg(fixture_path("slice/preparse_profile.yaml"))
+        .arg("--top")
+        .arg("1")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_json_envelope_shape(&envelope);
+    assert_eq!(
+        envelope.pointer("/result/candidates/0/column"),
+        Some(&serde_json::json!("Account ID"))
+    );
+    assert_eq!(
+        envelope.pointer("/result/candidates/0/viable"),
+        Some(&serde_json::json!(true))
+    );
+}
+
+#[test]
+fn suggest_key_ranks_column_pairs_when_no_single_column_is_viable() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("deals.csv");
+    fs::write(
+        &dataset,
+        "deal_id,loan_number,balance,status\nD1,1,100,open\nD1,2,100,open\nD2,1,250,closed\nD2,2,300,open\n",
+    )
+    .expect("dataset write");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("suggest-key")
+        .arg(&dataset)
+        .arg("--top")
+        .arg("2")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        envelope.pointer("/result/candidates/0/viable"),
+        Some(&json!(false))
+    );
+    assert_eq!(
+        envelope.pointer("/result/composite_candidates"),
+        Some(&json!([
+            {
+                "columns": ["deal_id", "loan_number"],
+                "positions": [0, 1],
+                "uniqueness": 1.0,
+                "null_rate": 0.0,
+                "stability_score": 4,
+                "viable": true,
+                "rank": 1
+            },
+            {
+                "columns": ["loan_number", "balance"],
+                "positions": [1, 2],
+                "uniqueness": 1.0,
+                "null_rate": 0.0,
+                "stability_score": 1,
+                "viable": true,
+                "rank": 2
+            }
+        ]))
+    );
+}
+
+#[test]
+fn suggest_key_searches_triples_only_when_no_pair_is_viable() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("periods.csv");
+    fs::write(
+        &dataset,
+        "loan_id,period,tranche\nL1,2026-01,A\nL1,2026-02,A\nL1,2026-01,B\nL2,2026-01,A\n",
+    )
+    .expect("dataset write");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("suggest-key")
+        .arg(&dataset)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    let composites = envelope
+        .pointer("/result/composite_candidates")
+        .and_then(|v| v.as_array())
+        .expect("composite candidates");
+    assert_eq!(
+        composites[0]["columns"],
+        json!(["loan_id", "period", "tranche"])
+    );
+    assert_eq!(composites[0]["viable"], true);
+    assert!(
+        composites[1..]
+            .iter()
+            .all(
+                |candidate| candidate["columns"].as_array().map(Vec::len) == Some(2)
+                    && candidate["viable"] == false
+            )
+    );
+}
+
+#[test]
+fn suggest_key_skips_composites_when_a_single_column_is_viable() {
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("suggest-key")
+        .arg(fixture_path("datasets/valid/loan_tape_basic.csv"))
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        envelope.pointer("/result/composite_candidates"),
+        Some(&json!([]))
+    );
+}
+
+#[test]
+fn suggest_key_finds_viable_pairs_beyond_the_first_exact_batch() {
+    let workspace = temp_workspace();
+    let dataset = workspace.path().join("wide.csv");
+    let mut contents = String::from("a,b,c,d,e,f,cohort,seq\n");
+    for row in 0..64 {
+        let quarter = row % 4;
+        contents.push_str(&format!(
+            "{quarter},{quarter},{quarter},{quarter},{quarter},{quarter},{},{}\n",
+            row % 8,
+            row / 8
+        ));
+    }
+    fs::write(&dataset, contents).expect("dataset write");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("suggest-key")
+        .arg(&dataset)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    let composites = envelope
+        .pointer("/result/composite_candidates")
+        .and_then(|v| v.as_array())
+        .expect("composite candidates");
+    assert_eq!(composites[0]["columns"], json!(["cohort", "seq"]));
+    assert_eq!(composites[0]["viable"], true);
+    assert_eq!(
+        composites
+            .iter()
+            .filter(|candidate| candidate["viable"] == true)
+            .count(),
+        1
+    );
+}
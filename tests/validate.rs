@@ -148,3 +148,126 @@ include_columns:
     let assert = profile_cmd().arg("validate").arg(&profile_path).assert();
     common::assert_success_exit!(assert);
 }
+
+#[test]
+fn validate_refuses_column_types_outside_include_columns() {
+    let workspace = common::temp_workspace();
+    let profile_path = workspace.path().join("typed.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: csv
+include_columns:
+  - loan_id
+  - balance
+column_types:
+  balance: decimal
+  rate: decimal
+",
+    )
+    .expect("profile fixture write should succeed");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("validate")
+        .arg(&profile_path)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+
+    assert_eq!(
+        envelope
+            .pointer("/result/detail/errors/0/field")
+            .and_then(|v| v.as_str()),
+        Some("column_types")
+    );
+}
+
+#[test]
+fn validate_refuses_normalize_rules_that_cannot_apply() {
+    let workspace = common::temp_workspace();
+    for (name, normalize, field) in [
+        (
+            "outside.yaml",
+            "normalize:\n  rate:\n    case: lower\n",
+            "normalize",
+        ),
+        (
+            "separator.yaml",
+            "normalize:\n  balance:\n    thousands_separator: \"1\"\n",
+            "normalize.balance.thousands_separator",
+        ),
+        (
+            "date.yaml",
+            "normalize:\n  balance:\n    date_format: \"%Q\"\n",
+            "normalize.balance.date_format",
+        ),
+    ] {
+        let profile_path = workspace.path().join(name);
+        fs::write(
+            &profile_path,
+            format!(
+                "schema_version: 1\nstatus: draft\nformat: csv\ninclude_columns:\n  - loan_id\n  - balance\n{normalize}"
+            ),
+        )
+        .expect("profile fixture write should succeed");
+
+        let assert = profile_cmd()
+            .arg("--json")
+            .arg("--no-witness")
+            .arg("validate")
+            .arg(&profile_path)
+            .assert();
+        let envelope = parse_stdout_json(&assert);
+        common::assert_refusal_exit!(assert);
+
+        assert_eq!(
+            envelope
+                .pointer("/result/detail/errors/0/field")
+                .and_then(|v| v.as_str()),
+            Some(field)
+        );
+    }
+}
+
+#[test]
+fn validate_refuses_column_equivalence_with_negative_tolerance() {
+    let workspace = common::temp_workspace();
+    let profile_path = workspace.path().join("tolerance.yaml");
+    fs::write(
+        &profile_path,
+        "\
+schema_version: 1
+status: draft
+format: csv
+include_columns:
+  - loan_id
+  - balance
+column_equivalence:
+  balance:
+    float_decimals: 2
+    tolerance:
+      absolute: -0.01
+",
+    )
+    .expect("profile fixture write should succeed");
+
+    let assert = profile_cmd()
+        .arg("--json")
+        .arg("--no-witness")
+        .arg("validate")
+        .arg(&profile_path)
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+
+    assert_eq!(
+        envelope
+            .pointer("/result/detail/errors/0/field")
+            .and_then(|v| v.as_str()),
+        Some("column_equivalence.balance.tolerance.absolute")
+    );
+}
@@ -0,0 +1,262 @@
+mod common;
+
+use common::{parse_stdout_json, profile_cmd, temp_workspace};
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+
+/// A cover sheet followed by a `Loans` sheet with a title and blank row above the header.
+fn write_vendor_workbook(path: &Path) {
+    let mut workbook = Workbook::new();
+    let notes = workbook.add_worksheet();
+    notes.set_name("Notes").expect("sheet name");
+    notes
+        .write_string(0, 0, "Prepared for trustee")
+        .expect("cell");
+
+    let loans = workbook.add_worksheet();
+    loans.set_name("Loans").expect("sheet name");
+    loans
+        .write_string(0, 0, "Servicer Tape - March")
+        .expect("cell");
+    for (column, header) in ["loan_id", "balance", "rate", "as_of"].iter().enumerate() {
+        loans.write_string(2, column as u16, *header).expect("cell");
+    }
+    let date_format = Format::new().set_num_format("yyyy-mm-dd");
+    for (row, (loan_id, balance, rate)) in [("LN-1", 100.0, 0.0525), ("LN-2", 250.5, 0.061)]
+        .iter()
+        .enumerate()
+    {
+        let row = row as u32 + 3;
+        loans.write_string(row, 0, *loan_id).expect("cell");
+        loans.write_number(row, 1, *balance).expect("cell");
+        loans.write_number(row, 2, *rate).expect("cell");
+        let as_of = ExcelDateTime::from_ymd(2026, 3, 31).expect("date");
+        loans
+            .write_datetime_with_format(row, 3, &as_of, &date_format)
+            .expect("cell");
+    }
+    workbook.save(path).expect("workbook save");
+}
+
+fn write_xlsx_profile(path: &Path) {
+    fs::write(
+        path,
+        "\
+schema_version: 1
+status: draft
+format: xlsx
+sheet: Loans
+pre_parse:
+  slice:
+    mode: preamble_skip
+    header_at_row: 3
+key:
+  - loan_id
+include_columns:
+  - loan_id
+  - balance
+  - rate
+  - as_of
+",
+    )
+    .expect("profile write should succeed");
+}
+
+#[test]
+fn slice_reads_selected_sheet_below_preamble_rows() {
+    let workspace = temp_workspace();
+    let workbook = workspace.path().join("tape.xlsx");
+    write_vendor_workbook(&workbook);
+    let manifest = workspace.path().join("slice.manifest.json");
+
+    let assert = profile_cmd()
+        .arg("slice")
+        .arg(&workbook)
+        .arg("--sheet")
+        .arg("Loans")
+        .arg("--header-at-row")
+        .arg("3")
+        .arg("--emit-manifest")
+        .arg(&manifest)
+        .arg("--json")
+        .arg("--explicit")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        envelope["result"]["slice_csv"],
+        "loan_id,balance,rate,as_of\nLN-1,100,0.0525,2026-03-31\nLN-2,250.5,0.061,2026-03-31\n"
+    );
+    assert_eq!(envelope["result"]["rows"]["input_physical_rows"], 5);
+
+    let manifest_json: Value =
+        serde_json::from_str(&fs::read_to_string(&manifest).expect("manifest")).expect("json");
+    assert_eq!(manifest_json["sheet"], "Loans");
+    assert_eq!(manifest_json["source_encoding"], Value::Null);
+    assert_eq!(
+        manifest_json["preamble_rows"],
+        json!([["Servicer Tape - March"], []])
+    );
+}
+
+#[test]
+fn slice_refuses_unknown_sheet() {
+    let workspace = temp_workspace();
+    let workbook = workspace.path().join("tape.xlsx");
+    write_vendor_workbook(&workbook);
+
+    let assert = profile_cmd()
+        .arg("slice")
+        .arg(&workbook)
+        .arg("--sheet")
+        .arg("Payments")
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+
+    assert_eq!(envelope["result"]["code"], "E_INVALID_SCHEMA");
+}
+
+#[test]
+fn lint_stats_and_suggest_key_read_the_profile_sheet() {
+    let workspace = temp_workspace();
+    let workbook = workspace.path().join("tape.xlsx");
+    write_vendor_workbook(&workbook);
+    let profile_path = workspace.path().join("profile.yaml");
+    write_xlsx_profile(&profile_path);
+
+    let lint = profile_cmd()
+        .arg("lint")
+        .arg(&profile_path)
+        .arg("--against")
+        .arg(&workbook)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let lint_envelope = parse_stdout_json(&lint);
+    common::assert_success_exit!(lint);
+    assert_eq!(lint_envelope["result"]["issues"], json!([]));
+
+    let stats = profile_cmd()
+        .arg("stats")
+        .arg(&workbook)
+        .arg("--profile")
+        .arg(&profile_path)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let stats_envelope = parse_stdout_json(&stats);
+    common::assert_success_exit!(stats);
+    assert_eq!(stats_envelope["result"]["row_count"], 2);
+    assert_eq!(
+        stats_envelope["result"]["columns"][1]["name"],
+        json!("balance")
+    );
+
+    let suggest = profile_cmd()
+        .arg("suggest-key")
+        .arg(&workbook)
+        .arg("--profile")
+        .arg(&profile_path)
+        .arg("--json")
+        .arg("--no-witness")
+        .assert();
+    let suggest_envelope = parse_stdout_json(&suggest);
+    common::assert_success_exit!(suggest);
+    assert_eq!(
+        suggest_envelope["result"]["candidates"][0]["column"],
+        "loan_id"
+    );
+}
+
+#[test]
+fn draft_init_infers_xlsx_and_records_the_sheet() {
+    let workspace = temp_workspace();
+    let workbook_path = workspace.path().join("tape.xlsx");
+    let mut workbook = Workbook::new();
+    workbook
+        .add_worksheet()
+        .set_name("Summary")
+        .expect("sheet name");
+    let loans = workbook.add_worksheet();
+    loans.set_name("Loans").expect("sheet name");
+    loans.write_string(0, 0, "loan_id").expect("cell");
+    loans.write_string(0, 1, "balance").expect("cell");
+    loans.write_string(1, 0, "LN-1").expect("cell");
+    loans.write_number(1, 1, 10).expect("cell");
+    loans.write_string(2, 0, "LN-2").expect("cell");
+    loans.write_number(2, 1, 12.5).expect("cell");
+    workbook.save(&workbook_path).expect("workbook save");
+
+    let out = workspace.path().join("draft.yaml");
+    let assert = profile_cmd()
+        .arg("draft")
+        .arg("init")
+        .arg(&workbook_path)
+        .arg("--sheet")
+        .arg("Loans")
+        .arg("--infer-types")
+        .arg("--out")
+        .arg(&out)
+        .arg("--json")
+        .assert();
+    common::assert_success_exit!(assert);
+
+    assert_eq!(
+        fs::read_to_string(&out).expect("read draft"),
+        "\
+schema_version: 1
+status: draft
+format: xlsx
+sheet: Loans
+equivalence:
+  float_decimals: 6
+  trim_strings: true
+key: []
+include_columns:
+- loan_id
+- balance
+column_types:
+  balance: decimal
+  loan_id: string
+"
+    );
+
+    let validate = profile_cmd()
+        .arg("validate")
+        .arg(&out)
+        .arg("--json")
+        .assert();
+    common::assert_success_exit!(validate);
+}
+
+#[test]
+fn validate_rejects_sheet_on_csv_profiles() {
+    let workspace = temp_workspace();
+    let profile_path = workspace.path().join("profile.yaml");
+    fs::write(
+        &profile_path,
+        "schema_version: 1\nstatus: draft\nformat: csv\nsheet: Loans\ninclude_columns:\n  - loan_id\n",
+    )
+    .expect("profile write should succeed");
+
+    let assert = profile_cmd()
+        .arg("validate")
+        .arg(&profile_path)
+        .arg("--json")
+        .assert();
+    let envelope = parse_stdout_json(&assert);
+    common::assert_refusal_exit!(assert);
+
+    assert_eq!(envelope["result"]["code"], "E_INVALID_SCHEMA");
+    assert_eq!(
+        envelope["result"]["detail"]["errors"][0]["field"],
+        json!("sheet")
+    );
+}